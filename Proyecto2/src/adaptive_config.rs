@@ -1,19 +1,14 @@
+use crate::volume::VolumeMode;
+use std::str::FromStr;
+
 #[derive(Clone, Copy, Debug)]
 pub enum RenderQuality {
-    Low,      // 400x300
-    Medium,   // 600x450  
-    High,     // 800x600
+    Low,
+    Medium,
+    High,
 }
 
 impl RenderQuality {
-    pub fn dimensions(&self) -> (u32, u32) {
-        match self {
-            RenderQuality::Low => (400, 300),
-            RenderQuality::Medium => (600, 450),
-            RenderQuality::High => (800, 600),
-        }
-    }
-    
     pub fn max_depth(&self) -> i32 {
         match self {
             RenderQuality::Low => 3,
@@ -22,11 +17,84 @@ impl RenderQuality {
         }
     }
     
+    pub fn volume_mode(&self) -> VolumeMode {
+        match self {
+            RenderQuality::Low => VolumeMode::SingleScatterApprox,
+            RenderQuality::Medium => VolumeMode::RayMarched { steps: 8 },
+            RenderQuality::High => VolumeMode::RayMarched { steps: 16 },
+        }
+    }
+    
+    // Escala de render (% del tamaño de la ventana) asociada a cada preset
+    pub fn render_scale(&self) -> u32 {
+        match self {
+            RenderQuality::Low => 50,
+            RenderQuality::Medium => 75,
+            RenderQuality::High => 100,
+        }
+    }
+    
     pub fn description(&self) -> &str {
         match self {
-            RenderQuality::Low => "Baja (400x300)",
-            RenderQuality::Medium => "Media (600x450)",
-            RenderQuality::High => "Alta (800x600)",
+            RenderQuality::Low => "Baja (50%)",
+            RenderQuality::Medium => "Media (75%)",
+            RenderQuality::High => "Alta (100%)",
+        }
+    }
+}
+
+// Resolución de render: proporcional a la ventana o fija en pixels
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RenderResolution {
+    // Porcentaje del tamaño de la ventana (25-200)
+    Scale(u32),
+    Fixed(u32, u32),
+}
+
+impl RenderResolution {
+    pub const MIN_SCALE: u32 = 25;
+    pub const MAX_SCALE: u32 = 200;
+    
+    // Escala limitada al rango admitido
+    pub fn scale(percent: u32) -> Self {
+        RenderResolution::Scale(percent.clamp(Self::MIN_SCALE, Self::MAX_SCALE))
+    }
+    
+    // Tamaño en pixels para una ventana de window_width x window_height
+    pub fn dimensions(&self, window_width: u32, window_height: u32) -> (u32, u32) {
+        match *self {
+            RenderResolution::Scale(percent) => (
+                (window_width * percent / 100).max(1),
+                (window_height * percent / 100).max(1),
+            ),
+            RenderResolution::Fixed(width, height) => (width.max(1), height.max(1)),
+        }
+    }
+    
+    pub fn description(&self) -> String {
+        match self {
+            RenderResolution::Scale(percent) => format!("{}% de la ventana", percent),
+            RenderResolution::Fixed(width, height) => format!("{}x{} fija", width, height),
+        }
+    }
+}
+
+// "1920x1080" para una resolución fija, "150%" (o "150") para una escala
+impl FromStr for RenderResolution {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("resolución inválida '{}' (usa ANCHOxALTO o un porcentaje)", s);
+        match s.split_once('x') {
+            Some((width, height)) => {
+                let width = width.parse::<u32>().map_err(|_| invalid())?;
+                let height = height.parse::<u32>().map_err(|_| invalid())?;
+                Ok(RenderResolution::Fixed(width, height))
+            }
+            None => {
+                let percent = s.trim_end_matches('%').parse::<u32>().map_err(|_| invalid())?;
+                Ok(RenderResolution::scale(percent))
+            }
         }
     }
 }
@@ -35,16 +103,175 @@ impl RenderQuality {
 #[derive(Clone)]
 pub struct AdaptiveConfig {
     pub quality: RenderQuality,
+    // Resolución de render respecto a la ventana
+    pub resolution: RenderResolution,
     pub enable_auto_rotation: bool,
     pub rotation_speed: f32,
+    // Fotones emitidos en el pre-paso de cáusticas (0 = desactivado)
+    pub photon_count: usize,
+    // Radio de búsqueda de fotones
+    pub photon_gather_radius: f32,
+    // Tiempo objetivo por fotograma mientras la cámara se mueve (segundos)
+    pub frame_budget: f32,
 }
 
 impl AdaptiveConfig {
     pub fn performance_mode() -> Self {
         Self {
             quality: RenderQuality::Low,
+            resolution: RenderResolution::Scale(RenderQuality::Low.render_scale()),
             enable_auto_rotation: true,
             rotation_speed: 1.5, // Velocidad para mostrar la escena spa
+            photon_count: 200_000,
+            photon_gather_radius: 0.04,
+            frame_budget: 0.1,
         }
     }
-}
\ No newline at end of file
+}
+// Parámetros de render que ajusta el controlador adaptativo
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FrameSettings {
+    // Fracción de la resolución de render (1 = resolución completa)
+    pub resolution_factor: f32,
+    pub max_depth: i32,
+    pub samples: u32,
+}
+
+// Controlador que mide cada render y degrada o recupera la calidad para cumplir
+// el presupuesto de tiempo mientras la cámara se mueve; en reposo usa la calidad completa
+#[derive(Debug, Clone)]
+pub struct AdaptiveController {
+    pub enabled: bool,
+    // Tiempo objetivo por fotograma en segundos
+    pub budget: f32,
+    resolution_factor: f32,
+    max_depth: i32,
+    samples: u32,
+}
+
+impl AdaptiveController {
+    pub const MIN_RESOLUTION_FACTOR: f32 = 0.25;
+    pub const MIN_DEPTH: i32 = 1;
+    
+    pub fn new(budget: f32) -> Self {
+        AdaptiveController {
+            enabled: true,
+            budget: budget.max(1e-3),
+            // Sin límites hasta la primera medición (se recortan a la calidad completa)
+            resolution_factor: 1.0,
+            max_depth: i32::MAX,
+            samples: u32::MAX,
+        }
+    }
+    
+    // Parámetros para el siguiente render
+    pub fn settings(&self, full: FrameSettings, moving: bool) -> FrameSettings {
+        if !self.enabled || !moving {
+            return full;
+        }
+        FrameSettings {
+            resolution_factor: self.resolution_factor.min(full.resolution_factor),
+            max_depth: self.max_depth.min(full.max_depth),
+            samples: self.samples.min(full.samples),
+        }
+    }
+    
+    // Registra la duración de un render hecho en movimiento y ajusta los parámetros.
+    // Al degradar se sacrifican primero las muestras, luego la resolución y al final los
+    // rebotes; al recuperar se sigue el orden inverso
+    pub fn record(&mut self, render_time: f32, full: FrameSettings) {
+        self.resolution_factor = self.resolution_factor.min(full.resolution_factor);
+        self.max_depth = self.max_depth.min(full.max_depth);
+        self.samples = self.samples.min(full.samples);
+        
+        let ratio = render_time / self.budget;
+        if ratio > 1.1 {
+            if self.samples > 1 {
+                self.samples = (self.samples / 2).max(1);
+            } else if self.resolution_factor > Self::MIN_RESOLUTION_FACTOR {
+                // El tiempo crece con el número de pixels (cuadrado de la escala)
+                self.resolution_factor = (self.resolution_factor / ratio.sqrt()).max(Self::MIN_RESOLUTION_FACTOR);
+            } else if self.max_depth > Self::MIN_DEPTH {
+                self.max_depth -= 1;
+            }
+        } else if ratio < 0.5 {
+            if self.max_depth < full.max_depth {
+                self.max_depth += 1;
+            } else if self.resolution_factor < full.resolution_factor {
+                // Subida limitada para no rebasar el presupuesto de golpe
+                let growth = (1.0 / ratio.max(1e-3)).sqrt().min(1.25);
+                self.resolution_factor = (self.resolution_factor * growth).min(full.resolution_factor);
+            } else if self.samples < full.samples {
+                self.samples = (self.samples * 2).min(full.samples);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FULL: FrameSettings = FrameSettings { resolution_factor: 1.0, max_depth: 5, samples: 4 };
+
+    #[test]
+    fn full_quality_when_still() {
+        let mut controller = AdaptiveController::new(0.1);
+        controller.record(1.0, FULL);
+        assert_eq!(controller.settings(FULL, false), FULL);
+        assert_ne!(controller.settings(FULL, true), FULL);
+    }
+
+    #[test]
+    fn degrades_samples_then_resolution_then_depth() {
+        let mut controller = AdaptiveController::new(0.1);
+        controller.record(0.4, FULL);
+        controller.record(0.4, FULL);
+        let settings = controller.settings(FULL, true);
+        assert_eq!((settings.samples, settings.resolution_factor, settings.max_depth), (1, 1.0, 5));
+
+        controller.record(0.4, FULL);
+        assert!((controller.settings(FULL, true).resolution_factor - 0.5).abs() < 1e-6);
+
+        controller.record(0.4, FULL);
+        controller.record(0.4, FULL);
+        let settings = controller.settings(FULL, true);
+        assert_eq!(settings.resolution_factor, AdaptiveController::MIN_RESOLUTION_FACTOR);
+        assert_eq!(settings.max_depth, 4);
+    }
+
+    #[test]
+    fn recovers_when_under_budget() {
+        let mut controller = AdaptiveController::new(0.1);
+        for _ in 0..6 {
+            controller.record(1.0, FULL);
+        }
+        for _ in 0..40 {
+            controller.record(0.01, FULL);
+        }
+        assert_eq!(controller.settings(FULL, true), FULL);
+    }
+
+    #[test]
+    fn parses_fixed_and_scaled_resolutions() {
+        assert_eq!("1920x1080".parse::<RenderResolution>(), Ok(RenderResolution::Fixed(1920, 1080)));
+        assert_eq!("150%".parse::<RenderResolution>(), Ok(RenderResolution::Scale(150)));
+        assert_eq!("80".parse::<RenderResolution>(), Ok(RenderResolution::Scale(80)));
+        // Las escalas fuera de rango se limitan
+        assert_eq!("5%".parse::<RenderResolution>(), Ok(RenderResolution::Scale(RenderResolution::MIN_SCALE)));
+        assert_eq!("900".parse::<RenderResolution>(), Ok(RenderResolution::Scale(RenderResolution::MAX_SCALE)));
+        for invalid in ["", "x", "1920x", "ax1080", "1920x1080x2", "-50%", "grande"] {
+            assert!(invalid.parse::<RenderResolution>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn dimensions_follow_window_or_stay_fixed() {
+        assert_eq!(RenderResolution::Scale(50).dimensions(800, 600), (400, 300));
+        assert_eq!(RenderResolution::Scale(200).dimensions(800, 600), (1600, 1200));
+        assert_eq!(RenderResolution::Fixed(320, 240).dimensions(800, 600), (320, 240));
+        // Nunca se pide una imagen vacía
+        assert_eq!(RenderResolution::Scale(25).dimensions(2, 1), (1, 1));
+        assert_eq!(RenderResolution::Fixed(0, 0).dimensions(800, 600), (1, 1));
+    }
+}
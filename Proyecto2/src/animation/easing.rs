@@ -0,0 +1,69 @@
+// Curvas de interpolación entre fotogramas clave
+
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Easing {
+    // Mantiene el valor hasta el siguiente fotograma
+    Step,
+    #[default]
+    Linear,
+    // Aceleración y desaceleración cúbicas
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    // Spline Catmull-Rom que pasa por los fotogramas vecinos (trayectorias suaves)
+    CatmullRom,
+}
+
+impl Easing {
+    // Remapea el progreso t ∈ [0, 1] del tramo (CatmullRom se resuelve en la pista)
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Step => 0.0,
+            Easing::Linear | Easing::CatmullRom => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (2.0 - 2.0 * t).powi(3) / 2.0
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for Easing {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "step" => Ok(Easing::Step),
+            "linear" => Ok(Easing::Linear),
+            "ease_in" => Ok(Easing::EaseIn),
+            "ease_out" => Ok(Easing::EaseOut),
+            "ease_in_out" => Ok(Easing::EaseInOut),
+            "catmull_rom" | "smooth" => Ok(Easing::CatmullRom),
+            other => Err(format!("curva de interpolación desconocida '{}'", other)),
+        }
+    }
+}
+
+// Nombre usado en los archivos de escena (inverso de `FromStr`)
+impl fmt::Display for Easing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Easing::Step => "step",
+            Easing::Linear => "linear",
+            Easing::EaseIn => "ease_in",
+            Easing::EaseOut => "ease_out",
+            Easing::EaseInOut => "ease_in_out",
+            Easing::CatmullRom => "smooth",
+        };
+        f.write_str(name)
+    }
+}
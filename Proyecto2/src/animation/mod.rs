@@ -0,0 +1,13 @@
+// Módulo de animación: pistas de fotogramas clave y línea de tiempo de la escena
+
+pub mod easing;
+pub mod track;
+pub mod timeline;
+pub mod sequence;
+pub mod recorder;
+
+pub use easing::Easing;
+pub use track::{Track, Transform};
+pub use timeline::{Timeline, CameraTracks};
+pub use sequence::{SequenceConfig, render_sequence};
+pub use recorder::CameraRecorder;
@@ -0,0 +1,92 @@
+// Grabación del recorrido interactivo de la cámara como pistas de fotogramas clave
+
+use crate::camera::Camera;
+use super::Easing;
+use super::timeline::CameraTracks;
+
+#[derive(Debug, Clone)]
+pub struct CameraRecorder {
+    tracks: CameraTracks,
+    // Segundos transcurridos desde el inicio de la grabación
+    elapsed: f32,
+    next_sample: f32,
+    // Separación entre fotogramas grabados (la spline suaviza entre ellos)
+    interval: f32,
+}
+
+impl CameraRecorder {
+    pub fn new() -> Self {
+        CameraRecorder {
+            tracks: CameraTracks::default(),
+            elapsed: 0.0,
+            next_sample: 0.0,
+            interval: 0.25,
+        }
+    }
+
+    // Builder pattern para la separación entre fotogramas
+    pub fn with_interval(mut self, interval: f32) -> Self {
+        self.interval = interval.max(1e-3);
+        self
+    }
+
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    // Avanza `delta_time` segundos y guarda un fotograma si toca
+    pub fn record(&mut self, delta_time: f32, camera: &Camera) {
+        if self.elapsed >= self.next_sample {
+            self.add_key(camera);
+            self.next_sample += self.interval;
+        }
+        self.elapsed += delta_time;
+    }
+
+    // Termina la grabación con la pose final de la cámara
+    pub fn finish(mut self, camera: &Camera) -> CameraTracks {
+        self.add_key(camera);
+        self.tracks
+    }
+
+    fn add_key(&mut self, camera: &Camera) {
+        let time = self.elapsed;
+        self.tracks.position.add_key(time, camera.position, Easing::CatmullRom);
+        self.tracks.target.add_key(time, camera.target, Easing::CatmullRom);
+        self.tracks.fov.add_key(time, camera.fov, Easing::CatmullRom);
+    }
+}
+
+impl Default for CameraRecorder {
+    fn default() -> Self {
+        CameraRecorder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::Vec3;
+
+    #[test]
+    fn records_at_interval_and_on_finish() {
+        let mut camera = Camera::new(Vec3::zero(), Vec3::new(0.0, 0.0, -1.0), Vec3::up(), 60.0, 1.0);
+        let mut recorder = CameraRecorder::new().with_interval(0.25);
+        for step in 0..5 {
+            camera.look_at(Vec3::new(step as f32, 0.0, 0.0), Vec3::new(step as f32, 0.0, -1.0));
+            recorder.record(0.125, &camera);
+        }
+        camera.set_fov(40.0);
+        let tracks = recorder.finish(&camera);
+
+        // Un fotograma cada 0.25 s más la pose final
+        let times: Vec<f32> = tracks.position.keys().iter().map(|k| k.time).collect();
+        assert_eq!(times, vec![0.0, 0.25, 0.5, 0.625]);
+        let xs: Vec<f32> = tracks.position.keys().iter().map(|k| k.value.x).collect();
+        assert_eq!(xs, vec![0.0, 2.0, 4.0, 4.0]);
+        assert!(tracks.position.keys().iter().all(|k| k.easing == Easing::CatmullRom));
+        assert_eq!(tracks.target.keys().len(), 4);
+        assert_eq!(tracks.fov.keys().last().map(|k| k.value), Some(40.0));
+        assert_eq!(tracks.duration(), 0.625);
+    }
+}
@@ -0,0 +1,91 @@
+// Renderizado sin ventana de secuencias de fotogramas a archivos numerados
+
+use crate::camera::Camera;
+use crate::framebuffer::Framebuffer;
+use crate::raytracer::Raytracer;
+use crate::scene::Scene;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone)]
+pub struct SequenceConfig {
+    // Rango de fotogramas (ambos incluidos)
+    pub start: u32,
+    pub end: u32,
+    pub fps: f32,
+    // Carpeta donde se escriben frame_0000.png, frame_0001.png...
+    pub output_dir: PathBuf,
+    // Fracción del fotograma con el obturador abierto (0 = sin motion blur)
+    pub shutter: f32,
+}
+
+impl SequenceConfig {
+    pub fn new(start: u32, end: u32) -> Self {
+        SequenceConfig {
+            start,
+            end: end.max(start),
+            fps: 24.0,
+            output_dir: PathBuf::from("frames"),
+            shutter: 0.0,
+        }
+    }
+
+    // Builder pattern para los fotogramas por segundo
+    pub fn with_fps(mut self, fps: f32) -> Self {
+        self.fps = fps.max(1e-3);
+        self
+    }
+
+    // Builder pattern para la carpeta de salida
+    pub fn with_output_dir(mut self, output_dir: impl Into<PathBuf>) -> Self {
+        self.output_dir = output_dir.into();
+        self
+    }
+
+    // Builder pattern para el obturador (0.5 = obturador de 180°)
+    pub fn with_shutter(mut self, shutter: f32) -> Self {
+        self.shutter = shutter.clamp(0.0, 1.0);
+        self
+    }
+
+    // Instante de un fotograma en segundos
+    pub fn frame_time(&self, frame: u32) -> f32 {
+        frame as f32 / self.fps
+    }
+}
+
+// Anima la escena y la cámara fotograma a fotograma y guarda cada imagen.
+// Devuelve las rutas escritas
+pub fn render_sequence(
+    raytracer: &Raytracer,
+    scene: &mut Scene,
+    camera: &mut Camera,
+    config: &SequenceConfig,
+) -> Result<Vec<PathBuf>, Box<dyn std::error::Error>> {
+    std::fs::create_dir_all(&config.output_dir)?;
+    let mut framebuffer = Framebuffer::new(raytracer.width, raytracer.height);
+    let mut written = Vec::new();
+
+    // Las cáusticas dependen de dónde estén luces, objetos y olas en cada fotograma
+    let rebuild_photons = scene.has_time_dependent_photons();
+    if rebuild_photons {
+        println!("La escena cambia con el tiempo: el mapa de fotones se reconstruye en cada fotograma");
+    }
+
+    for frame in config.start..=config.end {
+        let time = config.frame_time(frame);
+        scene.animate(time, camera);
+        camera.set_shutter(time, time + config.shutter / config.fps);
+        if rebuild_photons {
+            // Fotones en la apertura del obturador
+            scene.rebuild_photon_map(time);
+        }
+
+        raytracer.render_to_framebuffer(scene, camera, &mut framebuffer);
+        let path = config.output_dir.join(format!("frame_{:04}.png", frame));
+        framebuffer.save(&path.to_string_lossy())?;
+        println!("Fotograma {} guardado en {}", frame, path.display());
+        written.push(path);
+    }
+
+    Ok(written)
+}
@@ -0,0 +1,333 @@
+// Línea de tiempo: pistas de cámara, luces, materiales y transformaciones de objetos
+
+use crate::math::{Vec3, Ray, Rng};
+use crate::camera::Camera;
+use crate::geometry::{Aabb, HitInterval, HitRecord, Hittable, Instance};
+use crate::material::Material;
+use crate::scene::Scene;
+use crate::texture::Texture;
+use super::{Easing, Track, Transform};
+use std::f32::consts::PI;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Default)]
+pub struct CameraTracks {
+    pub position: Track<Vec3>,
+    pub target: Track<Vec3>,
+    // Campo de visión vertical en grados
+    pub fov: Track<f32>,
+}
+
+impl CameraTracks {
+    pub fn is_empty(&self) -> bool {
+        self.position.is_empty() && self.target.is_empty() && self.fov.is_empty()
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.position.end_time().max(self.target.end_time()).max(self.fov.end_time())
+    }
+
+    // Coloca la cámara según las pistas (las que estén vacías no la modifican)
+    pub fn apply(&self, camera: &mut Camera, time: f32) {
+        let position = self.position.sample(time).unwrap_or(camera.position);
+        let target = self.target.sample(time).unwrap_or(camera.target);
+        camera.look_at(position, target);
+        if let Some(fov) = self.fov.sample(time) {
+            camera.set_fov(fov);
+        }
+    }
+}
+
+// Pistas de una luz de `Scene::lights` (por índice)
+#[derive(Debug, Clone, Default)]
+pub struct LightTracks {
+    pub index: usize,
+    pub position: Track<Vec3>,
+    pub color: Track<Vec3>,
+    pub intensity: Track<f32>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MaterialTracks {
+    // Reemplaza la textura por un color sólido
+    pub color: Track<Vec3>,
+    pub specular: Track<f32>,
+    pub roughness: Track<f32>,
+    pub reflectivity: Track<f32>,
+    pub transparency: Track<f32>,
+    pub emission: Track<Vec3>,
+}
+
+impl MaterialTracks {
+    pub fn is_empty(&self) -> bool {
+        self.color.is_empty()
+            && self.specular.is_empty()
+            && self.roughness.is_empty()
+            && self.reflectivity.is_empty()
+            && self.transparency.is_empty()
+            && self.emission.is_empty()
+    }
+
+    // Sobrescribe los parámetros animados del material
+    pub fn apply(&self, material: &mut Material, time: f32) {
+        if let Some(color) = self.color.sample(time) {
+            material.texture = Texture::solid_color(color);
+        }
+        if let Some(specular) = self.specular.sample(time) {
+            material.specular = specular;
+        }
+        if let Some(roughness) = self.roughness.sample(time) {
+            material.roughness = roughness;
+        }
+        if let Some(reflectivity) = self.reflectivity.sample(time) {
+            material.reflectivity = reflectivity;
+        }
+        if let Some(transparency) = self.transparency.sample(time) {
+            material.transparency = transparency;
+        }
+        if let Some(emission) = self.emission.sample(time) {
+            material.emission = emission;
+        }
+    }
+
+    fn end_time(&self) -> f32 {
+        [
+            self.color.end_time(),
+            self.specular.end_time(),
+            self.roughness.end_time(),
+            self.reflectivity.end_time(),
+            self.transparency.end_time(),
+            self.emission.end_time(),
+        ]
+        .into_iter()
+        .fold(0.0, f32::max)
+    }
+}
+
+// Pistas de un objeto con nombre (ver `Scene::add_named_object`)
+#[derive(Debug, Clone, Default)]
+pub struct ObjectTracks {
+    pub name: String,
+    // Transformación aplicada en espacio mundo sobre la posición original del objeto
+    pub transform: Track<Transform>,
+    pub material: MaterialTracks,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Timeline {
+    pub camera: CameraTracks,
+    pub lights: Vec<LightTracks>,
+    pub objects: Vec<ObjectTracks>,
+}
+
+impl Timeline {
+    pub fn new() -> Self {
+        Timeline::default()
+    }
+
+    // Vuelta completa de la cámara alrededor de `center` partiendo de `start`, en `duration` segundos
+    pub fn orbit(center: Vec3, start: Vec3, duration: f32) -> Self {
+        let offset = start - center;
+        let radius = (offset.x * offset.x + offset.z * offset.z).sqrt();
+        let start_angle = offset.z.atan2(offset.x);
+
+        let mut timeline = Timeline::new();
+        let steps = 8;
+        for i in 0..=steps {
+            let angle = start_angle + 2.0 * PI * i as f32 / steps as f32;
+            let position = center + Vec3::new(angle.cos() * radius, offset.y, angle.sin() * radius);
+            timeline.camera.position.add_key(duration * i as f32 / steps as f32, position, Easing::CatmullRom);
+        }
+        timeline.camera.target.add_key(0.0, center, Easing::Linear);
+        timeline
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.camera.is_empty()
+            && self.lights.is_empty()
+            && self.objects.is_empty()
+    }
+
+    // Indica si la animación cambia luces u objetos (no solo la cámara)
+    pub fn animates_scene(&self) -> bool {
+        !self.lights.is_empty() || !self.objects.is_empty()
+    }
+
+    // Instante del último fotograma de cualquier pista
+    pub fn duration(&self) -> f32 {
+        let camera = self.camera.duration();
+        let lights = self.lights.iter().map(|l| {
+            l.position.end_time().max(l.color.end_time()).max(l.intensity.end_time())
+        });
+        let objects = self.objects.iter().map(|o| o.transform.end_time().max(o.material.end_time()));
+        lights.chain(objects).fold(camera, f32::max)
+    }
+
+    // Pistas de la luz `index` (se crean si no existen)
+    pub fn light_mut(&mut self, index: usize) -> &mut LightTracks {
+        match self.lights.iter().position(|l| l.index == index) {
+            Some(i) => &mut self.lights[i],
+            None => {
+                self.lights.push(LightTracks { index, ..Default::default() });
+                self.lights.last_mut().unwrap()
+            }
+        }
+    }
+
+    // Pistas del objeto `name` (se crean si no existen)
+    pub fn object_mut(&mut self, name: &str) -> &mut ObjectTracks {
+        match self.objects.iter().position(|o| o.name == name) {
+            Some(i) => &mut self.objects[i],
+            None => {
+                self.objects.push(ObjectTracks { name: name.to_string(), ..Default::default() });
+                self.objects.last_mut().unwrap()
+            }
+        }
+    }
+
+    // Coloca la cámara según sus pistas
+    pub fn apply_camera(&self, camera: &mut Camera, time: f32) {
+        self.camera.apply(camera, time);
+    }
+
+    // Actualiza luces y objetos animados de la escena
+    pub fn apply_scene(&self, scene: &mut Scene, time: f32) {
+        for tracks in &self.lights {
+            scene.update_light(tracks.index, |light| {
+                if let Some(position) = tracks.position.sample(time) {
+                    light.position = position;
+                }
+                if let Some(color) = tracks.color.sample(time) {
+                    light.color = color;
+                }
+                if let Some(intensity) = tracks.intensity.sample(time) {
+                    light.intensity = intensity;
+                }
+            });
+        }
+
+        for tracks in &self.objects {
+            // Siempre se parte del objeto original para no acumular transformaciones
+            let Some(mut object) = scene.named_object(&tracks.name) else {
+                continue;
+            };
+            if !tracks.material.is_empty() {
+                let mut material = object.material().cloned().unwrap_or_else(Material::new);
+                tracks.material.apply(&mut material, time);
+                object = Arc::new(MaterialOverride { object, material });
+            }
+            if let Some(transform) = tracks.transform.sample(time) {
+                object = Arc::new(Instance::new(object, transform.to_mat4()));
+            }
+            scene.replace_named_object(&tracks.name, object);
+        }
+    }
+
+    // Aplica toda la línea de tiempo en un instante
+    pub fn apply(&self, time: f32, scene: &mut Scene, camera: &mut Camera) {
+        self.apply_camera(camera, time);
+        self.apply_scene(scene, time);
+    }
+}
+
+// Objeto con su material reemplazado por el valor animado
+#[derive(Debug)]
+struct MaterialOverride {
+    object: Arc<dyn Hittable + Send + Sync>,
+    material: Material,
+}
+
+impl Hittable for MaterialOverride {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
+        let mut hit = self.object.hit(ray, t_min, t_max)?;
+        hit.material = self.material.clone();
+        Some(hit)
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        self.object.bounding_box()
+    }
+
+    fn intervals(&self, ray: &Ray) -> Vec<HitInterval> {
+        let mut intervals = self.object.intervals(ray);
+        for interval in &mut intervals {
+            interval.enter.material = self.material.clone();
+            interval.exit.material = self.material.clone();
+        }
+        intervals
+    }
+
+    fn material(&self) -> Option<&Material> {
+        Some(&self.material)
+    }
+
+    fn surface_area(&self) -> f32 {
+        self.object.surface_area()
+    }
+
+    fn sample_surface(&self, rng: &mut Rng) -> Option<(Vec3, Vec3)> {
+        self.object.sample_surface(rng)
+    }
+
+    fn is_time_dependent(&self) -> bool {
+        self.object.is_time_dependent()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Cube;
+    use crate::lighting::Light;
+    use crate::math::Quat;
+
+    fn scene_with_named_cube() -> Scene {
+        let mut scene = Scene::new();
+        scene.add_light(Light::new(Vec3::new(0.0, 5.0, 0.0), Vec3::one(), 1.0));
+        scene.add_object(Cube::new(Vec3::new(-5.0, 0.0, 0.0), Vec3::one(), Material::new()));
+        scene.add_named_object("caja", Arc::new(Cube::new(Vec3::zero(), Vec3::one(), Material::new())));
+        scene
+    }
+
+    fn ray_down(x: f32) -> Ray {
+        Ray::new(Vec3::new(x, 10.0, 0.0), Vec3::new(0.0, -1.0, 0.0))
+    }
+
+    #[test]
+    fn apply_scene_animates_lights_and_named_objects() {
+        let mut scene = scene_with_named_cube();
+        let mut timeline = Timeline::new();
+        timeline.light_mut(0).intensity.add_key(0.0, 1.0, Easing::Linear);
+        timeline.light_mut(0).intensity.add_key(2.0, 3.0, Easing::Linear);
+        let moved = Transform::new(Vec3::new(4.0, 0.0, 0.0), Quat::identity(), Vec3::one());
+        let tracks = timeline.object_mut("caja");
+        tracks.transform.add_key(0.0, Transform::identity(), Easing::Linear);
+        tracks.transform.add_key(1.0, moved, Easing::Linear);
+        tracks.material.emission.add_key(0.0, Vec3::one(), Easing::Step);
+
+        timeline.apply_scene(&mut scene, 1.0);
+        assert!((scene.lights()[0].intensity - 2.0).abs() < 1e-5);
+        assert_eq!(scene.objects.len(), 2);
+        // El objeto animado ocupa su índice original y se movió
+        let animated = scene.objects.get(1).unwrap();
+        assert!(animated.hit(&ray_down(4.0), 1e-3, f32::INFINITY).is_some());
+        assert!(animated.hit(&ray_down(0.0), 1e-3, f32::INFINITY).is_none());
+        let hit = scene.hit(&ray_down(4.0), 1e-3, f32::INFINITY).unwrap();
+        assert!((hit.material.emission - Vec3::one()).length() < 1e-6);
+        // El material emisivo animado se registra como luz de área
+        assert_eq!(scene.emitters.len(), 1);
+
+        // Volver a evaluar parte del original: no se acumulan transformaciones
+        timeline.apply_scene(&mut scene, 1.0);
+        assert!(scene.objects.get(1).unwrap().hit(&ray_down(4.0), 1e-3, f32::INFINITY).is_some());
+        assert_eq!(scene.emitters.len(), 1);
+    }
+
+    #[test]
+    fn animates_scene_ignores_camera_only_timelines() {
+        let mut timeline = Timeline::orbit(Vec3::zero(), Vec3::new(2.0, 1.0, 0.0), 4.0);
+        assert!(!timeline.animates_scene());
+        timeline.light_mut(0);
+        assert!(timeline.animates_scene());
+    }
+}
@@ -0,0 +1,202 @@
+// Pistas de fotogramas clave para cualquier valor interpolable
+
+use crate::math::{Vec3, Quat, Mat4};
+use super::Easing;
+
+// Valores que pueden interpolarse entre fotogramas (t puede salir de [0, 1] en las splines)
+pub trait Animatable: Clone {
+    fn interpolate(&self, other: &Self, t: f32) -> Self;
+}
+
+impl Animatable for f32 {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl Animatable for Vec3 {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        self.lerp(other, t)
+    }
+}
+
+impl Animatable for Quat {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        self.slerp(other, t)
+    }
+}
+
+// Transformación descompuesta de un objeto (se interpola por componentes)
+#[derive(Debug, Clone, Copy)]
+pub struct Transform {
+    pub translation: Vec3,
+    pub rotation: Quat,
+    pub scale: Vec3,
+}
+
+impl Transform {
+    pub fn new(translation: Vec3, rotation: Quat, scale: Vec3) -> Self {
+        Transform { translation, rotation, scale }
+    }
+
+    pub fn identity() -> Self {
+        Transform::new(Vec3::zero(), Quat::identity(), Vec3::one())
+    }
+
+    pub fn to_mat4(&self) -> Mat4 {
+        Mat4::from_trs(self.translation, self.rotation, self.scale)
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform::identity()
+    }
+}
+
+impl Animatable for Transform {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        Transform {
+            translation: self.translation.lerp(&other.translation, t),
+            rotation: self.rotation.slerp(&other.rotation, t),
+            scale: self.scale.lerp(&other.scale, t),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Keyframe<T> {
+    // Instante en segundos
+    pub time: f32,
+    pub value: T,
+    // Curva del tramo que empieza en este fotograma
+    pub easing: Easing,
+}
+
+// Secuencia de fotogramas ordenada por instante
+#[derive(Debug, Clone)]
+pub struct Track<T> {
+    keys: Vec<Keyframe<T>>,
+}
+
+impl<T: Animatable> Track<T> {
+    pub fn new() -> Self {
+        Track { keys: Vec::new() }
+    }
+
+    // Builder pattern para añadir un fotograma
+    pub fn with_key(mut self, time: f32, value: T, easing: Easing) -> Self {
+        self.add_key(time, value, easing);
+        self
+    }
+
+    // Inserta un fotograma manteniendo el orden (reemplaza uno en el mismo instante)
+    pub fn add_key(&mut self, time: f32, value: T, easing: Easing) {
+        let key = Keyframe { time, value, easing };
+        match self.keys.iter().position(|k| k.time >= time) {
+            Some(i) if self.keys[i].time == time => self.keys[i] = key,
+            Some(i) => self.keys.insert(i, key),
+            None => self.keys.push(key),
+        }
+    }
+
+    pub fn keys(&self) -> &[Keyframe<T>] {
+        &self.keys
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    // Instante del último fotograma
+    pub fn end_time(&self) -> f32 {
+        self.keys.last().map_or(0.0, |k| k.time)
+    }
+
+    // Valor en un instante; fuera del rango se mantiene el fotograma extremo
+    pub fn sample(&self, time: f32) -> Option<T> {
+        let first = self.keys.first()?;
+        if time <= first.time {
+            return Some(first.value.clone());
+        }
+        let next = self.keys.partition_point(|k| k.time <= time);
+        if next >= self.keys.len() {
+            return self.keys.last().map(|k| k.value.clone());
+        }
+
+        let (a, b) = (&self.keys[next - 1], &self.keys[next]);
+        let t = (time - a.time) / (b.time - a.time);
+        if a.easing == Easing::CatmullRom {
+            // En los extremos se repite el fotograma del borde
+            let p0 = &self.keys[next.saturating_sub(2)].value;
+            let p3 = &self.keys.get(next + 1).unwrap_or(b).value;
+            Some(catmull_rom(p0, &a.value, &b.value, p3, t))
+        } else {
+            Some(a.value.interpolate(&b.value, a.easing.apply(t)))
+        }
+    }
+}
+
+impl<T: Animatable> Default for Track<T> {
+    fn default() -> Self {
+        Track::new()
+    }
+}
+
+// Catmull-Rom uniforme con el algoritmo de Barry-Goldman (solo usa interpolaciones)
+fn catmull_rom<T: Animatable>(p0: &T, p1: &T, p2: &T, p3: &T, t: f32) -> T {
+    let a1 = p0.interpolate(p1, t + 1.0);
+    let a2 = p1.interpolate(p2, t);
+    let a3 = p2.interpolate(p3, t - 1.0);
+    let b1 = a1.interpolate(&a2, (t + 1.0) / 2.0);
+    let b2 = a2.interpolate(&a3, t / 2.0);
+    b1.interpolate(&b2, t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_holds_values_outside_range() {
+        let track = Track::new().with_key(1.0, 2.0, Easing::Linear).with_key(3.0, 6.0, Easing::Linear);
+        assert_eq!(track.sample(0.0), Some(2.0));
+        assert_eq!(track.sample(5.0), Some(6.0));
+        assert_eq!(Track::<f32>::new().sample(1.0), None);
+    }
+
+    #[test]
+    fn sample_interpolates_with_easing() {
+        let linear = Track::new().with_key(0.0, 0.0, Easing::Linear).with_key(2.0, 4.0, Easing::Linear);
+        assert!((linear.sample(0.5).unwrap() - 1.0).abs() < 1e-6);
+
+        let step = Track::new().with_key(0.0, 0.0, Easing::Step).with_key(1.0, 1.0, Easing::Linear);
+        assert_eq!(step.sample(0.99), Some(0.0));
+
+        let ease_in = Track::new().with_key(0.0, 0.0, Easing::EaseIn).with_key(1.0, 1.0, Easing::Linear);
+        assert!(ease_in.sample(0.5).unwrap() < 0.5);
+    }
+
+    #[test]
+    fn keys_stay_sorted() {
+        let track = Track::new()
+            .with_key(2.0, 20.0, Easing::Linear)
+            .with_key(0.0, 0.0, Easing::Linear)
+            .with_key(2.0, 10.0, Easing::Linear);
+        let times: Vec<f32> = track.keys().iter().map(|k| k.time).collect();
+        assert_eq!(times, vec![0.0, 2.0]);
+        assert_eq!(track.sample(2.0), Some(10.0));
+    }
+
+    #[test]
+    fn catmull_rom_passes_through_keys() {
+        let track = Track::new()
+            .with_key(0.0, Vec3::new(0.0, 0.0, 0.0), Easing::CatmullRom)
+            .with_key(1.0, Vec3::new(1.0, 2.0, 0.0), Easing::CatmullRom)
+            .with_key(2.0, Vec3::new(2.0, 0.0, 0.0), Easing::CatmullRom);
+        let at_key = track.sample(1.0).unwrap();
+        assert!((at_key - Vec3::new(1.0, 2.0, 0.0)).length() < 1e-5);
+        // Sobre la curva la altura supera la de la interpolación lineal
+        assert!(track.sample(0.75).unwrap().y > 1.5);
+    }
+}
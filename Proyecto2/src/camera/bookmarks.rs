@@ -0,0 +1,97 @@
+// Marcadores de cámara para volver a puntos de vista guardados
+
+use crate::math::Vec3;
+use super::Camera;
+
+#[derive(Debug, Clone, Copy)]
+pub struct CameraBookmark {
+    pub position: Vec3,
+    pub target: Vec3,
+    pub fov: f32,
+}
+
+impl CameraBookmark {
+    pub fn from_camera(camera: &Camera) -> Self {
+        CameraBookmark {
+            position: camera.position,
+            target: camera.target,
+            fov: camera.fov,
+        }
+    }
+
+    // Devuelve la cámara al punto de vista guardado
+    pub fn apply(&self, camera: &mut Camera) {
+        camera.look_at(self.position, self.target);
+        camera.set_fov(self.fov);
+    }
+}
+
+// Un marcador por tecla numérica (0-9)
+#[derive(Debug, Clone, Default)]
+pub struct CameraBookmarks {
+    slots: [Option<CameraBookmark>; 10],
+}
+
+impl CameraBookmarks {
+    pub fn new() -> Self {
+        CameraBookmarks::default()
+    }
+
+    pub fn get(&self, slot: usize) -> Option<&CameraBookmark> {
+        self.slots.get(slot).and_then(|bookmark| bookmark.as_ref())
+    }
+
+    // Guarda el punto de vista actual en `slot` (se ignoran posiciones fuera de rango)
+    pub fn store(&mut self, slot: usize, camera: &Camera) {
+        if let Some(bookmark) = self.slots.get_mut(slot) {
+            *bookmark = Some(CameraBookmark::from_camera(camera));
+        }
+    }
+
+    // Restaura el marcador de `slot`; false si está vacío
+    pub fn recall(&self, slot: usize, camera: &mut Camera) -> bool {
+        match self.get(slot) {
+            Some(bookmark) => {
+                bookmark.apply(camera);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn camera() -> Camera {
+        Camera::new(Vec3::new(0.0, 1.0, 3.0), Vec3::zero(), Vec3::up(), 60.0, 1.5)
+    }
+
+    #[test]
+    fn recall_restores_stored_view() {
+        let mut camera = camera();
+        let mut bookmarks = CameraBookmarks::new();
+        bookmarks.store(3, &camera);
+
+        camera.look_at(Vec3::new(5.0, 5.0, 5.0), Vec3::new(1.0, 0.0, 0.0));
+        camera.set_fov(30.0);
+        assert!(bookmarks.recall(3, &mut camera));
+        assert!((camera.position - Vec3::new(0.0, 1.0, 3.0)).length() < 1e-6);
+        assert!(camera.target.length() < 1e-6);
+        assert_eq!(camera.fov, 60.0);
+    }
+
+    #[test]
+    fn empty_and_out_of_range_slots() {
+        let mut camera = camera();
+        let mut bookmarks = CameraBookmarks::new();
+        // Fuera de rango se ignora sin entrar en pánico
+        bookmarks.store(10, &camera);
+        assert!(bookmarks.get(10).is_none());
+
+        camera.look_at(Vec3::new(2.0, 0.0, 0.0), Vec3::zero());
+        assert!(!bookmarks.recall(0, &mut camera));
+        assert!((camera.position - Vec3::new(2.0, 0.0, 0.0)).length() < 1e-6);
+    }
+}
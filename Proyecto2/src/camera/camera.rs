@@ -1,6 +1,7 @@
 // Sistema de cámara para raytracing
 
-use crate::math::{Vec3, Ray};
+use crate::math::{Vec3, Ray, Quat};
+use super::Projection;
 
 #[derive(Debug, Clone)]
 pub struct Camera {
@@ -29,6 +30,13 @@ pub struct Camera {
     pub lower_left_corner: Vec3,
     pub horizontal: Vec3,
     pub vertical: Vec3,
+    
+    // Intervalo del obturador (apertura y cierre); igual en ambos = sin motion blur
+    pub shutter_open: f32,
+    pub shutter_close: f32,
+    
+    // Proyección usada para generar los rayos
+    pub projection: Projection,
 }
 
 impl Camera {
@@ -49,6 +57,9 @@ impl Camera {
             lower_left_corner: Vec3::zero(),
             horizontal: Vec3::zero(),
             vertical: Vec3::zero(),
+            shutter_open: 0.0,
+            shutter_close: 0.0,
+            projection: Projection::Perspective,
         };
         
         camera.update_camera_vectors();
@@ -56,6 +67,58 @@ impl Camera {
         camera
     }
     
+    // Builder pattern para definir el intervalo del obturador
+    pub fn with_shutter(mut self, open: f32, close: f32) -> Self {
+        self.set_shutter(open, close);
+        self
+    }
+    
+    pub fn set_shutter(&mut self, open: f32, close: f32) {
+        self.shutter_open = open;
+        self.shutter_close = close.max(open);
+    }
+    
+    // Indica si el obturador permanece abierto un intervalo no nulo
+    pub fn has_motion_blur(&self) -> bool {
+        self.shutter_close > self.shutter_open
+    }
+    
+    // Convierte una muestra en [0, 1) en un instante dentro del obturador
+    pub fn shutter_time(&self, sample: f32) -> f32 {
+        self.shutter_open + (self.shutter_close - self.shutter_open) * sample
+    }
+    
+    // Builder pattern para la proyección
+    pub fn with_projection(mut self, projection: Projection) -> Self {
+        self.set_projection(projection);
+        self
+    }
+    
+    pub fn set_projection(&mut self, projection: Projection) {
+        self.projection = projection;
+        self.set_fov(self.fov);
+    }
+    
+    // Cambia la proporción de aspecto (ancho / alto) de la imagen
+    pub fn set_aspect_ratio(&mut self, aspect_ratio: f32) {
+        self.aspect_ratio = aspect_ratio;
+        self.update_viewport();
+    }
+    
+    // Coloca la cámara en una posición mirando hacia un punto
+    pub fn look_at(&mut self, position: Vec3, target: Vec3) {
+        self.position = position;
+        self.target = target;
+        self.update_camera_vectors();
+        self.update_viewport();
+    }
+    
+    // Cambia el campo de visión vertical (grados; el ojo de pez admite hasta 360)
+    pub fn set_fov(&mut self, fov: f32) {
+        self.fov = fov.clamp(1.0, self.projection.max_fov());
+        self.update_viewport();
+    }
+    
     // Actualiza los vectores del sistema de coordenadas de la cámara
     fn update_camera_vectors(&mut self) {
         // Calcular el vector forward (hacia donde mira la cámara)
@@ -84,21 +147,40 @@ impl Camera {
     }
     
     // Genera un rayo desde la cámara hacia las coordenadas (u, v) del viewport
-    pub fn get_ray(&self, u: f32, v: f32) -> Ray {
+    // en el instante de apertura del obturador
+    pub fn get_ray(&self, u: f32, v: f32) -> Option<Ray> {
+        self.get_ray_at(u, v, self.shutter_open)
+    }
+    
+    // Genera un rayo hacia (u, v) en un instante concreto.
+    // None si (u, v) cae fuera de la imagen circular del ojo de pez
+    pub fn get_ray_at(&self, u: f32, v: f32, time: f32) -> Option<Ray> {
         // Convierte u,v [0,1] a coordenadas NDC [-1,1]
         let ndc_x = (u * 2.0) - 1.0;
         let ndc_y = (v * 2.0) - 1.0;
         
-        // Calcula el punto en el plano de la imagen
-        let half_width = (crate::math::degrees_to_radians(self.fov) * 0.5).tan() * self.aspect_ratio;
+        // Medidas del plano de la imagen a distancia focal
         let half_height = (crate::math::degrees_to_radians(self.fov) * 0.5).tan();
+        let half_width = half_height * self.aspect_ratio;
+        let image_offset = self.right * (ndc_x * half_width) + self.camera_up * (ndc_y * half_height);
         
-        let target_point = self.position + self.forward * self.focal_length
-                          + self.right * (ndc_x * half_width)
-                          + self.camera_up * (ndc_y * half_height);
-        
-        let direction = (target_point - self.position).normalize();
-        Ray::new(self.position, direction)
+        let (origin, direction) = match self.projection {
+            Projection::Perspective => {
+                let target_point = self.position + self.forward * self.focal_length + image_offset;
+                (self.position, (target_point - self.position).normalize())
+            }
+            Projection::Orthographic => {
+                // El plano de imagen se escala hasta la distancia del target
+                let distance = (self.target - self.position).length();
+                (self.position + image_offset * distance, self.forward)
+            }
+            Projection::Fisheye(_) | Projection::Equirectangular => {
+                let local = self.projection.angular_direction(ndc_x, ndc_y, self.aspect_ratio, self.fov)?;
+                let direction = self.right * local.x + self.camera_up * local.y + self.forward * local.z;
+                (self.position, direction.normalize())
+            }
+        };
+        Some(Ray::new(origin, direction).with_time(time))
     }
     
     // Controles de cámara interactivos
@@ -137,4 +219,111 @@ impl Camera {
         self.position = self.target + direction * new_distance;
         self.update_camera_vectors();
     }
+    
+    // Controles de vuelo libre (primera persona)
+    
+    /// Desplaza la cámara sobre sus ejes locales (adelante, derecha, arriba) sin girarla
+    pub fn fly(&mut self, forward: f32, right: f32, up: f32) {
+        let offset = self.forward * forward + self.right * right + self.camera_up * up;
+        self.position += offset;
+        self.target += offset;
+        self.update_camera_vectors();
+        self.update_viewport();
+    }
+    
+    /// Gira la vista desde la posición actual usando delta del mouse (el target se mueve)
+    pub fn look(&mut self, delta_x: f32, delta_y: f32, sensitivity: f32) {
+        let distance = (self.target - self.position).length().max(0.1);
+        let up = self.up.normalize();
+        
+        // Guiñada alrededor del "arriba" (incluye el alabeo)
+        let forward = Quat::from_axis_angle(up, -delta_x * sensitivity).rotate(self.forward);
+        
+        // Cabeceo alrededor de right, limitado para no alinearse con el "arriba"
+        let pitch = forward.dot(&up).clamp(-1.0, 1.0).asin();
+        let new_pitch = (pitch + delta_y * sensitivity).clamp(-1.5, 1.5);
+        let right = forward.cross(&up).normalize();
+        let forward = Quat::from_axis_angle(right, new_pitch - pitch).rotate(forward);
+        
+        self.target = self.position + forward * distance;
+        self.update_camera_vectors();
+        self.update_viewport();
+    }
+    
+    /// Alabeo: gira el "arriba" de la cámara alrededor de la dirección de vista
+    pub fn roll(&mut self, angle: f32) {
+        self.up = Quat::from_axis_angle(self.forward, angle).rotate(self.camera_up);
+        self.update_camera_vectors();
+        self.update_viewport();
+    }
+    
+    /// Nivela el horizonte (deshace el alabeo)
+    pub fn reset_roll(&mut self) {
+        self.up = Vec3::up();
+        self.update_camera_vectors();
+        self.update_viewport();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx_vec(a: Vec3, b: Vec3) -> bool {
+        (a - b).length() < 1e-4
+    }
+
+    fn camera() -> Camera {
+        Camera::new(Vec3::new(0.0, 1.0, 5.0), Vec3::new(0.0, 1.0, 0.0), Vec3::up(), 60.0, 1.5)
+    }
+
+    // Ángulo de la vista sobre el plano perpendicular a `up`
+    fn pitch(camera: &Camera) -> f32 {
+        camera.forward.dot(&camera.up.normalize()).clamp(-1.0, 1.0).asin()
+    }
+
+    #[test]
+    fn fly_moves_position_and_target_together() {
+        let mut camera = camera();
+        let forward = camera.forward;
+        camera.fly(2.0, 1.0, 0.5);
+        assert!(approx_vec(camera.position, Vec3::new(1.0, 1.5, 3.0)));
+        assert!(approx_vec(camera.target - camera.position, Vec3::new(0.0, 0.0, -5.0)));
+        assert!(approx_vec(camera.forward, forward));
+    }
+
+    #[test]
+    fn look_clamps_pitch_and_keeps_distance() {
+        let mut camera = camera();
+        camera.look(0.0, 100.0, 1.0);
+        assert!((pitch(&camera) - 1.5).abs() < 1e-3);
+        camera.look(0.0, -100.0, 1.0);
+        assert!((pitch(&camera) + 1.5).abs() < 1e-3);
+        assert!(((camera.target - camera.position).length() - 5.0).abs() < 1e-3);
+        assert!(approx_vec(camera.position, Vec3::new(0.0, 1.0, 5.0)));
+    }
+
+    #[test]
+    fn look_clamps_pitch_against_rolled_up() {
+        let mut camera = camera();
+        camera.roll(0.6);
+        for _ in 0..10 {
+            camera.look(0.3, 0.4, 1.0);
+            assert!(pitch(&camera).abs() <= 1.5 + 1e-3);
+        }
+        // El alabeo se conserva al girar la vista
+        assert!(camera.up.dot(&Vec3::up()) < 0.99);
+    }
+
+    #[test]
+    fn reset_roll_restores_up() {
+        let mut camera = camera();
+        let right = camera.right;
+        camera.roll(0.8);
+        assert!(!approx_vec(camera.camera_up, Vec3::up()));
+        camera.reset_roll();
+        assert!(approx_vec(camera.up, Vec3::up()));
+        assert!(approx_vec(camera.camera_up, Vec3::up()));
+        assert!(approx_vec(camera.right, right));
+    }
 }
@@ -1,5 +1,11 @@
 // Módulo de cámara
 
 pub mod camera;
+pub mod projection;
+pub mod stereo;
+pub mod bookmarks;
 
 pub use camera::Camera;
+pub use projection::{Projection, FisheyeMapping};
+pub use stereo::{StereoRig, StereoLayout, Eye};
+pub use bookmarks::{CameraBookmarks, CameraBookmark};
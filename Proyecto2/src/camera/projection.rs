@@ -0,0 +1,147 @@
+// Proyecciones de cámara: de coordenadas de imagen a direcciones de rayo
+
+use crate::math::Vec3;
+use std::f32::consts::PI;
+use std::str::FromStr;
+
+// Relación entre el ángulo al eje óptico y la distancia al centro de la imagen
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FisheyeMapping {
+    // r ∝ θ (ángulos iguales ocupan distancias iguales)
+    Equidistant,
+    // r ∝ sin(θ/2) (conserva el área, como los lentes de domo)
+    Equisolid,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Projection {
+    // Cámara estenopeica clásica
+    #[default]
+    Perspective,
+    // Rayos paralelos; el encuadre coincide con el de la perspectiva a la distancia del target
+    Orthographic,
+    // Imagen circular cuyo diámetro vertical abarca el campo de visión
+    Fisheye(FisheyeMapping),
+    // Panorama de 360° x 180° (longitud en u, latitud en v) centrado en la dirección de vista
+    Equirectangular,
+}
+
+impl Projection {
+    // Campo de visión máximo admitido por la proyección (grados)
+    pub fn max_fov(&self) -> f32 {
+        match self {
+            Projection::Fisheye(_) => 360.0,
+            _ => 179.0,
+        }
+    }
+
+    // Dirección en coordenadas de cámara (x derecha, y arriba, z adelante) de las
+    // proyecciones angulares para un punto NDC en [-1, 1]. None fuera del círculo del
+    // ojo de pez (y para perspectiva y ortográfica, que se resuelven en el plano de imagen)
+    pub fn angular_direction(&self, ndc_x: f32, ndc_y: f32, aspect_ratio: f32, fov: f32) -> Option<Vec3> {
+        match self {
+            Projection::Fisheye(mapping) => {
+                let (x, y) = (ndc_x * aspect_ratio, ndc_y);
+                let r = (x * x + y * y).sqrt();
+                if r > 1.0 {
+                    return None;
+                }
+                let max_theta = fov.to_radians() * 0.5;
+                let theta = match mapping {
+                    FisheyeMapping::Equidistant => r * max_theta,
+                    FisheyeMapping::Equisolid => 2.0 * (r * (max_theta * 0.5).sin()).asin(),
+                };
+                if r < 1e-6 {
+                    return Some(Vec3::new(0.0, 0.0, 1.0));
+                }
+                let (sin, cos) = theta.sin_cos();
+                Some(Vec3::new(x / r * sin, y / r * sin, cos))
+            }
+            Projection::Equirectangular => {
+                let longitude = ndc_x * PI;
+                let latitude = ndc_y * PI * 0.5;
+                let (sin_lat, cos_lat) = latitude.sin_cos();
+                let (sin_lon, cos_lon) = longitude.sin_cos();
+                Some(Vec3::new(cos_lat * sin_lon, sin_lat, cos_lat * cos_lon))
+            }
+            Projection::Perspective | Projection::Orthographic => None,
+        }
+    }
+}
+
+impl FromStr for Projection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "perspective" => Ok(Projection::Perspective),
+            "orthographic" | "ortho" => Ok(Projection::Orthographic),
+            "fisheye" | "fisheye_equidistant" => Ok(Projection::Fisheye(FisheyeMapping::Equidistant)),
+            "fisheye_equisolid" => Ok(Projection::Fisheye(FisheyeMapping::Equisolid)),
+            "equirectangular" | "panorama" => Ok(Projection::Equirectangular),
+            other => Err(format!("proyección desconocida '{}'", other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::camera::Camera;
+
+    const PROJECTIONS: [Projection; 5] = [
+        Projection::Perspective,
+        Projection::Orthographic,
+        Projection::Fisheye(FisheyeMapping::Equidistant),
+        Projection::Fisheye(FisheyeMapping::Equisolid),
+        Projection::Equirectangular,
+    ];
+
+    fn approx_vec(a: Vec3, b: Vec3) -> bool {
+        (a - b).length() < 1e-5
+    }
+
+    #[test]
+    fn center_looks_forward() {
+        for projection in PROJECTIONS {
+            if let Some(direction) = projection.angular_direction(0.0, 0.0, 1.5, 120.0) {
+                assert!(approx_vec(direction, Vec3::new(0.0, 0.0, 1.0)), "{:?}", projection);
+            }
+            // Perspectiva y ortográfica se resuelven en la cámara: el rayo central sigue a forward
+            let camera = Camera::new(Vec3::zero(), Vec3::new(0.0, 0.0, 5.0), Vec3::up(), 120.0, 1.5)
+                .with_projection(projection);
+            let ray = camera.get_ray(0.5, 0.5).unwrap();
+            assert!(approx_vec(ray.direction, Vec3::new(0.0, 0.0, 1.0)), "{:?}", projection);
+        }
+    }
+
+    #[test]
+    fn fisheye_is_circular() {
+        for mapping in [FisheyeMapping::Equidistant, FisheyeMapping::Equisolid] {
+            let fisheye = Projection::Fisheye(mapping);
+            assert!(fisheye.angular_direction(0.8, 0.8, 1.0, 180.0).is_none());
+            // Con aspecto 2 el círculo ocupa la mitad central del ancho
+            assert!(fisheye.angular_direction(0.6, 0.0, 2.0, 180.0).is_none());
+            assert!(fisheye.angular_direction(0.4, 0.0, 2.0, 180.0).is_some());
+
+            // El borde del círculo está a fov/2 del eje
+            let edge = fisheye.angular_direction(0.0, 1.0, 1.0, 180.0).unwrap();
+            assert!(approx_vec(edge, Vec3::new(0.0, 1.0, 0.0)), "{:?}", mapping);
+        }
+    }
+
+    #[test]
+    fn equirectangular_spans_full_longitude() {
+        let panorama = Projection::Equirectangular;
+        for ndc_x in [-1.0, 1.0] {
+            let direction = panorama.angular_direction(ndc_x, 0.0, 2.0, 60.0).unwrap();
+            let longitude = direction.x.atan2(direction.z).to_degrees();
+            assert!((longitude.abs() - 180.0).abs() < 1e-3, "{}", longitude);
+            assert!(approx_vec(direction, Vec3::new(0.0, 0.0, -1.0)));
+        }
+        let right = panorama.angular_direction(0.5, 0.0, 2.0, 60.0).unwrap();
+        assert!(approx_vec(right, Vec3::new(1.0, 0.0, 0.0)));
+        let up = panorama.angular_direction(0.3, 1.0, 2.0, 60.0).unwrap();
+        assert!(approx_vec(up, Vec3::new(0.0, 1.0, 0.0)));
+    }
+}
@@ -0,0 +1,182 @@
+// Rig estéreo para visores VR: dos ojos renderizados en una sola imagen
+
+use crate::math::Ray;
+use super::{Camera, Projection};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Eye {
+    Left,
+    Right,
+}
+
+impl Eye {
+    // Lado hacia el que se desplaza el ojo respecto al centro de la cámara
+    pub fn sign(&self) -> f32 {
+        match self {
+            Eye::Left => -1.0,
+            Eye::Right => 1.0,
+        }
+    }
+}
+
+// Disposición de los dos ojos dentro del framebuffer
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StereoLayout {
+    // Ojo izquierdo a la izquierda, derecho a la derecha
+    SideBySide,
+    // Ojo izquierdo arriba, derecho abajo (el formato habitual de los panoramas 360°)
+    OverUnder,
+}
+
+impl StereoLayout {
+    // Convierte (u, v) de la imagen completa en el ojo y sus coordenadas propias en [0, 1]
+    // (v crece hacia arriba, como en `Camera::get_ray_at`)
+    pub fn split(&self, u: f32, v: f32) -> (Eye, f32, f32) {
+        match self {
+            StereoLayout::SideBySide if u < 0.5 => (Eye::Left, u * 2.0, v),
+            StereoLayout::SideBySide => (Eye::Right, u * 2.0 - 1.0, v),
+            StereoLayout::OverUnder if v >= 0.5 => (Eye::Left, u, v * 2.0 - 1.0),
+            StereoLayout::OverUnder => (Eye::Right, u, v * 2.0),
+        }
+    }
+
+    // Proporción de aspecto de la imagen de cada ojo
+    pub fn eye_aspect_ratio(&self, width: u32, height: u32) -> f32 {
+        match self {
+            StereoLayout::SideBySide => (width as f32 * 0.5) / height as f32,
+            StereoLayout::OverUnder => width as f32 / (height as f32 * 0.5),
+        }
+    }
+}
+
+impl FromStr for StereoLayout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sbs" | "side_by_side" => Ok(StereoLayout::SideBySide),
+            "ou" | "over_under" | "top_bottom" => Ok(StereoLayout::OverUnder),
+            other => Err(format!("disposición estéreo desconocida '{}'", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct StereoRig {
+    pub layout: StereoLayout,
+    // Distancia entre los ojos en unidades de la escena
+    pub interpupillary_distance: f32,
+    // Distancia del plano sin paralaje en las proyecciones planas (None = distancia al target)
+    pub convergence: Option<f32>,
+}
+
+impl StereoRig {
+    pub fn new(layout: StereoLayout) -> Self {
+        StereoRig {
+            layout,
+            interpupillary_distance: 0.064,
+            convergence: None,
+        }
+    }
+
+    // Builder pattern para la distancia interpupilar
+    pub fn with_interpupillary_distance(mut self, distance: f32) -> Self {
+        self.interpupillary_distance = distance.max(0.0);
+        self
+    }
+
+    // Builder pattern para la distancia de convergencia
+    pub fn with_convergence(mut self, distance: f32) -> Self {
+        self.convergence = Some(distance.max(1e-3));
+        self
+    }
+
+    // Copia de la cámara con el aspecto de un solo ojo para una imagen de width x height
+    pub fn eye_camera(&self, camera: &Camera, width: u32, height: u32) -> Camera {
+        let mut eye_camera = camera.clone();
+        eye_camera.set_aspect_ratio(self.layout.eye_aspect_ratio(width, height));
+        eye_camera
+    }
+
+    // Rayo de un ojo hacia (u, v) de su propia imagen
+    pub fn eye_ray(&self, camera: &Camera, eye: Eye, u: f32, v: f32, time: f32) -> Option<Ray> {
+        let ray = camera.get_ray_at(u, v, time)?;
+        let half_offset = eye.sign() * self.interpupillary_distance * 0.5;
+
+        match camera.projection {
+            Projection::Perspective | Projection::Orthographic => {
+                // Ejes paralelos con el plano de imagen desplazado (off-axis): los rayos de
+                // ambos ojos se cruzan en el plano de convergencia, sin paralaje vertical
+                let convergence = self.convergence.unwrap_or_else(|| (camera.target - camera.position).length());
+                let along_axis = ray.direction.dot(&camera.forward).max(1e-4);
+                let focus = ray.origin + ray.direction * (convergence / along_axis);
+                let origin = ray.origin + camera.right * half_offset;
+                Some(Ray::new(origin, (focus - origin).normalize()).with_time(time))
+            }
+            Projection::Fisheye(_) | Projection::Equirectangular => {
+                // Estéreo omnidireccional (ODS): cada rayo sale de un círculo de diámetro
+                // interpupilar, desplazado perpendicular a su dirección. El producto vectorial
+                // sin normalizar reduce el desplazamiento hacia los polos y evita el giro de ojos
+                let tangent = ray.direction.cross(&camera.camera_up);
+                let origin = ray.origin + tangent * half_offset;
+                Some(Ray::new(origin, ray.direction).with_time(time))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::Vec3;
+
+    #[test]
+    fn split_maps_each_half_to_full_eye_image() {
+        let sbs = StereoLayout::SideBySide;
+        assert_eq!(sbs.split(0.25, 0.3), (Eye::Left, 0.5, 0.3));
+        assert_eq!(sbs.split(0.75, 0.3), (Eye::Right, 0.5, 0.3));
+        assert_eq!(sbs.split(0.5, 0.0), (Eye::Right, 0.0, 0.0));
+
+        // v crece hacia arriba: la mitad superior es el ojo izquierdo
+        let ou = StereoLayout::OverUnder;
+        assert_eq!(ou.split(0.3, 0.75), (Eye::Left, 0.3, 0.5));
+        assert_eq!(ou.split(0.3, 0.25), (Eye::Right, 0.3, 0.5));
+        assert_eq!(ou.split(0.3, 0.5), (Eye::Left, 0.3, 0.0));
+    }
+
+    #[test]
+    fn eye_aspect_ratio_halves_the_split_axis() {
+        assert_eq!(StereoLayout::SideBySide.eye_aspect_ratio(1600, 600), 800.0 / 600.0);
+        assert_eq!(StereoLayout::OverUnder.eye_aspect_ratio(1200, 1200), 2.0);
+    }
+
+    #[test]
+    fn left_eye_sits_left_of_center() {
+        let rig = StereoRig::new(StereoLayout::SideBySide).with_interpupillary_distance(0.1);
+        for projection in [Projection::Perspective, Projection::Orthographic, Projection::Equirectangular] {
+            let camera = Camera::new(Vec3::zero(), Vec3::new(0.0, 0.0, -4.0), Vec3::up(), 60.0, 1.0)
+                .with_projection(projection);
+            let center = camera.get_ray(0.5, 0.5).unwrap();
+            let left = rig.eye_ray(&camera, Eye::Left, 0.5, 0.5, 0.0).unwrap();
+            let right = rig.eye_ray(&camera, Eye::Right, 0.5, 0.5, 0.0).unwrap();
+
+            let offset = |ray: &Ray| (ray.origin - center.origin).dot(&camera.right);
+            assert!((offset(&left) + 0.05).abs() < 1e-5, "{:?}", projection);
+            assert!((offset(&right) - 0.05).abs() < 1e-5, "{:?}", projection);
+        }
+    }
+
+    #[test]
+    fn planar_eyes_converge_on_target_plane() {
+        let rig = StereoRig::new(StereoLayout::SideBySide);
+        let camera = Camera::new(Vec3::zero(), Vec3::new(0.0, 0.0, -4.0), Vec3::up(), 60.0, 1.0);
+        for (u, v) in [(0.5, 0.5), (0.2, 0.7), (0.9, 0.1)] {
+            let left = rig.eye_ray(&camera, Eye::Left, u, v, 0.0).unwrap();
+            let right = rig.eye_ray(&camera, Eye::Right, u, v, 0.0).unwrap();
+            // Ambos rayos cruzan z = -4 en el mismo punto
+            let at_plane = |ray: &Ray| ray.at((-4.0 - ray.origin.z) / ray.direction.z);
+            assert!((at_plane(&left) - at_plane(&right)).length() < 1e-4);
+        }
+    }
+}
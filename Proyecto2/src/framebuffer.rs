@@ -52,26 +52,31 @@ impl Framebuffer {
         self.set_pixel(x, y, Color::from_vec3(&color));
     }
     
-    // Para compatibilidad con raylib, convertir a Vec<Vec<raylib::Color>>
-    pub fn to_raylib_buffer(&self) -> Vec<Vec<raylib::prelude::Color>> {
-        let mut buffer = Vec::with_capacity(self.height as usize);
-        
-        for y in 0..self.height {
-            let mut row = Vec::with_capacity(self.width as usize);
-            for x in 0..self.width {
-                let idx = (y * self.width + x) as usize;
-                let pixel = self.pixels[idx];
-                let color = raylib::prelude::Color::new(
-                    ((pixel >> 16) & 0xFF) as u8,
-                    ((pixel >> 8) & 0xFF) as u8,
-                    (pixel & 0xFF) as u8,
-                    ((pixel >> 24) & 0xFF) as u8,
-                );
-                row.push(color);
-            }
-            buffer.push(row);
+    // Pixels en bytes RGBA por filas (el formato R8G8B8A8 de las texturas de raylib)
+    pub fn to_rgba_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.pixels.len() * 4);
+        for &pixel in &self.pixels {
+            bytes.extend_from_slice(&[
+                ((pixel >> 16) & 0xFF) as u8,
+                ((pixel >> 8) & 0xFF) as u8,
+                (pixel & 0xFF) as u8,
+                ((pixel >> 24) & 0xFF) as u8,
+            ]);
         }
-        
-        buffer
+        bytes
     }
-}
\ No newline at end of file
+    
+    // Guarda el contenido como imagen (el formato se deduce de la extensión)
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut image = image::RgbImage::new(self.width, self.height);
+        for (pixel, &value) in image.pixels_mut().zip(&self.pixels) {
+            *pixel = image::Rgb([
+                ((value >> 16) & 0xFF) as u8,
+                ((value >> 8) & 0xFF) as u8,
+                (value & 0xFF) as u8,
+            ]);
+        }
+        image.save(path)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+// Caja envolvente alineada a los ejes (para descartar rayos rápidamente)
+
+use crate::math::{Vec3, Ray, Mat4};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Aabb {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Aabb {
+    pub fn new(min: Vec3, max: Vec3) -> Self {
+        Aabb { min, max }
+    }
+
+    // Caja que contiene a ambas
+    pub fn union(&self, other: &Aabb) -> Aabb {
+        Aabb::new(
+            Vec3::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y), self.min.z.min(other.min.z)),
+            Vec3::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y), self.max.z.max(other.max.z)),
+        )
+    }
+
+    // Caja alineada que contiene las 8 esquinas transformadas
+    pub fn transform(&self, matrix: &Mat4) -> Aabb {
+        let mut result: Option<Aabb> = None;
+        for i in 0..8 {
+            let corner = Vec3::new(
+                if i & 1 == 0 { self.min.x } else { self.max.x },
+                if i & 2 == 0 { self.min.y } else { self.max.y },
+                if i & 4 == 0 { self.min.z } else { self.max.z },
+            );
+            let p = matrix.transform_point(corner);
+            let point_box = Aabb::new(p, p);
+            result = Some(match result {
+                Some(b) => b.union(&point_box),
+                None => point_box,
+            });
+        }
+        result.unwrap_or(*self)
+    }
+
+    // Tramo [t_enter, t_exit] del rayo dentro de la caja, limitado a [t_min, t_max]
+    pub fn intersect(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<(f32, f32)> {
+        let origin = [ray.origin.x, ray.origin.y, ray.origin.z];
+        let direction = [ray.direction.x, ray.direction.y, ray.direction.z];
+        let min = [self.min.x, self.min.y, self.min.z];
+        let max = [self.max.x, self.max.y, self.max.z];
+        let (mut t0, mut t1) = (t_min, t_max);
+
+        for axis in 0..3 {
+            let inv_dir = 1.0 / direction[axis];
+            let mut near = (min[axis] - origin[axis]) * inv_dir;
+            let mut far = (max[axis] - origin[axis]) * inv_dir;
+            if inv_dir < 0.0 {
+                std::mem::swap(&mut near, &mut far);
+            }
+            t0 = t0.max(near);
+            t1 = t1.min(far);
+            if t1 < t0 {
+                return None;
+            }
+        }
+
+        Some((t0, t1))
+    }
+
+    // Verifica si el rayo atraviesa la caja
+    pub fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> bool {
+        self.intersect(ray, t_min, t_max).is_some()
+    }
+}
@@ -0,0 +1,152 @@
+// Conos con base opcional (lámparas, boquillas)
+
+use crate::math::{Vec3, Ray, Mat3};
+use crate::math::polynomial::solve_quadratic;
+use crate::material::Material;
+use super::{axis_frame, disk_bounds, Aabb, HitRecord, Hittable};
+use std::f32::consts::PI;
+
+#[derive(Debug, Clone)]
+pub struct Cone {
+    // Centro de la base
+    pub base: Vec3,
+    // Dirección de la base hacia el vértice (unitaria)
+    pub axis: Vec3,
+    // Radio de la base
+    pub radius: f32,
+    pub height: f32,
+    // Si es false la base queda abierta
+    pub capped: bool,
+    pub material: Material,
+    frame: Mat3,
+}
+
+impl Cone {
+    pub fn new(base: Vec3, axis: Vec3, radius: f32, height: f32, material: Material) -> Self {
+        let axis = axis.normalize();
+        Cone {
+            base,
+            axis,
+            radius: radius.abs(),
+            height: height.abs().max(1e-6),
+            capped: true,
+            material,
+            frame: axis_frame(axis),
+        }
+    }
+
+    // Builder pattern para activar o quitar la base
+    pub fn with_caps(mut self, capped: bool) -> Self {
+        self.capped = capped;
+        self
+    }
+}
+
+impl Hittable for Cone {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
+        // Rayo en espacio local: eje Y, base en el origen, vértice en y = h
+        let to_local = self.frame.transpose();
+        let o = to_local * (ray.origin - self.base);
+        let d = to_local * ray.direction;
+        let (r, h) = (self.radius, self.height);
+        let k2 = (r / h) * (r / h);
+
+        let mut best: Option<(f32, Vec3, f32, f32)> = None;
+        let mut consider = |t: f32, normal: Vec3, u: f32, v: f32| {
+            if t >= t_min && t <= t_max && best.is_none_or(|(best_t, ..)| t < best_t) {
+                best = Some((t, normal, u, v));
+            }
+        };
+
+        // Superficie lateral: x² + z² = k²·(h - y)²
+        let w = h - o.y;
+        let a = (d.x * d.x + d.z * d.z - k2 * d.y * d.y) as f64;
+        let b = 2.0 * (o.x * d.x + o.z * d.z + k2 * w * d.y) as f64;
+        let c = (o.x * o.x + o.z * o.z - k2 * w * w) as f64;
+        for t in solve_quadratic(a, b, c) {
+            let t = t as f32;
+            let p = o + d * t;
+            if p.y >= 0.0 && p.y <= h {
+                // Gradiente de x² + z² - k²·(h - y)²
+                let normal = Vec3::new(p.x, k2 * (h - p.y), p.z).normalize();
+                let u = 0.5 + p.z.atan2(p.x) / (2.0 * PI);
+                consider(t, normal, u, p.y / h);
+            }
+        }
+
+        // Base: plano y = 0
+        if self.capped && d.y.abs() > 1e-8 {
+            let t = -o.y / d.y;
+            let p = o + d * t;
+            if p.x * p.x + p.z * p.z <= r * r {
+                consider(t, Vec3::new(0.0, -1.0, 0.0), p.x / (2.0 * r) + 0.5, p.z / (2.0 * r) + 0.5);
+            }
+        }
+
+        let (t, local_normal, u, v) = best?;
+        let normal = (self.frame * local_normal).normalize();
+        Some(HitRecord::new(ray.at(t), normal, t, ray, self.material.clone(), u, v))
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        let apex = self.base + self.axis * self.height;
+        Some(disk_bounds(self.base, self.axis, self.radius).union(&Aabb::new(apex, apex)))
+    }
+
+    fn material(&self) -> Option<&Material> {
+        Some(&self.material)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx_vec(a: Vec3, b: Vec3) -> bool {
+        (a - b).length() < 1e-4
+    }
+
+    // Radio 1 en la base, vértice en y = 2
+    fn lamp() -> Cone {
+        Cone::new(Vec3::zero(), Vec3::up(), 1.0, 2.0, Material::new())
+    }
+
+    #[test]
+    fn side_normal_leans_toward_apex() {
+        let hit = lamp().hit(&Ray::new(Vec3::new(3.0, 1.0, 0.0), Vec3::new(-1.0, 0.0, 0.0)), 0.001, f32::INFINITY).unwrap();
+        // A media altura el radio es 0.5
+        assert!((hit.t - 2.5).abs() < 1e-4);
+        assert!(approx_vec(hit.normal, Vec3::new(2.0, 1.0, 0.0).normalize()) && hit.front_face);
+        assert!((hit.v - 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn ignores_mirrored_nappe() {
+        // La cuádrica también tiene solución en el cono reflejado sobre el vértice
+        let above = Ray::new(Vec3::new(3.0, 3.0, 0.0), Vec3::new(-1.0, 0.0, 0.0));
+        assert!(lamp().hit(&above, 0.001, f32::INFINITY).is_none());
+        // Y por debajo de la base
+        let below = Ray::new(Vec3::new(3.0, -1.0, 0.0), Vec3::new(-1.0, 0.0, 0.0));
+        assert!(lamp().hit(&below, 0.001, f32::INFINITY).is_none());
+    }
+
+    #[test]
+    fn base_cap_is_optional() {
+        let up = Ray::new(Vec3::new(0.2, -3.0, 0.1), Vec3::up());
+        let hit = lamp().hit(&up, 0.001, f32::INFINITY).unwrap();
+        assert!((hit.t - 3.0).abs() < 1e-4);
+        assert!(approx_vec(hit.normal, Vec3::new(0.0, -1.0, 0.0)) && hit.front_face);
+        // Los bordes de la base quedan dentro de la textura
+        for x in [-0.99, 0.99] {
+            let edge = lamp().hit(&Ray::new(Vec3::new(x, -3.0, 0.0), Vec3::up()), 0.001, f32::INFINITY).unwrap();
+            assert!((0.0..=1.0).contains(&edge.u) && (0.0..=1.0).contains(&edge.v));
+            assert!((edge.u - 0.5).abs() > 0.45 || (edge.v - 0.5).abs() > 0.45);
+        }
+
+        // Sin base el rayo entra y choca con la cara interior: 0.05 = k²·(2 - y)²
+        let open = lamp().with_caps(false).hit(&up, 0.001, f32::INFINITY).unwrap();
+        let y = 2.0 - 0.2f32.sqrt();
+        assert!((open.t - (y + 3.0)).abs() < 1e-3);
+        assert!(!open.front_face);
+    }
+}
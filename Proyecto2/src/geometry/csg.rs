@@ -0,0 +1,218 @@
+// Geometría sólida constructiva: unión, intersección y diferencia de objetos cerrados
+
+use crate::math::{Vec3, Ray};
+use super::{Aabb, HitInterval, HitRecord, Hittable};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CsgOperation {
+    // Puntos dentro de cualquiera de los dos operandos
+    Union,
+    // Puntos dentro de ambos operandos
+    Intersection,
+    // Puntos dentro del primero y fuera del segundo
+    Difference,
+}
+
+impl CsgOperation {
+    fn contains(&self, inside_left: bool, inside_right: bool) -> bool {
+        match self {
+            CsgOperation::Union => inside_left || inside_right,
+            CsgOperation::Intersection => inside_left && inside_right,
+            CsgOperation::Difference => inside_left && !inside_right,
+        }
+    }
+}
+
+// Nodo CSG. Cada superficie conserva el material y la normal del operando al que pertenece
+// (en una diferencia, las paredes talladas usan el material del segundo operando).
+#[derive(Debug, Clone)]
+pub struct Csg {
+    pub operation: CsgOperation,
+    pub left: Arc<dyn Hittable + Send + Sync>,
+    pub right: Arc<dyn Hittable + Send + Sync>,
+    bounds: Option<Aabb>,
+}
+
+impl Csg {
+    // Combina dos objetos compartidos
+    pub fn new(operation: CsgOperation, left: Arc<dyn Hittable + Send + Sync>, right: Arc<dyn Hittable + Send + Sync>) -> Self {
+        let bounds = match operation {
+            CsgOperation::Union => match (left.bounding_box(), right.bounding_box()) {
+                (Some(a), Some(b)) => Some(a.union(&b)),
+                _ => None,
+            },
+            CsgOperation::Intersection => match (left.bounding_box(), right.bounding_box()) {
+                (Some(a), Some(b)) => Some(Aabb::new(
+                    Vec3::new(a.min.x.max(b.min.x), a.min.y.max(b.min.y), a.min.z.max(b.min.z)),
+                    Vec3::new(a.max.x.min(b.max.x), a.max.y.min(b.max.y), a.max.z.min(b.max.z)),
+                )),
+                (a, b) => a.or(b),
+            },
+            CsgOperation::Difference => left.bounding_box(),
+        };
+        Csg { operation, left, right, bounds }
+    }
+
+    pub fn union<A: Hittable + 'static, B: Hittable + 'static>(left: A, right: B) -> Self {
+        Csg::new(CsgOperation::Union, Arc::new(left), Arc::new(right))
+    }
+
+    pub fn intersection<A: Hittable + 'static, B: Hittable + 'static>(left: A, right: B) -> Self {
+        Csg::new(CsgOperation::Intersection, Arc::new(left), Arc::new(right))
+    }
+
+    pub fn difference<A: Hittable + 'static, B: Hittable + 'static>(left: A, right: B) -> Self {
+        Csg::new(CsgOperation::Difference, Arc::new(left), Arc::new(right))
+    }
+}
+
+impl Hittable for Csg {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
+        if let Some(bounds) = &self.bounds
+            && !bounds.hit(ray, t_min, t_max)
+        {
+            return None;
+        }
+
+        // Primera frontera del sólido resultante dentro de [t_min, t_max]
+        self.intervals(ray)
+            .into_iter()
+            .flat_map(|interval| [interval.enter, interval.exit])
+            .find(|record| record.t >= t_min && record.t <= t_max)
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        self.bounds
+    }
+
+    fn is_time_dependent(&self) -> bool {
+        self.left.is_time_dependent() || self.right.is_time_dependent()
+    }
+
+    fn intervals(&self, ray: &Ray) -> Vec<HitInterval> {
+        // Eventos de entrada/salida de ambos operandos: (registro, es_izquierdo, entra)
+        let mut events: Vec<(HitRecord, bool, bool)> = Vec::new();
+        for (object, is_left) in [(&self.left, true), (&self.right, false)] {
+            for interval in object.intervals(ray) {
+                events.push((interval.enter, is_left, true));
+                events.push((interval.exit, is_left, false));
+            }
+        }
+        events.sort_by(|a, b| a.0.t.total_cmp(&b.0.t));
+
+        let (mut inside_left, mut inside_right) = (false, false);
+        let mut inside = false;
+        let mut enter: Option<HitRecord> = None;
+        let mut result = Vec::new();
+
+        for (record, is_left, entering) in events {
+            if is_left {
+                inside_left = entering;
+            } else {
+                inside_right = entering;
+            }
+            let now_inside = self.operation.contains(inside_left, inside_right);
+            if now_inside == inside {
+                continue;
+            }
+            inside = now_inside;
+
+            // La normal ya mira contra el rayo; solo cambia si la frontera es entrada o salida
+            let record = HitRecord { front_face: now_inside, ..record };
+            if now_inside {
+                enter = Some(record);
+            } else if let Some(enter) = enter.take() {
+                result.push(HitInterval { enter, exit: record });
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Cube;
+    use crate::material::Material;
+
+    const LEFT_COLOR: Vec3 = Vec3 { x: 1.0, y: 0.0, z: 0.0 };
+    const RIGHT_COLOR: Vec3 = Vec3 { x: 0.0, y: 0.0, z: 1.0 };
+
+    // Dos cubos de lado 2 solapados en x ∈ [0, 1]: izquierdo en [-1, 1], derecho en [0, 2]
+    fn operands() -> (Cube, Cube) {
+        let left = Cube::new(Vec3::zero(), Vec3::new(2.0, 2.0, 2.0), Material::new().with_color(LEFT_COLOR));
+        let right = Cube::new(Vec3::new(1.0, 0.0, 0.0), Vec3::new(2.0, 2.0, 2.0), Material::new().with_color(RIGHT_COLOR));
+        (left, right)
+    }
+
+    // Rayo en +x que entra al cubo izquierdo en t = 2
+    fn ray() -> Ray {
+        Ray::new(Vec3::new(-3.0, 0.25, 0.25), Vec3::new(1.0, 0.0, 0.0))
+    }
+
+    fn span(interval: &HitInterval) -> (f32, f32) {
+        (interval.enter.t, interval.exit.t)
+    }
+
+    fn approx_span(a: (f32, f32), b: (f32, f32)) -> bool {
+        (a.0 - b.0).abs() < 1e-4 && (a.1 - b.1).abs() < 1e-4
+    }
+
+    fn color(record: &HitRecord) -> Vec3 {
+        record.material.texture.value(record.u, record.v)
+    }
+
+    #[test]
+    fn union_merges_overlapping_spans() {
+        let (left, right) = operands();
+        let intervals = Csg::union(left, right).intervals(&ray());
+        assert_eq!(intervals.len(), 1);
+        assert!(approx_span(span(&intervals[0]), (2.0, 5.0)));
+        assert!(intervals[0].enter.front_face && !intervals[0].exit.front_face);
+    }
+
+    #[test]
+    fn intersection_keeps_shared_span() {
+        let (left, right) = operands();
+        let intervals = Csg::intersection(left, right).intervals(&ray());
+        assert_eq!(intervals.len(), 1);
+        assert!(approx_span(span(&intervals[0]), (3.0, 4.0)));
+        // Entra por la cara del derecho y sale por la del izquierdo
+        assert!((color(&intervals[0].enter) - RIGHT_COLOR).length() < 1e-4);
+        assert!((color(&intervals[0].exit) - LEFT_COLOR).length() < 1e-4);
+    }
+
+    #[test]
+    fn difference_exits_through_subtracted_operand() {
+        let (left, right) = operands();
+        let difference = Csg::difference(left, right.clone());
+        let intervals = difference.intervals(&ray());
+        assert_eq!(intervals.len(), 1);
+        assert!(approx_span(span(&intervals[0]), (2.0, 3.0)));
+
+        // La pared tallada es la cara de entrada del derecho, con la orientación invertida
+        let exit = &intervals[0].exit;
+        let carved = &right.intervals(&ray())[0].enter;
+        assert!(!exit.front_face && carved.front_face);
+        assert!((exit.point - carved.point).length() < 1e-4);
+        assert!((color(exit) - RIGHT_COLOR).length() < 1e-4);
+        let outward = if exit.front_face { exit.normal } else { -exit.normal };
+        let carved_outward = carved.normal;
+        assert!((outward + carved_outward).length() < 1e-4);
+        assert!((outward - Vec3::new(1.0, 0.0, 0.0)).length() < 1e-4);
+
+        // Desde dentro del sólido el primer impacto es esa misma pared
+        let hit = difference.hit(&ray(), 2.5, f32::INFINITY).unwrap();
+        assert!((hit.t - 3.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn difference_misses_when_fully_subtracted() {
+        let (left, _) = operands();
+        let bigger = Cube::new(Vec3::zero(), Vec3::new(3.0, 3.0, 3.0), Material::new());
+        let difference = Csg::difference(left, bigger);
+        assert!(difference.intervals(&ray()).is_empty());
+        assert!(difference.hit(&ray(), 0.001, f32::INFINITY).is_none());
+    }
+}
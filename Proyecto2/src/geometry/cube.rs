@@ -1,8 +1,8 @@
 // Cubos para raytracing
 
-use crate::math::{Vec3, Ray};
+use crate::math::{Vec3, Ray, Rng};
 use crate::material::Material;
-use super::{HitRecord, Hittable};
+use super::{Aabb, HitInterval, HitRecord, Hittable};
 
 const EPSILON: f32 = 0.001;
 
@@ -119,27 +119,123 @@ impl Hittable for Cube {
         }
         
         // Calcula las coordenadas UV según la cara golpeada
-        let center = (self.min + self.max) * 0.5;
-        let local_point = point - center;
-        let half_size = (self.max - self.min) * 0.5;
+        let (u, v) = face_uv(self.min, self.max, point, hit_normal);
         
-        let (u, v) = if hit_normal.x.abs() > 0.5 {
-            // Cara X (izquierda o derecha)
-            let u = (local_point.z / half_size.z + 1.0) * 0.5;
-            let v = (local_point.y / half_size.y + 1.0) * 0.5;
-            (u, 1.0 - v)
-        } else if hit_normal.y.abs() > 0.5 {
-            // Cara Y (arriba o abajo) 
-            let u = (local_point.x / half_size.x + 1.0) * 0.5;
-            let v = (local_point.z / half_size.z + 1.0) * 0.5;
-            (u, 1.0 - v)
-        } else {
-            // Cara Z (frente o atrás)
-            let u = (local_point.x / half_size.x + 1.0) * 0.5;
-            let v = (local_point.y / half_size.y + 1.0) * 0.5;
-            (u, 1.0 - v)
+        Some(HitRecord::new(point, hit_normal, t, ray, self.material.clone(), u, v))
+    }
+    
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(Aabb::new(self.min, self.max))
+    }
+    
+    fn material(&self) -> Option<&Material> {
+        Some(&self.material)
+    }
+    
+    fn surface_area(&self) -> f32 {
+        let size = self.max - self.min;
+        2.0 * (size.x * size.y + size.y * size.z + size.z * size.x)
+    }
+    
+    fn intervals(&self, ray: &Ray) -> Vec<HitInterval> {
+        // Prueba de slabs sobre la recta completa, guardando el eje de entrada y de salida
+        let origin = [ray.origin.x, ray.origin.y, ray.origin.z];
+        let direction = [ray.direction.x, ray.direction.y, ray.direction.z];
+        let min = [self.min.x, self.min.y, self.min.z];
+        let max = [self.max.x, self.max.y, self.max.z];
+        let (mut t_enter, mut t_exit) = (f32::NEG_INFINITY, f32::INFINITY);
+        let (mut enter_normal, mut exit_normal) = (Vec3::zero(), Vec3::zero());
+        
+        for axis in 0..3 {
+            let inv_dir = 1.0 / direction[axis];
+            let mut t0 = (min[axis] - origin[axis]) * inv_dir;
+            let mut t1 = (max[axis] - origin[axis]) * inv_dir;
+            if inv_dir < 0.0 {
+                std::mem::swap(&mut t0, &mut t1);
+            }
+            
+            let sign = if inv_dir < 0.0 { 1.0 } else { -1.0 };
+            let mut normal = Vec3::zero();
+            match axis {
+                0 => normal.x = sign,
+                1 => normal.y = sign,
+                _ => normal.z = sign,
+            }
+            if t0 > t_enter {
+                t_enter = t0;
+                enter_normal = normal;
+            }
+            if t1 < t_exit {
+                t_exit = t1;
+                exit_normal = -normal;
+            }
+        }
+        
+        if t_exit < t_enter || !t_enter.is_finite() || !t_exit.is_finite() {
+            return Vec::new();
+        }
+        
+        let record = |t: f32, normal: Vec3| {
+            let point = ray.at(t);
+            let (u, v) = face_uv(self.min, self.max, point, normal);
+            HitRecord::new(point, normal, t, ray, self.material.clone(), u, v)
         };
+        vec![HitInterval {
+            enter: record(t_enter, enter_normal),
+            exit: record(t_exit, exit_normal),
+        }]
+    }
+    
+    fn sample_surface(&self, rng: &mut Rng) -> Option<(Vec3, Vec3)> {
+        let size = self.max - self.min;
+        let area_x = size.y * size.z;
+        let area_y = size.x * size.z;
+        let area_z = size.x * size.y;
+        let total = area_x + area_y + area_z;
+        if total <= 0.0 {
+            return None;
+        }
         
-        Some(HitRecord::new(point, hit_normal, t, ray, self.material.clone(), u, v))
+        // Elegir un par de caras proporcional a su área y luego una de las dos
+        let pick = rng.next_f32() * total;
+        let (a, b) = (rng.next_f32(), rng.next_f32());
+        let positive = rng.next_f32() < 0.5;
+        let sign = if positive { 1.0 } else { -1.0 };
+        
+        let sample = if pick < area_x {
+            let x = if positive { self.max.x } else { self.min.x };
+            (Vec3::new(x, self.min.y + a * size.y, self.min.z + b * size.z), Vec3::new(sign, 0.0, 0.0))
+        } else if pick < area_x + area_y {
+            let y = if positive { self.max.y } else { self.min.y };
+            (Vec3::new(self.min.x + a * size.x, y, self.min.z + b * size.z), Vec3::new(0.0, sign, 0.0))
+        } else {
+            let z = if positive { self.max.z } else { self.min.z };
+            (Vec3::new(self.min.x + a * size.x, self.min.y + b * size.y, z), Vec3::new(0.0, 0.0, sign))
+        };
+        Some(sample)
+    }
+}
+
+// Coordenadas UV de un punto sobre la cara (indicada por la normal) de una caja alineada
+pub(crate) fn face_uv(min: Vec3, max: Vec3, point: Vec3, hit_normal: Vec3) -> (f32, f32) {
+    let center = (min + max) * 0.5;
+    let local_point = point - center;
+    let half_size = (max - min) * 0.5;
+
+    if hit_normal.x.abs() > 0.5 {
+        // Cara X (izquierda o derecha)
+        let u = (local_point.z / half_size.z + 1.0) * 0.5;
+        let v = (local_point.y / half_size.y + 1.0) * 0.5;
+        (u, 1.0 - v)
+    } else if hit_normal.y.abs() > 0.5 {
+        // Cara Y (arriba o abajo) 
+        let u = (local_point.x / half_size.x + 1.0) * 0.5;
+        let v = (local_point.z / half_size.z + 1.0) * 0.5;
+        (u, 1.0 - v)
+    } else {
+        // Cara Z (frente o atrás)
+        let u = (local_point.x / half_size.x + 1.0) * 0.5;
+        let v = (local_point.y / half_size.y + 1.0) * 0.5;
+        (u, 1.0 - v)
     }
 }
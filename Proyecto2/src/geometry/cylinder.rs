@@ -0,0 +1,155 @@
+// Cilindros con tapas opcionales (tuberías, barandas, toallas enrolladas)
+
+use crate::math::{Vec3, Ray, Mat3};
+use crate::math::polynomial::solve_quadratic;
+use crate::material::Material;
+use super::{axis_frame, disk_bounds, Aabb, HitRecord, Hittable};
+use std::f32::consts::PI;
+
+#[derive(Debug, Clone)]
+pub struct Cylinder {
+    // Centro de la tapa inferior
+    pub base: Vec3,
+    // Dirección del eje (unitaria)
+    pub axis: Vec3,
+    pub radius: f32,
+    pub height: f32,
+    // Si es false el cilindro es un tubo abierto
+    pub capped: bool,
+    pub material: Material,
+    frame: Mat3,
+}
+
+impl Cylinder {
+    pub fn new(base: Vec3, axis: Vec3, radius: f32, height: f32, material: Material) -> Self {
+        let axis = axis.normalize();
+        Cylinder {
+            base,
+            axis,
+            radius: radius.abs(),
+            height: height.abs(),
+            capped: true,
+            material,
+            frame: axis_frame(axis),
+        }
+    }
+
+    // Builder pattern para activar o quitar las tapas
+    pub fn with_caps(mut self, capped: bool) -> Self {
+        self.capped = capped;
+        self
+    }
+}
+
+impl Hittable for Cylinder {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
+        // Rayo en espacio local: eje Y, base en el origen
+        let to_local = self.frame.transpose();
+        let o = to_local * (ray.origin - self.base);
+        let d = to_local * ray.direction;
+        let (r, h) = (self.radius, self.height);
+
+        // (t, normal local, u, v) de la colisión más cercana
+        let mut best: Option<(f32, Vec3, f32, f32)> = None;
+        let mut consider = |t: f32, normal: Vec3, u: f32, v: f32| {
+            if t >= t_min && t <= t_max && best.is_none_or(|(best_t, ..)| t < best_t) {
+                best = Some((t, normal, u, v));
+            }
+        };
+
+        // Pared lateral: x² + z² = r²
+        let a = (d.x * d.x + d.z * d.z) as f64;
+        let b = 2.0 * (o.x * d.x + o.z * d.z) as f64;
+        let c = (o.x * o.x + o.z * o.z - r * r) as f64;
+        for t in solve_quadratic(a, b, c) {
+            let t = t as f32;
+            let p = o + d * t;
+            if p.y >= 0.0 && p.y <= h {
+                let u = 0.5 + p.z.atan2(p.x) / (2.0 * PI);
+                consider(t, Vec3::new(p.x, 0.0, p.z) / r, u, p.y / h);
+            }
+        }
+
+        // Tapas: planos y = 0 e y = h
+        if self.capped && d.y.abs() > 1e-8 {
+            for (cap_y, normal_y) in [(0.0, -1.0), (h, 1.0)] {
+                let t = (cap_y - o.y) / d.y;
+                let p = o + d * t;
+                if p.x * p.x + p.z * p.z <= r * r {
+                    consider(t, Vec3::new(0.0, normal_y, 0.0), p.x / (2.0 * r) + 0.5, p.z / (2.0 * r) + 0.5);
+                }
+            }
+        }
+
+        let (t, local_normal, u, v) = best?;
+        let normal = (self.frame * local_normal).normalize();
+        Some(HitRecord::new(ray.at(t), normal, t, ray, self.material.clone(), u, v))
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        let bottom = disk_bounds(self.base, self.axis, self.radius);
+        let top = disk_bounds(self.base + self.axis * self.height, self.axis, self.radius);
+        Some(bottom.union(&top))
+    }
+
+    fn material(&self) -> Option<&Material> {
+        Some(&self.material)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx_vec(a: Vec3, b: Vec3) -> bool {
+        (a - b).length() < 1e-4
+    }
+
+    fn post() -> Cylinder {
+        Cylinder::new(Vec3::zero(), Vec3::up(), 0.5, 2.0, Material::new())
+    }
+
+    #[test]
+    fn side_hit_has_outward_normal() {
+        let cylinder = post();
+        let hit = cylinder.hit(&Ray::new(Vec3::new(3.0, 1.0, 0.0), Vec3::new(-1.0, 0.0, 0.0)), 0.001, f32::INFINITY).unwrap();
+        assert!((hit.t - 2.5).abs() < 1e-4);
+        assert!(approx_vec(hit.normal, Vec3::new(1.0, 0.0, 0.0)) && hit.front_face);
+        assert!((hit.v - 0.5).abs() < 1e-4);
+
+        // Por encima de la altura no hay pared
+        assert!(cylinder.hit(&Ray::new(Vec3::new(3.0, 2.5, 0.0), Vec3::new(-1.0, 0.0, 0.0)), 0.001, f32::INFINITY).is_none());
+    }
+
+    #[test]
+    fn caps_close_the_tube() {
+        let down = Ray::new(Vec3::new(0.1, 5.0, 0.2), Vec3::new(0.0, -1.0, 0.0));
+        let hit = post().hit(&down, 0.001, f32::INFINITY).unwrap();
+        assert!((hit.t - 3.0).abs() < 1e-4);
+        assert!(approx_vec(hit.normal, Vec3::up()) && hit.front_face);
+        // El centro de la tapa cae en el centro de la textura
+        let center = post().hit(&Ray::new(Vec3::new(0.0, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0)), 0.001, f32::INFINITY).unwrap();
+        assert!((center.u - 0.5).abs() < 1e-4 && (center.v - 0.5).abs() < 1e-4);
+
+        let up = Ray::new(Vec3::new(0.0, -1.0, 0.0), Vec3::up());
+        let bottom = post().hit(&up, 0.001, f32::INFINITY).unwrap();
+        assert!(approx_vec(bottom.normal, Vec3::new(0.0, -1.0, 0.0)) && bottom.front_face);
+
+        // Sin tapas el rayo paralelo al eje atraviesa el tubo
+        let open = post().with_caps(false);
+        assert!(open.hit(&down, 0.001, f32::INFINITY).is_none());
+        // Desde dentro se ve la cara interior de la pared
+        let inner = open.hit(&Ray::new(Vec3::up(), Vec3::new(1.0, 0.0, 0.0)), 0.001, f32::INFINITY).unwrap();
+        assert!((inner.t - 0.5).abs() < 1e-4);
+        assert!(approx_vec(inner.normal, Vec3::new(-1.0, 0.0, 0.0)) && !inner.front_face);
+    }
+
+    #[test]
+    fn follows_tilted_axis() {
+        let cylinder = Cylinder::new(Vec3::zero(), Vec3::new(1.0, 0.0, 0.0), 0.5, 2.0, Material::new());
+        let hit = cylinder.hit(&Ray::new(Vec3::new(1.0, 3.0, 0.0), Vec3::new(0.0, -1.0, 0.0)), 0.001, f32::INFINITY).unwrap();
+        assert!((hit.t - 2.5).abs() < 1e-4);
+        assert!(approx_vec(hit.normal, Vec3::up()));
+        assert!(cylinder.hit(&Ray::new(Vec3::new(2.5, 3.0, 0.0), Vec3::new(0.0, -1.0, 0.0)), 0.001, f32::INFINITY).is_none());
+    }
+}
@@ -0,0 +1,115 @@
+// Discos (tapas, bordes redondos, paneles circulares)
+
+use crate::math::{Vec3, Ray, Rng, Mat3};
+use crate::material::Material;
+use super::{axis_frame, disk_bounds, Aabb, HitRecord, Hittable};
+use std::f32::consts::PI;
+
+#[derive(Debug, Clone)]
+pub struct Disk {
+    pub center: Vec3,
+    pub normal: Vec3,
+    pub radius: f32,
+    pub material: Material,
+    frame: Mat3,
+}
+
+impl Disk {
+    pub fn new(center: Vec3, normal: Vec3, radius: f32, material: Material) -> Self {
+        let normal = normal.normalize();
+        Disk {
+            center,
+            normal,
+            radius: radius.abs(),
+            material,
+            frame: axis_frame(normal),
+        }
+    }
+}
+
+impl Hittable for Disk {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
+        let denom = self.normal.dot(&ray.direction);
+        if denom.abs() < 1e-8 {
+            return None;
+        }
+        let t = self.normal.dot(&(self.center - ray.origin)) / denom;
+        if t < t_min || t > t_max {
+            return None;
+        }
+
+        let point = ray.at(t);
+        let offset = point - self.center;
+        if offset.length_squared() > self.radius * self.radius {
+            return None;
+        }
+
+        // UV polares: u = ángulo, v = distancia al centro
+        let (x, z) = (offset.dot(&self.frame.column(0)), offset.dot(&self.frame.column(2)));
+        let u = 0.5 + z.atan2(x) / (2.0 * PI);
+        let v = offset.length() / self.radius;
+
+        Some(HitRecord::new(point, self.normal, t, ray, self.material.clone(), u, v))
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(disk_bounds(self.center, self.normal, self.radius))
+    }
+
+    fn material(&self) -> Option<&Material> {
+        Some(&self.material)
+    }
+
+    fn surface_area(&self) -> f32 {
+        PI * self.radius * self.radius
+    }
+
+    fn sample_surface(&self, rng: &mut Rng) -> Option<(Vec3, Vec3)> {
+        // sqrt para que la densidad sea uniforme en área
+        let r = self.radius * rng.next_f32().sqrt();
+        let angle = 2.0 * PI * rng.next_f32();
+        let point = self.center + self.frame.column(0) * (r * angle.cos()) + self.frame.column(2) * (r * angle.sin());
+        Some((point, self.normal))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx_vec(a: Vec3, b: Vec3) -> bool {
+        (a - b).length() < 1e-4
+    }
+
+    #[test]
+    fn hits_inside_radius_and_misses_outside() {
+        let normal = Vec3::new(1.0, 1.0, 0.0).normalize();
+        let disk = Disk::new(Vec3::new(1.0, 0.0, 0.0), normal, 0.5, Material::new());
+        let hit = disk.hit(&Ray::new(disk.center + normal * 3.0, -normal), 0.001, f32::INFINITY).unwrap();
+        assert!((hit.t - 3.0).abs() < 1e-5);
+        assert!(approx_vec(hit.normal, normal) && hit.front_face);
+        assert!(hit.v.abs() < 1e-4);
+
+        // Rayo paralelo a la normal desplazado más allá del radio
+        let tangent = Vec3::new(0.0, 0.0, 1.0);
+        let inside = Ray::new(disk.center + tangent * 0.45 + normal * 3.0, -normal);
+        let outside = Ray::new(disk.center + tangent * 0.55 + normal * 3.0, -normal);
+        let edge = disk.hit(&inside, 0.001, f32::INFINITY).unwrap();
+        assert!((edge.v - 0.9).abs() < 1e-4);
+        assert!(disk.hit(&outside, 0.001, f32::INFINITY).is_none());
+        assert!((disk.surface_area() - PI * 0.25).abs() < 1e-5);
+    }
+
+    #[test]
+    fn samples_stay_inside_disk() {
+        let disk = Disk::new(Vec3::new(0.0, 2.0, 0.0), Vec3::new(0.0, 0.0, 1.0), 1.5, Material::new());
+        let mut rng = Rng::new(5);
+        for _ in 0..100 {
+            let (point, normal) = disk.sample_surface(&mut rng).unwrap();
+            let offset = point - disk.center;
+            assert!(offset.dot(&disk.normal).abs() < 1e-5);
+            assert!(offset.length() <= 1.5 + 1e-4);
+            assert!(approx_vec(normal, disk.normal));
+        }
+    }
+}
@@ -0,0 +1,379 @@
+// Terreno a partir de un mapa de alturas, recorrido con una jerarquía de mínimos/máximos
+
+use crate::math::{Vec3, Ray};
+use crate::material::Material;
+use super::{Aabb, HitRecord, Hittable};
+
+// Nivel de la jerarquía: (mínimo, máximo) de altura por nodo
+#[derive(Debug, Clone)]
+struct MipLevel {
+    width: usize,
+    depth: usize,
+    bounds: Vec<(f32, f32)>,
+}
+
+// Colisión con un triángulo de la celda (i, j), con coordenadas baricéntricas
+#[derive(Debug, Clone, Copy)]
+struct CellHit {
+    t: f32,
+    i: usize,
+    j: usize,
+    b1: f32,
+    b2: f32,
+    // Segundo triángulo de la celda (p00, p11, p01)
+    second: bool,
+}
+
+// Estado del recorrido de la jerarquía para un rayo
+struct Traversal<'a> {
+    ray: &'a Ray,
+    t_min: f32,
+    t_max: f32,
+    // Colisión más cercana hasta ahora (acorta el rayo)
+    best: Option<CellHit>,
+}
+
+impl Traversal<'_> {
+    fn limit(&self) -> f32 {
+        self.best.map_or(self.t_max, |hit| hit.t)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Heightfield {
+    // Esquina mínima (x, y base, z) del terreno
+    pub origin: Vec3,
+    // Extensión en x, altura máxima en y y extensión en z
+    pub size: Vec3,
+    pub material: Material,
+    // Muestras por lado (vértices de la malla)
+    samples: (usize, usize),
+    // Alturas en espacio mundo, fila por fila en z
+    heights: Vec<f32>,
+    // Normales suavizadas por vértice
+    normals: Vec<Vec3>,
+    // levels[0] = celdas individuales; el último nivel tiene un solo nodo
+    levels: Vec<MipLevel>,
+}
+
+impl Heightfield {
+    // Crea el terreno a partir de alturas normalizadas [0, 1] (samples.0 * samples.1 valores).
+    // `center` es el centro de la base y `size` = (ancho, altura máxima, profundidad).
+    pub fn new(heights: &[f32], samples: (usize, usize), center: Vec3, size: Vec3, material: Material) -> Self {
+        let samples = (samples.0.max(2), samples.1.max(2));
+        let origin = center - Vec3::new(size.x * 0.5, 0.0, size.z * 0.5);
+        let heights: Vec<f32> = (0..samples.0 * samples.1)
+            .map(|i| origin.y + heights.get(i).copied().unwrap_or(0.0).clamp(0.0, 1.0) * size.y)
+            .collect();
+
+        let mut heightfield = Heightfield {
+            origin,
+            size,
+            material,
+            samples,
+            heights,
+            normals: Vec::new(),
+            levels: Vec::new(),
+        };
+        heightfield.normals = heightfield.compute_normals();
+        heightfield.levels = heightfield.build_levels();
+        heightfield
+    }
+
+    // Carga un mapa de alturas en escala de grises; `resolution` remuestrea a N x N vértices
+    pub fn from_file(file_path: &str, center: Vec3, size: Vec3, resolution: Option<usize>, material: Material) -> Result<Self, Box<dyn std::error::Error>> {
+        use image::ImageReader;
+
+        let img = ImageReader::open(file_path)?.decode()?;
+        let gray = img.to_luma16();
+        let (width, height) = (gray.width() as usize, gray.height() as usize);
+        let source: Vec<f32> = gray.pixels().map(|p| p[0] as f32 / 65535.0).collect();
+
+        let (nx, nz) = match resolution {
+            Some(n) => (n.max(2), n.max(2)),
+            None => (width, height),
+        };
+
+        // Remuestreo bilineal de la imagen a la resolución pedida
+        let sample = |u: f32, v: f32| -> f32 {
+            let x = u * (width - 1) as f32;
+            let y = v * (height - 1) as f32;
+            let (x0, y0) = (x.floor() as usize, y.floor() as usize);
+            let (x1, y1) = ((x0 + 1).min(width - 1), (y0 + 1).min(height - 1));
+            let (fx, fy) = (x - x0 as f32, y - y0 as f32);
+            let at = |x: usize, y: usize| source[y * width + x];
+            let top = at(x0, y0) * (1.0 - fx) + at(x1, y0) * fx;
+            let bottom = at(x0, y1) * (1.0 - fx) + at(x1, y1) * fx;
+            top * (1.0 - fy) + bottom * fy
+        };
+
+        let mut heights = Vec::with_capacity(nx * nz);
+        for j in 0..nz {
+            for i in 0..nx {
+                heights.push(sample(i as f32 / (nx - 1) as f32, j as f32 / (nz - 1) as f32));
+            }
+        }
+
+        Ok(Heightfield::new(&heights, (nx, nz), center, size, material))
+    }
+
+    fn cell_size(&self) -> (f32, f32) {
+        (
+            self.size.x / (self.samples.0 - 1) as f32,
+            self.size.z / (self.samples.1 - 1) as f32,
+        )
+    }
+
+    fn height(&self, i: usize, j: usize) -> f32 {
+        self.heights[j * self.samples.0 + i]
+    }
+
+    fn vertex(&self, i: usize, j: usize) -> Vec3 {
+        let (dx, dz) = self.cell_size();
+        Vec3::new(self.origin.x + i as f32 * dx, self.height(i, j), self.origin.z + j as f32 * dz)
+    }
+
+    // Normales por diferencias centrales sobre la malla
+    fn compute_normals(&self) -> Vec<Vec3> {
+        let (nx, nz) = self.samples;
+        let (dx, dz) = self.cell_size();
+        let mut normals = Vec::with_capacity(nx * nz);
+        for j in 0..nz {
+            for i in 0..nx {
+                let (i0, i1) = (i.saturating_sub(1), (i + 1).min(nx - 1));
+                let (j0, j1) = (j.saturating_sub(1), (j + 1).min(nz - 1));
+                let slope_x = (self.height(i1, j) - self.height(i0, j)) / ((i1 - i0) as f32 * dx);
+                let slope_z = (self.height(i, j1) - self.height(i, j0)) / ((j1 - j0) as f32 * dz);
+                normals.push(Vec3::new(-slope_x, 1.0, -slope_z).normalize());
+            }
+        }
+        normals
+    }
+
+    // Jerarquía de mínimos/máximos: cada nivel agrupa 2x2 nodos del anterior
+    fn build_levels(&self) -> Vec<MipLevel> {
+        let (cells_x, cells_z) = (self.samples.0 - 1, self.samples.1 - 1);
+        let mut bounds = Vec::with_capacity(cells_x * cells_z);
+        for j in 0..cells_z {
+            for i in 0..cells_x {
+                let corners = [self.height(i, j), self.height(i + 1, j), self.height(i, j + 1), self.height(i + 1, j + 1)];
+                let min = corners.iter().copied().fold(f32::INFINITY, f32::min);
+                let max = corners.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+                bounds.push((min, max));
+            }
+        }
+
+        let mut levels = vec![MipLevel { width: cells_x, depth: cells_z, bounds }];
+        while levels.last().is_some_and(|level| level.width > 1 || level.depth > 1) {
+            let previous = levels.last().unwrap();
+            let (width, depth) = (previous.width.div_ceil(2), previous.depth.div_ceil(2));
+            let mut bounds = Vec::with_capacity(width * depth);
+            for j in 0..depth {
+                for i in 0..width {
+                    let mut node = (f32::INFINITY, f32::NEG_INFINITY);
+                    for (ci, cj) in [(2 * i, 2 * j), (2 * i + 1, 2 * j), (2 * i, 2 * j + 1), (2 * i + 1, 2 * j + 1)] {
+                        if ci < previous.width && cj < previous.depth {
+                            let (min, max) = previous.bounds[cj * previous.width + ci];
+                            node = (node.0.min(min), node.1.max(max));
+                        }
+                    }
+                    bounds.push(node);
+                }
+            }
+            levels.push(MipLevel { width, depth, bounds });
+        }
+        levels
+    }
+
+    // Caja del nodo (i, j) del nivel dado
+    fn node_box(&self, level: usize, i: usize, j: usize) -> Aabb {
+        let (dx, dz) = self.cell_size();
+        let span = (1usize << level) as f32;
+        let (min_y, max_y) = self.levels[level].bounds[j * self.levels[level].width + i];
+        let x0 = self.origin.x + i as f32 * span * dx;
+        let z0 = self.origin.z + j as f32 * span * dz;
+        let x1 = (x0 + span * dx).min(self.origin.x + self.size.x);
+        let z1 = (z0 + span * dz).min(self.origin.z + self.size.z);
+        // Margen pequeño para no perder rayos rasantes por redondeo
+        let pad = 1e-4;
+        Aabb::new(Vec3::new(x0 - pad, min_y - pad, z0 - pad), Vec3::new(x1 + pad, max_y + pad, z1 + pad))
+    }
+
+    // Desciende por la jerarquía visitando los hijos en orden de entrada del rayo
+    fn traverse(&self, traversal: &mut Traversal, level: usize, i: usize, j: usize) {
+        let (ray, t_min, t_max) = (traversal.ray, traversal.t_min, traversal.limit());
+        if self.node_box(level, i, j).intersect(ray, t_min, t_max).is_none() {
+            return;
+        }
+
+        if level == 0 {
+            if let Some(hit) = self.intersect_cell(ray, i, j, t_min, t_max) {
+                traversal.best = Some(hit);
+            }
+            return;
+        }
+
+        let child_level = &self.levels[level - 1];
+        let mut children: Vec<(f32, usize, usize)> = Vec::with_capacity(4);
+        for (ci, cj) in [(2 * i, 2 * j), (2 * i + 1, 2 * j), (2 * i, 2 * j + 1), (2 * i + 1, 2 * j + 1)] {
+            if ci < child_level.width
+                && cj < child_level.depth
+                && let Some((t_enter, _)) = self.node_box(level - 1, ci, cj).intersect(ray, t_min, t_max)
+            {
+                children.push((t_enter, ci, cj));
+            }
+        }
+        children.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        for (t_enter, ci, cj) in children {
+            if traversal.best.is_some_and(|hit| hit.t < t_enter) {
+                break;
+            }
+            self.traverse(traversal, level - 1, ci, cj);
+        }
+    }
+
+    // Intersección con los dos triángulos de una celda
+    fn intersect_cell(&self, ray: &Ray, i: usize, j: usize, t_min: f32, t_max: f32) -> Option<CellHit> {
+        let (p00, p10, p01, p11) = (self.vertex(i, j), self.vertex(i + 1, j), self.vertex(i, j + 1), self.vertex(i + 1, j + 1));
+        let mut best: Option<CellHit> = None;
+        for (second, a, b, c) in [(false, p00, p10, p11), (true, p00, p11, p01)] {
+            let limit = best.map_or(t_max, |hit| hit.t);
+            if let Some((t, b1, b2)) = intersect_triangle(ray, a, b, c, t_min, limit) {
+                best = Some(CellHit { t, i, j, b1, b2, second });
+            }
+        }
+        best
+    }
+}
+
+// Möller–Trumbore: (t, coordenada baricéntrica de b, de c)
+fn intersect_triangle(ray: &Ray, a: Vec3, b: Vec3, c: Vec3, t_min: f32, t_max: f32) -> Option<(f32, f32, f32)> {
+    let edge1 = b - a;
+    let edge2 = c - a;
+    let p = ray.direction.cross(&edge2);
+    let det = edge1.dot(&p);
+    if det.abs() < 1e-12 {
+        return None;
+    }
+    let inv_det = 1.0 / det;
+    let s = ray.origin - a;
+    let u = s.dot(&p) * inv_det;
+    // Pequeña tolerancia en los bordes para no dejar agujeros entre triángulos vecinos
+    let tolerance = 1e-5;
+    if u < -tolerance || u > 1.0 + tolerance {
+        return None;
+    }
+    let q = s.cross(&edge1);
+    let v = ray.direction.dot(&q) * inv_det;
+    if v < -tolerance || u + v > 1.0 + tolerance {
+        return None;
+    }
+    let t = edge2.dot(&q) * inv_det;
+    if t < t_min || t > t_max {
+        return None;
+    }
+    Some((t, u, v))
+}
+
+impl Hittable for Heightfield {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
+        let top = self.levels.len() - 1;
+        let mut traversal = Traversal { ray, t_min, t_max, best: None };
+        self.traverse(&mut traversal, top, 0, 0);
+        let CellHit { t, i, j, b1, b2, second } = traversal.best?;
+
+        // Normal suavizada interpolando las normales de los vértices del triángulo
+        let index = |i: usize, j: usize| j * self.samples.0 + i;
+        let (na, nb, nc) = if second {
+            (self.normals[index(i, j)], self.normals[index(i + 1, j + 1)], self.normals[index(i, j + 1)])
+        } else {
+            (self.normals[index(i, j)], self.normals[index(i + 1, j)], self.normals[index(i + 1, j + 1)])
+        };
+        let normal = (na * (1.0 - b1 - b2) + nb * b1 + nc * b2).normalize();
+
+        let point = ray.at(t);
+        let u = ((point.x - self.origin.x) / self.size.x).clamp(0.0, 1.0);
+        let v = ((point.z - self.origin.z) / self.size.z).clamp(0.0, 1.0);
+        Some(HitRecord::new(point, normal, t, ray, self.material.clone(), u, v))
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        let top = self.levels.last()?;
+        let (min_y, max_y) = top.bounds[0];
+        Some(Aabb::new(
+            Vec3::new(self.origin.x, min_y, self.origin.z),
+            Vec3::new(self.origin.x + self.size.x, max_y, self.origin.z + self.size.z),
+        ))
+    }
+
+    fn material(&self) -> Option<&Material> {
+        Some(&self.material)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::Rng;
+
+    // Terreno irregular con lados que no son potencia de dos
+    fn terrain() -> Heightfield {
+        let samples = (11, 6);
+        let mut rng = Rng::new(5);
+        let heights: Vec<f32> = (0..samples.0 * samples.1).map(|_| rng.next_f32()).collect();
+        Heightfield::new(&heights, samples, Vec3::zero(), Vec3::new(4.0, 1.0, 2.0), Material::new())
+    }
+
+    #[test]
+    fn mip_nodes_bound_their_children() {
+        let heightfield = terrain();
+        let levels = &heightfield.levels;
+        assert_eq!((levels[0].width, levels[0].depth), (10, 5));
+        let top = levels.last().unwrap();
+        assert_eq!((top.width, top.depth), (1, 1));
+
+        // Nivel 0: cada celda acota las alturas de sus cuatro esquinas
+        for j in 0..levels[0].depth {
+            for i in 0..levels[0].width {
+                let (min, max) = levels[0].bounds[j * levels[0].width + i];
+                for (ci, cj) in [(i, j), (i + 1, j), (i, j + 1), (i + 1, j + 1)] {
+                    let h = heightfield.height(ci, cj);
+                    assert!(min <= h && h <= max);
+                }
+            }
+        }
+
+        for level in 1..levels.len() {
+            let (parent, child) = (&levels[level], &levels[level - 1]);
+            for j in 0..child.depth {
+                for i in 0..child.width {
+                    let (child_min, child_max) = child.bounds[j * child.width + i];
+                    let (min, max) = parent.bounds[(j / 2) * parent.width + i / 2];
+                    assert!(min <= child_min && child_max <= max, "nivel {} ({}, {})", level, i, j);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn hierarchy_finds_closest_cell() {
+        let heightfield = terrain();
+        let mut rng = Rng::new(9);
+        let (cells_x, cells_z) = (heightfield.samples.0 - 1, heightfield.samples.1 - 1);
+        for _ in 0..200 {
+            let origin = Vec3::new(rng.next_f32() * 6.0 - 1.0, 2.0 + rng.next_f32(), rng.next_f32() * 4.0 - 1.0);
+            let target = Vec3::new(rng.next_f32() * 4.0, rng.next_f32(), rng.next_f32() * 2.0);
+            let ray = Ray::new(origin, (target - origin).normalize());
+
+            // Referencia: todas las celdas sin jerarquía
+            let brute_force = (0..cells_z)
+                .flat_map(|j| (0..cells_x).map(move |i| (i, j)))
+                .filter_map(|(i, j)| heightfield.intersect_cell(&ray, i, j, 1e-3, f32::INFINITY))
+                .map(|hit| hit.t)
+                .fold(f32::INFINITY, f32::min);
+            let hit = heightfield.hit(&ray, 1e-3, f32::INFINITY).map_or(f32::INFINITY, |hit| hit.t);
+            assert!((hit - brute_force).abs() < 1e-4 || hit == brute_force, "{} vs {}", hit, brute_force);
+        }
+    }
+}
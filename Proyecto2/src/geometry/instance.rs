@@ -0,0 +1,200 @@
+// Instancias: un objeto compartido colocado con una transformación arbitraria
+
+use crate::math::{Vec3, Ray, Rng, Mat3, Mat4};
+use crate::material::Material;
+use super::{Aabb, HitInterval, HitRecord, Hittable};
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub struct Instance {
+    // Objeto en su espacio local (puede compartirse entre muchas instancias)
+    pub object: Arc<dyn Hittable + Send + Sync>,
+    // Transformación local -> mundo
+    transform: Mat4,
+    // Transformación mundo -> local (None si la matriz es singular)
+    inverse: Option<Mat4>,
+    // Caja envolvente en espacio mundo
+    bounds: Option<Aabb>,
+}
+
+impl Instance {
+    // Crea una instancia de un objeto compartido
+    pub fn new(object: Arc<dyn Hittable + Send + Sync>, transform: Mat4) -> Self {
+        let bounds = object.bounding_box().map(|b| b.transform(&transform));
+        Instance {
+            object,
+            transform,
+            inverse: transform.inverse(),
+            bounds,
+        }
+    }
+
+    // Crea una instancia tomando posesión del objeto
+    pub fn from_object<T: Hittable + 'static>(object: T, transform: Mat4) -> Self {
+        Instance::new(Arc::new(object), transform)
+    }
+
+    pub fn transform(&self) -> &Mat4 {
+        &self.transform
+    }
+
+    // Reemplaza la transformación (recalcula inversa y caja envolvente)
+    pub fn set_transform(&mut self, transform: Mat4) {
+        *self = Instance::new(self.object.clone(), transform);
+    }
+
+    // Factor de escala si la parte lineal es rotación × escala uniforme
+    fn uniform_scale(&self) -> Option<f32> {
+        let linear = Mat3::from_mat4(&self.transform);
+        let scale = linear.determinant().abs().cbrt();
+        if scale == 0.0 {
+            return None;
+        }
+        let columns = [linear.column(0), linear.column(1), linear.column(2)];
+        let tolerance = 1e-4 * scale * scale;
+        let uniform = (0..3).all(|i| {
+            (columns[i].dot(&columns[i]) - scale * scale).abs() < tolerance
+                && columns[i].dot(&columns[(i + 1) % 3]).abs() < tolerance
+        });
+        uniform.then_some(scale)
+    }
+
+    // Lleva el rayo a espacio local; devuelve también la escala de t (|dirección local|)
+    pub(crate) fn to_local(&self, ray: &Ray) -> Option<(Ray, f32)> {
+        let inverse = self.inverse.as_ref()?;
+        let local_direction = inverse.transform_vector(ray.direction);
+        let scale = local_direction.length();
+        if scale == 0.0 {
+            return None;
+        }
+        Some((ray.spawn(inverse.transform_point(ray.origin), local_direction), scale))
+    }
+
+    // Lleva un registro de colisión local a espacio mundo
+    pub(crate) fn to_world(&self, local: HitRecord, scale: f32) -> HitRecord {
+        let inverse = self.inverse.unwrap_or_default();
+        HitRecord {
+            point: self.transform.transform_point(local.point),
+            // La normal local ya mira contra el rayo; la transformación conserva esa orientación
+            normal: inverse.transform_normal(local.normal),
+            t: local.t / scale,
+            ..local
+        }
+    }
+}
+
+impl Hittable for Instance {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
+        if let Some(bounds) = &self.bounds
+            && !bounds.hit(ray, t_min, t_max)
+        {
+            return None;
+        }
+
+        let (local_ray, scale) = self.to_local(ray)?;
+        let local_hit = self.object.hit(&local_ray, t_min * scale, t_max * scale)?;
+        Some(self.to_world(local_hit, scale))
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        self.bounds
+    }
+    
+    fn intervals(&self, ray: &Ray) -> Vec<HitInterval> {
+        let Some((local_ray, scale)) = self.to_local(ray) else {
+            return Vec::new();
+        };
+        self.object.intervals(&local_ray).into_iter().map(|interval| HitInterval {
+            enter: self.to_world(interval.enter, scale),
+            exit: self.to_world(interval.exit, scale),
+        }).collect()
+    }
+
+    fn material(&self) -> Option<&Material> {
+        self.object.material()
+    }
+
+    fn surface_area(&self) -> f32 {
+        // Con escala no uniforme el área no es un múltiplo fijo del área local
+        // y las muestras dejarían de ser uniformes: no se usa como luz de área
+        self.uniform_scale().map_or(0.0, |scale| self.object.surface_area() * scale * scale)
+    }
+
+    fn sample_surface(&self, rng: &mut Rng) -> Option<(Vec3, Vec3)> {
+        self.uniform_scale()?;
+        let (point, normal) = self.object.sample_surface(rng)?;
+        let inverse = self.inverse.as_ref()?;
+        Some((self.transform.transform_point(point), inverse.transform_normal(normal)))
+    }
+
+    fn is_time_dependent(&self) -> bool {
+        self.object.is_time_dependent()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Cube;
+    use crate::lighting::Emitter;
+    use std::f32::consts::{FRAC_PI_4, SQRT_2};
+
+    fn approx_vec(a: Vec3, b: Vec3) -> bool {
+        (a - b).length() < 1e-4
+    }
+
+    // Cubo de lado 2 girado 45° sobre Y y llevado a (5, 0, 0): una arista apunta hacia -z
+    fn rotated_cube() -> Instance {
+        let cube = Cube::new(Vec3::zero(), Vec3::new(2.0, 2.0, 2.0), Material::new());
+        Instance::from_object(cube, Mat4::translation(Vec3::new(5.0, 0.0, 0.0)) * Mat4::rotation_y(FRAC_PI_4))
+    }
+
+    #[test]
+    fn hit_is_in_world_space() {
+        let instance = rotated_cube();
+        // Rayo en +z algo a la derecha de la arista: golpea la cara con normal (1, 0, -1)/√2
+        let ray = Ray::new(Vec3::new(5.3, 0.2, -10.0), Vec3::new(0.0, 0.0, 1.0));
+        let hit = instance.hit(&ray, 0.001, f32::INFINITY).unwrap();
+
+        let z = 0.3 - SQRT_2;
+        assert!(approx_vec(hit.point, Vec3::new(5.3, 0.2, z)));
+        assert!((hit.t - (z + 10.0)).abs() < 1e-4);
+        assert!(approx_vec(hit.normal, Vec3::new(1.0, 0.0, -1.0) / SQRT_2));
+        assert!(hit.front_face);
+    }
+
+    #[test]
+    fn intervals_match_world_space_hits() {
+        let instance = rotated_cube();
+        let ray = Ray::new(Vec3::new(4.6, -0.5, -10.0), Vec3::new(0.0, 0.0, 1.0));
+        let intervals = instance.intervals(&ray);
+        assert_eq!(intervals.len(), 1);
+
+        // Entra por la cara (-1, 0, -1)/√2 y sale por la contigua, (-1, 0, 1)/√2
+        let (enter, exit) = (&intervals[0].enter, &intervals[0].exit);
+        assert!(approx_vec(enter.point, Vec3::new(4.6, -0.5, 0.4 - SQRT_2)));
+        assert!(approx_vec(enter.normal, Vec3::new(-1.0, 0.0, -1.0) / SQRT_2));
+        assert!(approx_vec(exit.point, Vec3::new(4.6, -0.5, SQRT_2 - 0.4)));
+        // La normal de salida mira contra el rayo (opuesta a la normal exterior)
+        assert!(approx_vec(exit.normal, Vec3::new(1.0, 0.0, -1.0) / SQRT_2));
+        assert!(!exit.front_face);
+    }
+
+    #[test]
+    fn only_uniform_scale_is_sampled_as_area_light() {
+        let light = Material::new().with_emission(Vec3::one());
+        let cube = || Cube::new(Vec3::zero(), Vec3::one(), light.clone());
+        let uniform = Instance::from_object(cube(), Mat4::rotation_y(FRAC_PI_4) * Mat4::scaling(Vec3::new(3.0, 3.0, 3.0)));
+        assert!((uniform.surface_area() - 54.0).abs() < 1e-3);
+        let mut rng = Rng::new(7);
+        let (point, _) = uniform.sample_surface(&mut rng).unwrap();
+        assert!(point.y.abs() <= 1.5 + 1e-4);
+
+        // Una caja estirada no tiene área proporcional a la local
+        let stretched = Instance::from_object(cube(), Mat4::scaling(Vec3::new(4.0, 1.0, 1.0)));
+        assert_eq!(stretched.surface_area(), 0.0);
+        assert!(stretched.sample_surface(&mut rng).is_none());
+        assert!(Emitter::from_object(Arc::new(stretched)).is_none());
+        assert!(Emitter::from_object(Arc::new(uniform)).is_some());
+    }
+}
@@ -1,10 +1,37 @@
 // Módulo de geometría
 pub mod cube;
+pub mod aabb;
+pub mod instance;
+pub mod voxel_grid;
+pub mod csg;
+pub mod sdf;
+pub mod plane;
+pub mod disk;
+pub mod cylinder;
+pub mod cone;
+pub mod torus;
+pub mod heightfield;
+pub mod moving;
+pub mod water;
 
 pub use cube::Cube;
+pub use aabb::Aabb;
+pub use instance::Instance;
+pub use voxel_grid::VoxelGrid;
+pub use csg::{Csg, CsgOperation};
+pub use sdf::{Sdf, SdfObject};
+pub use plane::Plane;
+pub use disk::Disk;
+pub use cylinder::Cylinder;
+pub use cone::Cone;
+pub use torus::Torus;
+pub use heightfield::Heightfield;
+pub use moving::{Moving, Motion};
+pub use water::{WaterSurface, Wave};
 
-use crate::math::{Vec3, Ray};
+use crate::math::{Vec3, Ray, Rng, Mat3};
 use crate::material::Material;
+use std::sync::Arc;
 
 // Registro de información de colisión de un rayo con un objeto
 #[derive(Debug, Clone)]
@@ -42,14 +69,100 @@ impl HitRecord {
     }
 }
 
+// Base ortonormal con `axis` como eje Y local (columnas: tangente, eje, bitangente).
+// Lleva direcciones locales a mundo; su transpuesta hace lo contrario.
+pub(crate) fn axis_frame(axis: Vec3) -> Mat3 {
+    let y = axis.normalize();
+    let helper = if y.x.abs() > 0.9 { Vec3::new(0.0, 0.0, 1.0) } else { Vec3::new(1.0, 0.0, 0.0) };
+    let x = y.cross(&helper).normalize();
+    let z = x.cross(&y);
+    Mat3::from_columns(x, y, z)
+}
+
+// Caja envolvente de un disco de radio `radius` con normal `axis`
+pub(crate) fn disk_bounds(center: Vec3, axis: Vec3, radius: f32) -> Aabb {
+    let a = axis.normalize();
+    let extent = Vec3::new(
+        radius * (1.0 - a.x * a.x).max(0.0).sqrt(),
+        radius * (1.0 - a.y * a.y).max(0.0).sqrt(),
+        radius * (1.0 - a.z * a.z).max(0.0).sqrt(),
+    ) + Vec3::new(1e-4, 1e-4, 1e-4);
+    Aabb::new(center - extent, center + extent)
+}
+
+// Tramo del rayo dentro de un sólido cerrado (para CSG)
+#[derive(Debug, Clone)]
+pub struct HitInterval {
+    // Superficie por la que entra el rayo (front_face = true)
+    pub enter: HitRecord,
+    // Superficie por la que sale el rayo (front_face = false)
+    pub exit: HitRecord,
+}
+
+// Separación mínima entre colisiones consecutivas al recorrer un objeto
+const INTERVAL_EPSILON: f32 = 0.0005;
+
 pub trait Hittable: Send + Sync + std::fmt::Debug {
     // Verifica si el rayo intersecta el objeto entre t_min y t_max
     fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord>;
+    
+    // Caja envolvente en espacio mundo (None si es infinita o desconocida)
+    fn bounding_box(&self) -> Option<Aabb> {
+        None
+    }
+    
+    // Material único del objeto (None si no tiene uno solo); se usa para detectar emisores
+    fn material(&self) -> Option<&Material> {
+        None
+    }
+    
+    // Área de la superficie (0.0 si el objeto no puede muestrearse como luz de área)
+    fn surface_area(&self) -> f32 {
+        0.0
+    }
+    
+    // Muestrea un punto uniforme sobre la superficie: (punto, normal exterior)
+    fn sample_surface(&self, _rng: &mut Rng) -> Option<(Vec3, Vec3)> {
+        None
+    }
+    
+    // Si la superficie cambia con `ray.time` (olas, objetos en movimiento)
+    fn is_time_dependent(&self) -> bool {
+        false
+    }
+    
+    // Todos los tramos del rayo dentro del objeto, ordenados por t (pueden empezar antes de t = 0).
+    // Por defecto encadena llamadas a hit(), lo que sirve para cualquier superficie cerrada.
+    fn intervals(&self, ray: &Ray) -> Vec<HitInterval> {
+        let mut intervals = Vec::new();
+        let mut enter: Option<HitRecord> = None;
+        let mut t = INTERVAL_EPSILON;
+        
+        for _ in 0..64 {
+            let Some(record) = self.hit(ray, t, f32::INFINITY) else {
+                break;
+            };
+            t = record.t + INTERVAL_EPSILON;
+            if record.front_face {
+                enter = Some(record);
+            } else {
+                // Sin entrada previa el rayo nació dentro del objeto
+                let enter = enter.take().unwrap_or_else(|| HitRecord {
+                    t: f32::NEG_INFINITY,
+                    front_face: true,
+                    ..record.clone()
+                });
+                intervals.push(HitInterval { enter, exit: record });
+            }
+        }
+        intervals
+    }
 }
 
 // Lista de objetos que pueden ser intersectados (thread-safe)
+#[derive(Clone)]
 pub struct HittableList {
-    objects: Vec<Box<dyn Hittable + Send + Sync>>,
+    objects: Vec<Arc<dyn Hittable + Send + Sync>>,
 }
 
 // Implementar Debug manualmente para HittableList
@@ -73,7 +186,36 @@ impl HittableList {
     }
 
     pub fn add<T: Hittable + Send + Sync + std::fmt::Debug + 'static>(&mut self, object: T) {
-        self.objects.push(Box::new(object));
+        self.objects.push(Arc::new(object));
+    }
+    
+    // Añade un objeto compartido (el mismo objeto puede estar en varias listas)
+    pub fn add_shared(&mut self, object: Arc<dyn Hittable + Send + Sync>) {
+        self.objects.push(object);
+    }
+    
+    // Objeto en una posición de la lista
+    pub fn get(&self, index: usize) -> Option<Arc<dyn Hittable + Send + Sync>> {
+        self.objects.get(index).cloned()
+    }
+    
+    // Reemplaza el objeto en una posición (no hace nada si el índice no existe)
+    pub fn set(&mut self, index: usize, object: Arc<dyn Hittable + Send + Sync>) {
+        if let Some(slot) = self.objects.get_mut(index) {
+            *slot = object;
+        }
+    }
+    
+    pub fn iter(&self) -> impl Iterator<Item = &Arc<dyn Hittable + Send + Sync>> {
+        self.objects.iter()
+    }
+    
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
     }
 }
 
@@ -91,4 +233,20 @@ impl Hittable for HittableList {
 
         closest_hit
     }
+    
+    fn bounding_box(&self) -> Option<Aabb> {
+        let mut result: Option<Aabb> = None;
+        for object in &self.objects {
+            let object_box = object.bounding_box()?;
+            result = Some(match result {
+                Some(b) => b.union(&object_box),
+                None => object_box,
+            });
+        }
+        result
+    }
+    
+    fn is_time_dependent(&self) -> bool {
+        self.objects.iter().any(|object| object.is_time_dependent())
+    }
 }
@@ -0,0 +1,229 @@
+// Objetos en movimiento: desplazan un objeto según el instante del rayo (motion blur)
+
+use crate::math::{Vec3, Ray};
+use crate::material::Material;
+use super::{Aabb, HitInterval, HitRecord, Hittable};
+use std::sync::Arc;
+
+// Trayectoria de un objeto como desplazamiento respecto a su posición original
+#[derive(Debug, Clone)]
+pub enum Motion {
+    // Velocidad constante en unidades por segundo (desplazamiento = velocidad · t), sin límite
+    Linear { velocity: Vec3 },
+    // Fotogramas clave (instante, desplazamiento) ordenados por instante
+    Keyframed(Vec<(f32, Vec3)>),
+}
+
+impl Motion {
+    // Movimiento lineal con velocidad constante (unidades por segundo)
+    pub fn linear(velocity: Vec3) -> Self {
+        Motion::Linear { velocity }
+    }
+
+    // Crea una trayectoria por fotogramas clave (se ordenan por instante)
+    pub fn keyframed(mut keys: Vec<(f32, Vec3)>) -> Self {
+        keys.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Motion::Keyframed(keys)
+    }
+
+    // Desplazamiento en un instante; los fotogramas clave mantienen el extremo más cercano
+    // fuera de su intervalo
+    pub fn offset_at(&self, time: f32) -> Vec3 {
+        match self {
+            Motion::Linear { velocity } => *velocity * time,
+            Motion::Keyframed(keys) => {
+                let Some(&(first_time, first)) = keys.first() else {
+                    return Vec3::zero();
+                };
+                if time <= first_time {
+                    return first;
+                }
+                // Primer fotograma posterior al instante
+                let next = keys.partition_point(|&(t, _)| t <= time);
+                if next >= keys.len() {
+                    return keys[keys.len() - 1].1;
+                }
+                let (t0, a) = keys[next - 1];
+                let (t1, b) = keys[next];
+                a.lerp(&b, (time - t0) / (t1 - t0))
+            }
+        }
+    }
+
+    // Desplazamientos extremos entre los instantes start y end: la interpolación lineal
+    // nunca sale de la envolvente de los extremos y de los fotogramas intermedios
+    fn extremes(&self, start: f32, end: f32) -> Vec<Vec3> {
+        let mut extremes = vec![self.offset_at(start), self.offset_at(end)];
+        if let Motion::Keyframed(keys) = self {
+            extremes.extend(keys.iter().filter(|&&(t, _)| t > start && t < end).map(|&(_, offset)| offset));
+        }
+        extremes
+    }
+    
+    // Intervalo en el que la trayectoria cambia (None si no tiene límites)
+    fn time_range(&self) -> Option<(f32, f32)> {
+        match self {
+            Motion::Linear { .. } => None,
+            Motion::Keyframed(keys) => Some((keys.first()?.0, keys.last()?.0)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Moving {
+    pub object: Arc<dyn Hittable + Send + Sync>,
+    pub motion: Motion,
+    // Caja del objeto en su posición original (se desplaza con el instante de cada rayo)
+    object_bounds: Option<Aabb>,
+    // Caja que cubre el objeto durante el intervalo renderizado (None si no tiene límites)
+    bounds: Option<Aabb>,
+}
+
+impl Moving {
+    pub fn new(object: Arc<dyn Hittable + Send + Sync>, motion: Motion) -> Self {
+        let object_bounds = object.bounding_box();
+        let mut moving = Moving { object, motion, object_bounds, bounds: None };
+        if let Some((start, end)) = moving.motion.time_range() {
+            moving.bounds = moving.bounds_between(start, end);
+        }
+        moving
+    }
+    
+    // Builder pattern para acotar la caja al intervalo que se va a renderizar
+    // (p. ej. el obturador de la cámara)
+    pub fn with_time_interval(mut self, start: f32, end: f32) -> Self {
+        self.bounds = self.bounds_between(start, end.max(start));
+        self
+    }
+
+    // Crea un objeto en movimiento tomando posesión del objeto
+    pub fn from_object<T: Hittable + 'static>(object: T, motion: Motion) -> Self {
+        Moving::new(Arc::new(object), motion)
+    }
+
+    fn bounds_between(&self, start: f32, end: f32) -> Option<Aabb> {
+        let b = self.object_bounds?;
+        self.motion
+            .extremes(start, end)
+            .into_iter()
+            .map(|offset| Aabb::new(b.min + offset, b.max + offset))
+            .reduce(|a, c| a.union(&c))
+    }
+
+    // Lleva el rayo a la posición original del objeto en el instante del rayo
+    fn to_local(&self, ray: &Ray) -> (Ray, Vec3) {
+        let offset = self.motion.offset_at(ray.time);
+        (ray.spawn(ray.origin - offset, ray.direction), offset)
+    }
+}
+
+impl Hittable for Moving {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
+        // La caja original contra el rayo local equivale a la caja en el instante del rayo
+        let (local_ray, offset) = self.to_local(ray);
+        if let Some(bounds) = &self.object_bounds
+            && !bounds.hit(&local_ray, t_min, t_max)
+        {
+            return None;
+        }
+
+        let mut hit = self.object.hit(&local_ray, t_min, t_max)?;
+        hit.point += offset;
+        Some(hit)
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        self.bounds
+    }
+
+    fn intervals(&self, ray: &Ray) -> Vec<HitInterval> {
+        let (local_ray, offset) = self.to_local(ray);
+        let mut intervals = self.object.intervals(&local_ray);
+        for interval in &mut intervals {
+            interval.enter.point += offset;
+            interval.exit.point += offset;
+        }
+        intervals
+    }
+
+    fn material(&self) -> Option<&Material> {
+        self.object.material()
+    }
+
+    // El muestreo de luces de área no conoce el instante del rayo: un emisor en
+    // movimiento solo ilumina al ser golpeado (sus muestras quedarían en otro sitio)
+    fn surface_area(&self) -> f32 {
+        0.0
+    }
+
+    fn is_time_dependent(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Cube;
+    use crate::lighting::Emitter;
+    use crate::math::Rng;
+
+    fn approx_vec(a: Vec3, b: Vec3) -> bool {
+        (a - b).length() < 1e-5
+    }
+
+    #[test]
+    fn linear_offset_grows_with_time() {
+        let motion = Motion::linear(Vec3::new(2.0, 0.0, -1.0));
+        assert!(approx_vec(motion.offset_at(0.0), Vec3::zero()));
+        assert!(approx_vec(motion.offset_at(0.5), Vec3::new(1.0, 0.0, -0.5)));
+        // Sigue moviéndose pasado el primer segundo (secuencias y visor)
+        assert!(approx_vec(motion.offset_at(10.0), Vec3::new(20.0, 0.0, -10.0)));
+        assert!(approx_vec(motion.offset_at(-1.0), Vec3::new(-2.0, 0.0, 1.0)));
+    }
+
+    #[test]
+    fn keyframed_offset_interpolates_and_holds_ends() {
+        let motion = Motion::keyframed(vec![
+            (2.0, Vec3::new(0.0, 4.0, 0.0)),
+            (0.0, Vec3::zero()),
+            (1.0, Vec3::new(2.0, 0.0, 0.0)),
+        ]);
+        assert!(approx_vec(motion.offset_at(0.5), Vec3::new(1.0, 0.0, 0.0)));
+        assert!(approx_vec(motion.offset_at(1.5), Vec3::new(1.0, 2.0, 0.0)));
+        assert!(approx_vec(motion.offset_at(-3.0), Vec3::zero()));
+        assert!(approx_vec(motion.offset_at(7.0), Vec3::new(0.0, 4.0, 0.0)));
+        assert!(approx_vec(Motion::keyframed(Vec::new()).offset_at(1.0), Vec3::zero()));
+    }
+
+    #[test]
+    fn bounds_cover_rendered_interval() {
+        let cube = Cube::new(Vec3::zero(), Vec3::new(1.0, 1.0, 1.0), Material::new());
+        let moving = Moving::from_object(cube, Motion::linear(Vec3::new(1.0, 0.0, 0.0)));
+        assert!(moving.bounding_box().is_none());
+
+        let bounds = moving.with_time_interval(3.0, 4.0).bounding_box().unwrap();
+        assert!((bounds.min.x - 2.5).abs() < 1e-5 && (bounds.max.x - 4.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn hit_follows_ray_time() {
+        let cube = Cube::new(Vec3::zero(), Vec3::new(1.0, 1.0, 1.0), Material::new());
+        let moving = Moving::from_object(cube, Motion::linear(Vec3::new(0.0, 0.0, -1.0)));
+        let ray = |time: f32| Ray::new(Vec3::new(0.0, 0.0, 10.0), Vec3::new(0.0, 0.0, -1.0)).with_time(time);
+
+        let early = moving.hit(&ray(0.0), 1e-3, f32::INFINITY).unwrap();
+        let late = moving.hit(&ray(5.0), 1e-3, f32::INFINITY).unwrap();
+        assert!((early.t - 9.5).abs() < 1e-4);
+        assert!((late.t - 14.5).abs() < 1e-4);
+        assert!(approx_vec(late.point, Vec3::new(0.0, 0.0, -4.5)));
+    }
+
+    #[test]
+    fn moving_emitters_are_not_area_lights() {
+        let light = Material::new().with_emission(Vec3::one());
+        let moving = Moving::from_object(Cube::new(Vec3::zero(), Vec3::one(), light), Motion::linear(Vec3::one()));
+        assert!(moving.sample_surface(&mut Rng::new(1)).is_none());
+        assert!(Emitter::from_object(Arc::new(moving)).is_none());
+    }
+}
@@ -0,0 +1,166 @@
+// Planos infinitos o rectangulares (suelo, paneles)
+
+use crate::math::{Vec3, Ray, Rng, Mat3};
+use crate::material::Material;
+use super::{axis_frame, Aabb, HitRecord, Hittable};
+
+#[derive(Debug, Clone)]
+pub struct Plane {
+    // Punto del plano (centro si está acotado)
+    pub point: Vec3,
+    pub normal: Vec3,
+    pub material: Material,
+    // Semiancho y semiprofundidad del rectángulo (None = plano infinito)
+    pub half_size: Option<(f32, f32)>,
+    // Tamaño de una repetición de textura en el plano infinito
+    pub uv_scale: f32,
+    frame: Mat3,
+}
+
+impl Plane {
+    // Crea un plano infinito
+    pub fn new(point: Vec3, normal: Vec3, material: Material) -> Self {
+        let normal = normal.normalize();
+        Plane {
+            point,
+            normal,
+            material,
+            half_size: None,
+            uv_scale: 1.0,
+            frame: axis_frame(normal),
+        }
+    }
+
+    // Builder pattern para acotar el plano a un rectángulo de ancho x profundidad
+    pub fn with_size(mut self, width: f32, depth: f32) -> Self {
+        self.half_size = Some((width.abs() * 0.5, depth.abs() * 0.5));
+        self
+    }
+
+    // Builder pattern para configurar la repetición de la textura
+    pub fn with_uv_scale(mut self, uv_scale: f32) -> Self {
+        self.uv_scale = uv_scale.max(1e-4);
+        self
+    }
+}
+
+impl Hittable for Plane {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
+        let denom = self.normal.dot(&ray.direction);
+        if denom.abs() < 1e-8 {
+            return None;
+        }
+        let t = self.normal.dot(&(self.point - ray.origin)) / denom;
+        if t < t_min || t > t_max {
+            return None;
+        }
+
+        let point = ray.at(t);
+        let offset = point - self.point;
+        let (x, z) = (offset.dot(&self.frame.column(0)), offset.dot(&self.frame.column(2)));
+
+        let (u, v) = match self.half_size {
+            Some((half_width, half_depth)) => {
+                if x.abs() > half_width || z.abs() > half_depth {
+                    return None;
+                }
+                // Igual que la cara superior de un cubo
+                ((x / half_width + 1.0) * 0.5, 1.0 - (z / half_depth + 1.0) * 0.5)
+            }
+            None => ((x / self.uv_scale).rem_euclid(1.0), (z / self.uv_scale).rem_euclid(1.0)),
+        };
+
+        Some(HitRecord::new(point, self.normal, t, ray, self.material.clone(), u, v))
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        let (half_width, half_depth) = self.half_size?;
+        let corner = |sx: f32, sz: f32| {
+            self.point + self.frame.column(0) * (sx * half_width) + self.frame.column(2) * (sz * half_depth)
+        };
+        let pad = Vec3::new(1e-4, 1e-4, 1e-4);
+        let mut bounds = Aabb::new(corner(-1.0, -1.0) - pad, corner(-1.0, -1.0) + pad);
+        for (sx, sz) in [(1.0, -1.0), (-1.0, 1.0), (1.0, 1.0)] {
+            let c = corner(sx, sz);
+            bounds = bounds.union(&Aabb::new(c - pad, c + pad));
+        }
+        Some(bounds)
+    }
+
+    fn material(&self) -> Option<&Material> {
+        Some(&self.material)
+    }
+
+    fn surface_area(&self) -> f32 {
+        self.half_size.map_or(0.0, |(half_width, half_depth)| 4.0 * half_width * half_depth)
+    }
+
+    fn sample_surface(&self, rng: &mut Rng) -> Option<(Vec3, Vec3)> {
+        let (half_width, half_depth) = self.half_size?;
+        let x = (rng.next_f32() * 2.0 - 1.0) * half_width;
+        let z = (rng.next_f32() * 2.0 - 1.0) * half_depth;
+        let point = self.point + self.frame.column(0) * x + self.frame.column(2) * z;
+        Some((point, self.normal))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx_vec(a: Vec3, b: Vec3) -> bool {
+        (a - b).length() < 1e-4
+    }
+
+    fn floor() -> Plane {
+        Plane::new(Vec3::zero(), Vec3::up(), Material::new())
+    }
+
+    #[test]
+    fn infinite_plane_hits_from_both_sides() {
+        let plane = floor();
+        let hit = plane.hit(&Ray::new(Vec3::new(30.0, 2.0, -7.0), Vec3::new(0.0, -1.0, 0.0)), 0.001, f32::INFINITY).unwrap();
+        assert!((hit.t - 2.0).abs() < 1e-5);
+        assert!(approx_vec(hit.normal, Vec3::up()) && hit.front_face);
+        assert!((0.0..1.0).contains(&hit.u) && (0.0..1.0).contains(&hit.v));
+
+        // Desde abajo la normal mira contra el rayo
+        let below = plane.hit(&Ray::new(Vec3::new(0.0, -1.0, 0.0), Vec3::up()), 0.001, f32::INFINITY).unwrap();
+        assert!(approx_vec(below.normal, Vec3::new(0.0, -1.0, 0.0)) && !below.front_face);
+
+        assert!(plane.hit(&Ray::new(Vec3::up(), Vec3::new(1.0, 0.0, 0.0)), 0.001, f32::INFINITY).is_none());
+        assert!(plane.bounding_box().is_none());
+    }
+
+    #[test]
+    fn bounded_plane_misses_outside_rectangle() {
+        let plane = floor().with_size(4.0, 2.0);
+        // Ancho a lo largo del eje X local y fondo a lo largo del Z local
+        let (width_axis, depth_axis) = (plane.frame.column(0), plane.frame.column(2));
+        let down = |x: f32, z: f32| Ray::new(width_axis * x + depth_axis * z + Vec3::up(), Vec3::new(0.0, -1.0, 0.0));
+        assert!(plane.hit(&down(1.9, 0.9), 0.001, f32::INFINITY).is_some());
+        assert!(plane.hit(&down(2.1, 0.0), 0.001, f32::INFINITY).is_none());
+        assert!(plane.hit(&down(0.0, 1.1), 0.001, f32::INFINITY).is_none());
+
+        // El centro cae en el centro de la textura
+        let center = plane.hit(&down(0.0, 0.0), 0.001, f32::INFINITY).unwrap();
+        assert!((center.u - 0.5).abs() < 1e-5 && (center.v - 0.5).abs() < 1e-5);
+        assert_eq!(plane.surface_area(), 8.0);
+    }
+
+    #[test]
+    fn samples_stay_on_rectangle() {
+        let plane = Plane::new(Vec3::new(1.0, 2.0, 3.0), Vec3::new(1.0, 1.0, 0.0), Material::new()).with_size(2.0, 1.0);
+        let bounds = plane.bounding_box().unwrap();
+        let mut rng = Rng::new(3);
+        for _ in 0..100 {
+            let (point, normal) = plane.sample_surface(&mut rng).unwrap();
+            assert!((point - plane.point).dot(&plane.normal).abs() < 1e-5);
+            assert!(approx_vec(normal, plane.normal));
+            let eps = 1e-4;
+            assert!(point.x >= bounds.min.x - eps && point.x <= bounds.max.x + eps);
+            assert!(point.y >= bounds.min.y - eps && point.y <= bounds.max.y + eps);
+            assert!(point.z >= bounds.min.z - eps && point.z <= bounds.max.z + eps);
+        }
+    }
+}
@@ -0,0 +1,353 @@
+// Objetos definidos por campos de distancia con signo (SDF), dibujados con sphere tracing
+
+use crate::math::{Vec3, Ray};
+use crate::material::Material;
+use super::{Aabb, HitRecord, Hittable};
+use std::str::FromStr;
+
+// Función de distancia componible (distancias en espacio local del objeto)
+#[derive(Debug, Clone)]
+pub enum Sdf {
+    Sphere { radius: f32 },
+    // Caja centrada en el origen con semiejes `half_size`
+    Box { half_size: Vec3 },
+    RoundBox { half_size: Vec3, radius: f32 },
+    // Toro en el plano XZ alrededor del eje Y
+    Torus { major: f32, minor: f32 },
+    Union(Box<Sdf>, Box<Sdf>),
+    // Unión suave con radio de mezcla `k`
+    SmoothUnion { a: Box<Sdf>, b: Box<Sdf>, k: f32 },
+    Intersection(Box<Sdf>, Box<Sdf>),
+    // Puntos de `a` fuera de `b`
+    Subtraction(Box<Sdf>, Box<Sdf>),
+    // Torsión alrededor del eje Y (`amount` radianes por unidad de altura)
+    Twist { inner: Box<Sdf>, amount: f32 },
+    Translate { inner: Box<Sdf>, offset: Vec3 },
+    Scale { inner: Box<Sdf>, factor: f32 },
+}
+
+impl Sdf {
+    pub fn sphere(radius: f32) -> Self {
+        Sdf::Sphere { radius }
+    }
+
+    pub fn cuboid(half_size: Vec3) -> Self {
+        Sdf::Box { half_size }
+    }
+
+    pub fn round_box(half_size: Vec3, radius: f32) -> Self {
+        Sdf::RoundBox { half_size, radius }
+    }
+
+    pub fn torus(major: f32, minor: f32) -> Self {
+        Sdf::Torus { major, minor }
+    }
+
+    pub fn union(self, other: Sdf) -> Self {
+        Sdf::Union(Box::new(self), Box::new(other))
+    }
+
+    pub fn smooth_union(self, other: Sdf, k: f32) -> Self {
+        Sdf::SmoothUnion { a: Box::new(self), b: Box::new(other), k: k.max(1e-4) }
+    }
+
+    pub fn intersection(self, other: Sdf) -> Self {
+        Sdf::Intersection(Box::new(self), Box::new(other))
+    }
+
+    pub fn subtract(self, other: Sdf) -> Self {
+        Sdf::Subtraction(Box::new(self), Box::new(other))
+    }
+
+    pub fn twist(self, amount: f32) -> Self {
+        Sdf::Twist { inner: Box::new(self), amount }
+    }
+
+    pub fn translate(self, offset: Vec3) -> Self {
+        Sdf::Translate { inner: Box::new(self), offset }
+    }
+
+    pub fn scale(self, factor: f32) -> Self {
+        Sdf::Scale { inner: Box::new(self), factor: factor.max(1e-4) }
+    }
+
+    // Distancia con signo del punto a la superficie (negativa dentro)
+    pub fn distance(&self, p: Vec3) -> f32 {
+        match self {
+            Sdf::Sphere { radius } => p.length() - radius,
+            Sdf::Box { half_size } => {
+                let q = Vec3::new(p.x.abs(), p.y.abs(), p.z.abs()) - *half_size;
+                let outside = Vec3::new(q.x.max(0.0), q.y.max(0.0), q.z.max(0.0)).length();
+                outside + q.x.max(q.y).max(q.z).min(0.0)
+            }
+            Sdf::RoundBox { half_size, radius } => {
+                Sdf::Box { half_size: *half_size }.distance(p) - radius
+            }
+            Sdf::Torus { major, minor } => {
+                let ring = (p.x * p.x + p.z * p.z).sqrt() - major;
+                (ring * ring + p.y * p.y).sqrt() - minor
+            }
+            Sdf::Union(a, b) => a.distance(p).min(b.distance(p)),
+            Sdf::SmoothUnion { a, b, k } => {
+                // Mínimo suave polinómico
+                let (da, db) = (a.distance(p), b.distance(p));
+                let h = (k - (da - db).abs()).max(0.0) / k;
+                da.min(db) - h * h * k * 0.25
+            }
+            Sdf::Intersection(a, b) => a.distance(p).max(b.distance(p)),
+            Sdf::Subtraction(a, b) => a.distance(p).max(-b.distance(p)),
+            Sdf::Twist { inner, amount } => {
+                let (s, c) = (amount * p.y).sin_cos();
+                inner.distance(Vec3::new(c * p.x - s * p.z, p.y, s * p.x + c * p.z))
+            }
+            Sdf::Translate { inner, offset } => inner.distance(p - *offset),
+            Sdf::Scale { inner, factor } => inner.distance(p / *factor) * factor,
+        }
+    }
+
+    // Caja envolvente conservadora en espacio local
+    pub fn bounds(&self) -> Aabb {
+        match self {
+            Sdf::Sphere { radius } => symmetric_box(Vec3::new(*radius, *radius, *radius)),
+            Sdf::Box { half_size } => symmetric_box(*half_size),
+            Sdf::RoundBox { half_size, radius } => symmetric_box(*half_size + Vec3::new(*radius, *radius, *radius)),
+            Sdf::Torus { major, minor } => symmetric_box(Vec3::new(major + minor, *minor, major + minor)),
+            Sdf::Union(a, b) => a.bounds().union(&b.bounds()),
+            Sdf::SmoothUnion { a, b, k } => {
+                let b = a.bounds().union(&b.bounds());
+                let pad = Vec3::new(*k, *k, *k) * 0.25;
+                Aabb::new(b.min - pad, b.max + pad)
+            }
+            Sdf::Intersection(a, b) => {
+                let (a, b) = (a.bounds(), b.bounds());
+                Aabb::new(
+                    Vec3::new(a.min.x.max(b.min.x), a.min.y.max(b.min.y), a.min.z.max(b.min.z)),
+                    Vec3::new(a.max.x.min(b.max.x), a.max.y.min(b.max.y), a.max.z.min(b.max.z)),
+                )
+            }
+            Sdf::Subtraction(a, _) => a.bounds(),
+            Sdf::Twist { inner, .. } => {
+                let b = inner.bounds();
+                let radius = radial_extent(&b);
+                Aabb::new(Vec3::new(-radius, b.min.y, -radius), Vec3::new(radius, b.max.y, radius))
+            }
+            Sdf::Translate { inner, offset } => {
+                let b = inner.bounds();
+                Aabb::new(b.min + *offset, b.max + *offset)
+            }
+            Sdf::Scale { inner, factor } => {
+                let b = inner.bounds();
+                Aabb::new(b.min * *factor, b.max * *factor)
+            }
+        }
+    }
+
+    // Cota de la constante de Lipschitz (1 = distancia exacta); la torsión la aumenta
+    pub fn lipschitz(&self) -> f32 {
+        match self {
+            Sdf::Union(a, b) | Sdf::Intersection(a, b) | Sdf::Subtraction(a, b) => a.lipschitz().max(b.lipschitz()),
+            Sdf::SmoothUnion { a, b, .. } => a.lipschitz().max(b.lipschitz()),
+            Sdf::Twist { inner, amount } => {
+                let radius = radial_extent(&inner.bounds());
+                inner.lipschitz() * (1.0 + (amount * radius).powi(2)).sqrt()
+            }
+            Sdf::Translate { inner, .. } | Sdf::Scale { inner, .. } => inner.lipschitz(),
+            _ => 1.0,
+        }
+    }
+}
+
+fn symmetric_box(half_size: Vec3) -> Aabb {
+    Aabb::new(-half_size, half_size)
+}
+
+// Radio máximo de la caja alrededor del eje Y
+fn radial_extent(b: &Aabb) -> f32 {
+    let x = b.min.x.abs().max(b.max.x.abs());
+    let z = b.min.z.abs().max(b.max.z.abs());
+    (x * x + z * z).sqrt()
+}
+
+// Formato de texto en expresiones-s, por ejemplo:
+// (smooth_union 0.1 (sphere 0.3) (translate 0.4 0 0 (round_box 0.2 0.1 0.2 0.05)))
+impl FromStr for Sdf {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let spaced = text.replace('(', " ( ").replace(')', " ) ");
+        let tokens: Vec<&str> = spaced.split_whitespace().collect();
+        let mut position = 0;
+        let sdf = parse_expression(&tokens, &mut position)?;
+        if position != tokens.len() {
+            return Err(format!("texto sobrante en la expresión SDF: '{}'", tokens[position..].join(" ")));
+        }
+        Ok(sdf)
+    }
+}
+
+fn parse_expression(tokens: &[&str], position: &mut usize) -> Result<Sdf, String> {
+    let expect = |position: &mut usize, token: &str| -> Result<(), String> {
+        match tokens.get(*position) {
+            Some(t) if *t == token => {
+                *position += 1;
+                Ok(())
+            }
+            Some(t) => Err(format!("se esperaba '{}' y se encontró '{}'", token, t)),
+            None => Err(format!("se esperaba '{}' al final de la expresión SDF", token)),
+        }
+    };
+    let number = |position: &mut usize| -> Result<f32, String> {
+        let token = tokens.get(*position).ok_or("faltan números en la expresión SDF")?;
+        *position += 1;
+        token.parse::<f32>().map_err(|_| format!("número inválido '{}'", token))
+    };
+    let vector = |position: &mut usize| -> Result<Vec3, String> {
+        Ok(Vec3::new(number(position)?, number(position)?, number(position)?))
+    };
+
+    expect(position, "(")?;
+    let name = *tokens.get(*position).ok_or("expresión SDF vacía")?;
+    *position += 1;
+
+    let sdf = match name {
+        "sphere" => Sdf::sphere(number(position)?),
+        "box" => Sdf::cuboid(vector(position)?),
+        "round_box" => {
+            let half_size = vector(position)?;
+            Sdf::round_box(half_size, number(position)?)
+        }
+        "torus" => {
+            let major = number(position)?;
+            Sdf::torus(major, number(position)?)
+        }
+        "union" | "smooth_union" | "intersection" | "subtract" => {
+            let k = if name == "smooth_union" { number(position)? } else { 0.0 };
+            let mut result = parse_expression(tokens, position)?;
+            while tokens.get(*position) == Some(&"(") {
+                let next = parse_expression(tokens, position)?;
+                result = match name {
+                    "union" => result.union(next),
+                    "smooth_union" => result.smooth_union(next, k),
+                    "intersection" => result.intersection(next),
+                    _ => result.subtract(next),
+                };
+            }
+            result
+        }
+        "twist" => {
+            let amount = number(position)?;
+            parse_expression(tokens, position)?.twist(amount)
+        }
+        "translate" => {
+            let offset = vector(position)?;
+            parse_expression(tokens, position)?.translate(offset)
+        }
+        "scale" => {
+            let factor = number(position)?;
+            parse_expression(tokens, position)?.scale(factor)
+        }
+        other => return Err(format!("forma SDF desconocida '{}'", other)),
+    };
+
+    expect(position, ")")?;
+    Ok(sdf)
+}
+
+#[derive(Debug, Clone)]
+pub struct SdfObject {
+    pub sdf: Sdf,
+    pub material: Material,
+    // Posición del origen local del SDF en la escena
+    pub position: Vec3,
+    // Máximo de pasos de sphere tracing por rayo
+    pub max_steps: u32,
+    // Distancia a la que se considera que el rayo tocó la superficie
+    pub precision: f32,
+    bounds: Aabb,
+    // Factor de seguridad del paso (1 / Lipschitz)
+    step_scale: f32,
+}
+
+impl SdfObject {
+    pub fn new(sdf: Sdf, position: Vec3, material: Material) -> Self {
+        let local = sdf.bounds();
+        SdfObject {
+            bounds: Aabb::new(local.min + position, local.max + position),
+            step_scale: 1.0 / sdf.lipschitz(),
+            sdf,
+            material,
+            position,
+            max_steps: 128,
+            precision: 0.0005,
+        }
+    }
+
+    // Builder pattern para configurar el límite de pasos
+    pub fn with_max_steps(mut self, max_steps: u32) -> Self {
+        self.max_steps = max_steps.max(1);
+        self
+    }
+
+    // Builder pattern para configurar la precisión de superficie
+    pub fn with_precision(mut self, precision: f32) -> Self {
+        self.precision = precision.max(1e-6);
+        self
+    }
+
+    fn distance(&self, point: Vec3) -> f32 {
+        self.sdf.distance(point - self.position)
+    }
+
+    // Normal por diferencias centrales (gradiente del campo)
+    fn normal_at(&self, point: Vec3) -> Vec3 {
+        let h = self.precision.max(1e-4);
+        let dx = Vec3::new(h, 0.0, 0.0);
+        let dy = Vec3::new(0.0, h, 0.0);
+        let dz = Vec3::new(0.0, 0.0, h);
+        Vec3::new(
+            self.distance(point + dx) - self.distance(point - dx),
+            self.distance(point + dy) - self.distance(point - dy),
+            self.distance(point + dz) - self.distance(point - dz),
+        )
+        .normalize()
+    }
+}
+
+impl Hittable for SdfObject {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
+        let (t_enter, t_exit) = self.bounds.intersect(ray, t_min, t_max)?;
+        let speed = ray.direction.length();
+        if speed == 0.0 {
+            return None;
+        }
+
+        // Dentro del sólido (p. ej. rayos refractados) se busca la salida con la distancia invertida
+        let mut t = t_enter;
+        let sign = if self.distance(ray.at(t)) < 0.0 { -1.0 } else { 1.0 };
+
+        for _ in 0..self.max_steps {
+            let d = self.distance(ray.at(t)) * sign;
+            if d < self.precision {
+                let point = ray.at(t);
+                let normal = self.normal_at(point);
+                // Coordenadas UV esféricas según la normal
+                let u = 0.5 + normal.z.atan2(normal.x) / (2.0 * std::f32::consts::PI);
+                let v = 0.5 - normal.y.clamp(-1.0, 1.0).asin() / std::f32::consts::PI;
+                return Some(HitRecord::new(point, normal, t, ray, self.material.clone(), u, v));
+            }
+            t += d * self.step_scale / speed;
+            if t > t_exit {
+                return None;
+            }
+        }
+        None
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(self.bounds)
+    }
+
+    fn material(&self) -> Option<&Material> {
+        Some(&self.material)
+    }
+}
@@ -0,0 +1,155 @@
+// Toros (borde redondo de la piscina, flotadores)
+
+use crate::math::{Vec3, Ray, Mat3};
+use crate::math::polynomial::solve_quartic;
+use crate::material::Material;
+use super::{axis_frame, Aabb, HitRecord, Hittable};
+use std::f32::consts::PI;
+
+#[derive(Debug, Clone)]
+pub struct Torus {
+    pub center: Vec3,
+    // Eje de simetría (unitario); el anillo queda en el plano perpendicular
+    pub axis: Vec3,
+    // Radio del anillo (centro del tubo)
+    pub major_radius: f32,
+    // Radio del tubo
+    pub minor_radius: f32,
+    pub material: Material,
+    frame: Mat3,
+}
+
+impl Torus {
+    pub fn new(center: Vec3, axis: Vec3, major_radius: f32, minor_radius: f32, material: Material) -> Self {
+        let axis = axis.normalize();
+        Torus {
+            center,
+            axis,
+            major_radius: major_radius.abs(),
+            minor_radius: minor_radius.abs(),
+            material,
+            frame: axis_frame(axis),
+        }
+    }
+}
+
+impl Hittable for Torus {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
+        let bounds = self.bounding_box()?;
+        bounds.intersect(ray, t_min, t_max)?;
+
+        // Rayo local con dirección unitaria; el origen se acerca al toro para reducir el error
+        let to_local = self.frame.transpose();
+        let direction = to_local * ray.direction;
+        let speed = direction.length();
+        if speed == 0.0 {
+            return None;
+        }
+        let e = direction / speed;
+        let origin = to_local * (ray.origin - self.center);
+        let shift = -origin.dot(&e);
+        let o = origin + e * shift;
+
+        // (|p|² + R² - r²)² = 4R²·(px² + pz²) con p = o + e·s
+        let (o, e) = (
+            [o.x as f64, o.y as f64, o.z as f64],
+            [e.x as f64, e.y as f64, e.z as f64],
+        );
+        let (big_r2, small_r2) = ((self.major_radius as f64).powi(2), (self.minor_radius as f64).powi(2));
+        let f = o[0] * e[0] + o[1] * e[1] + o[2] * e[2];
+        let g = o[0] * o[0] + o[1] * o[1] + o[2] * o[2] + big_r2 - small_r2;
+        let a = e[0] * e[0] + e[2] * e[2];
+        let b = o[0] * e[0] + o[2] * e[2];
+        let c = o[0] * o[0] + o[2] * o[2];
+
+        let t = solve_quartic(
+            1.0,
+            4.0 * f,
+            4.0 * f * f + 2.0 * g - 4.0 * big_r2 * a,
+            4.0 * f * g - 8.0 * big_r2 * b,
+            g * g - 4.0 * big_r2 * c,
+        )
+        .into_iter()
+        .map(|s| (s as f32 + shift) / speed)
+        .filter(|&t| t >= t_min && t <= t_max)
+        .min_by(|a, b| a.total_cmp(b))?;
+
+        let point = ray.at(t);
+        let p = to_local * (point - self.center);
+        let ring = (p.x * p.x + p.z * p.z).sqrt().max(1e-8);
+        let tube_center = Vec3::new(p.x, 0.0, p.z) * (self.major_radius / ring);
+        let local_normal = (p - tube_center).normalize();
+
+        // u recorre el anillo y v rodea el tubo
+        let u = 0.5 + p.z.atan2(p.x) / (2.0 * PI);
+        let v = 0.5 + p.y.atan2(ring - self.major_radius) / (2.0 * PI);
+
+        Some(HitRecord::new(point, self.frame * local_normal, t, ray, self.material.clone(), u, v))
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        let a = self.axis;
+        let (big, small) = (self.major_radius, self.minor_radius);
+        let extent = Vec3::new(
+            big * (1.0 - a.x * a.x).max(0.0).sqrt() + small,
+            big * (1.0 - a.y * a.y).max(0.0).sqrt() + small,
+            big * (1.0 - a.z * a.z).max(0.0).sqrt() + small,
+        );
+        Some(Aabb::new(self.center - extent, self.center + extent))
+    }
+
+    fn material(&self) -> Option<&Material> {
+        Some(&self.material)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx_vec(a: Vec3, b: Vec3) -> bool {
+        (a - b).length() < 1e-3
+    }
+
+    fn ring() -> Torus {
+        Torus::new(Vec3::zero(), Vec3::up(), 1.0, 0.25, Material::new())
+    }
+
+    #[test]
+    fn quartic_finds_nearest_root() {
+        let ray = Ray::new(Vec3::new(5.0, 0.0, 0.0), Vec3::new(-1.0, 0.0, 0.0));
+        let hit = ring().hit(&ray, 0.001, f32::INFINITY).unwrap();
+        assert!((hit.t - 3.75).abs() < 1e-3);
+        assert!(approx_vec(hit.normal, Vec3::new(1.0, 0.0, 0.0)) && hit.front_face);
+
+        // Pasado el primer corte sale del tubo por la cara interior
+        let exit = ring().hit(&ray, 4.0, f32::INFINITY).unwrap();
+        assert!((exit.t - 4.25).abs() < 1e-3);
+        assert!(!exit.front_face);
+
+        // Lejos del origen el desplazamiento mantiene la precisión
+        let far = Ray::new(Vec3::new(1000.0, 0.0, 0.0), Vec3::new(-1.0, 0.0, 0.0));
+        assert!((ring().hit(&far, 0.001, f32::INFINITY).unwrap().t - 998.75).abs() < 1e-2);
+    }
+
+    #[test]
+    fn hole_and_top_of_tube() {
+        let down = |x: f32| Ray::new(Vec3::new(x, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0));
+        assert!(ring().hit(&down(0.0), 0.001, f32::INFINITY).is_none());
+        assert!(ring().hit(&down(1.3), 0.001, f32::INFINITY).is_none());
+        let top = ring().hit(&down(1.0), 0.001, f32::INFINITY).unwrap();
+        assert!((top.t - 4.75).abs() < 1e-3);
+        assert!(approx_vec(top.normal, Vec3::up()));
+        assert!((top.v - 0.75).abs() < 1e-3);
+    }
+
+    #[test]
+    fn follows_tilted_axis() {
+        let torus = Torus::new(Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, 0.0, 1.0), 1.0, 0.25, Material::new());
+        let forward = |x: f32| Ray::new(Vec3::new(x, 1.0, 5.0), Vec3::new(0.0, 0.0, -1.0));
+        assert!(torus.hit(&forward(0.0), 0.001, f32::INFINITY).is_none());
+        let hit = torus.hit(&forward(1.0), 0.001, f32::INFINITY).unwrap();
+        assert!((hit.t - 4.75).abs() < 1e-3);
+        assert!(approx_vec(hit.normal, Vec3::new(0.0, 0.0, 1.0)));
+    }
+}
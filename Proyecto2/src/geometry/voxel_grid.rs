@@ -0,0 +1,415 @@
+// Rejilla de vóxeles recorrida con DDA 3D (Amanatides & Woo)
+
+use crate::math::{Vec3, Ray};
+use crate::material::Material;
+use super::cube::face_uv;
+use super::{Aabb, HitRecord, Hittable};
+use std::collections::HashMap;
+
+// Lado (en vóxeles) de los bloques usados para saltar espacio vacío
+const BRICK_SIZE: usize = 8;
+
+// Almacenamiento de índices de material (0 = vacío)
+#[derive(Debug, Clone)]
+enum VoxelStorage {
+    // Un byte por vóxel, ideal para rejillas llenas
+    Dense(Vec<u8>),
+    // Solo los vóxeles ocupados, ideal para rejillas grandes y casi vacías
+    Sparse(HashMap<usize, u8>),
+}
+
+#[derive(Debug, Clone)]
+pub struct VoxelGrid {
+    // Esquina mínima de la rejilla en espacio mundo
+    origin: Vec3,
+    // Lado de cada vóxel
+    voxel_size: f32,
+    // Número de vóxeles en x, y, z
+    dims: [usize; 3],
+    storage: VoxelStorage,
+    // El índice k (1..=255) usa palette[k - 1]
+    palette: Vec<Material>,
+    // Vóxeles ocupados por bloque, para saltar bloques vacíos
+    brick_dims: [usize; 3],
+    brick_counts: Vec<u32>,
+    filled: usize,
+}
+
+impl VoxelGrid {
+    // Crea una rejilla densa vacía
+    pub fn new(origin: Vec3, dims: (usize, usize, usize), voxel_size: f32) -> Self {
+        let count = dims.0 * dims.1 * dims.2;
+        VoxelGrid::with_storage(origin, dims, voxel_size, VoxelStorage::Dense(vec![0; count]))
+    }
+
+    // Crea una rejilla dispersa vacía
+    pub fn sparse(origin: Vec3, dims: (usize, usize, usize), voxel_size: f32) -> Self {
+        VoxelGrid::with_storage(origin, dims, voxel_size, VoxelStorage::Sparse(HashMap::new()))
+    }
+
+    fn with_storage(origin: Vec3, dims: (usize, usize, usize), voxel_size: f32, storage: VoxelStorage) -> Self {
+        let dims = [dims.0, dims.1, dims.2];
+        let brick_dims = [
+            dims[0].div_ceil(BRICK_SIZE),
+            dims[1].div_ceil(BRICK_SIZE),
+            dims[2].div_ceil(BRICK_SIZE),
+        ];
+        VoxelGrid {
+            origin,
+            voxel_size: voxel_size.max(1e-6),
+            dims,
+            storage,
+            palette: Vec::new(),
+            brick_dims,
+            brick_counts: vec![0; brick_dims[0] * brick_dims[1] * brick_dims[2]],
+            filled: 0,
+        }
+    }
+
+    // Builder pattern para configurar la paleta completa
+    pub fn with_palette(mut self, palette: Vec<Material>) -> Self {
+        self.palette = palette;
+        self.palette.truncate(255);
+        self
+    }
+
+    // Añade un material a la paleta y devuelve su índice (None si la paleta está llena)
+    pub fn add_material(&mut self, material: Material) -> Option<u8> {
+        if self.palette.len() >= 255 {
+            return None;
+        }
+        self.palette.push(material);
+        Some(self.palette.len() as u8)
+    }
+
+    pub fn origin(&self) -> Vec3 {
+        self.origin
+    }
+
+    pub fn voxel_size(&self) -> f32 {
+        self.voxel_size
+    }
+
+    pub fn dims(&self) -> (usize, usize, usize) {
+        (self.dims[0], self.dims[1], self.dims[2])
+    }
+
+    // Número de vóxeles ocupados
+    pub fn filled_count(&self) -> usize {
+        self.filled
+    }
+
+    pub fn palette_material(&self, index: u8) -> Option<&Material> {
+        if index == 0 {
+            return None;
+        }
+        self.palette.get(index as usize - 1)
+    }
+
+    // Índice de material del vóxel (0 si está vacío o fuera de la rejilla)
+    pub fn get(&self, x: usize, y: usize, z: usize) -> u8 {
+        if x >= self.dims[0] || y >= self.dims[1] || z >= self.dims[2] {
+            return 0;
+        }
+        let index = self.linear_index(x, y, z);
+        match &self.storage {
+            VoxelStorage::Dense(data) => data[index],
+            VoxelStorage::Sparse(data) => data.get(&index).copied().unwrap_or(0),
+        }
+    }
+
+    // Asigna un índice de material (0 vacía el vóxel); ignora coordenadas fuera de rango
+    pub fn set(&mut self, x: usize, y: usize, z: usize, value: u8) {
+        if x >= self.dims[0] || y >= self.dims[1] || z >= self.dims[2] {
+            return;
+        }
+        let index = self.linear_index(x, y, z);
+        let previous = match &mut self.storage {
+            VoxelStorage::Dense(data) => std::mem::replace(&mut data[index], value),
+            VoxelStorage::Sparse(data) => {
+                if value == 0 {
+                    data.remove(&index).unwrap_or(0)
+                } else {
+                    data.insert(index, value).unwrap_or(0)
+                }
+            }
+        };
+
+        let brick = self.brick_index(x / BRICK_SIZE, y / BRICK_SIZE, z / BRICK_SIZE);
+        if previous == 0 && value != 0 {
+            self.brick_counts[brick] += 1;
+            self.filled += 1;
+        } else if previous != 0 && value == 0 {
+            self.brick_counts[brick] -= 1;
+            self.filled -= 1;
+        }
+    }
+
+    // Llena una caja de vóxeles (límites inclusivos)
+    pub fn fill(&mut self, min: (usize, usize, usize), max: (usize, usize, usize), value: u8) {
+        for z in min.2..=max.2.min(self.dims[2].saturating_sub(1)) {
+            for y in min.1..=max.1.min(self.dims[1].saturating_sub(1)) {
+                for x in min.0..=max.0.min(self.dims[0].saturating_sub(1)) {
+                    self.set(x, y, z, value);
+                }
+            }
+        }
+    }
+
+    // Caja envolvente de toda la rejilla
+    pub fn bounds(&self) -> Aabb {
+        let size = Vec3::new(self.dims[0] as f32, self.dims[1] as f32, self.dims[2] as f32) * self.voxel_size;
+        Aabb::new(self.origin, self.origin + size)
+    }
+
+    fn linear_index(&self, x: usize, y: usize, z: usize) -> usize {
+        (z * self.dims[1] + y) * self.dims[0] + x
+    }
+
+    fn brick_index(&self, bx: usize, by: usize, bz: usize) -> usize {
+        (bz * self.brick_dims[1] + by) * self.brick_dims[0] + bx
+    }
+
+    fn cell_value(&self, cell: [i64; 3]) -> u8 {
+        self.get(cell[0] as usize, cell[1] as usize, cell[2] as usize)
+    }
+
+    fn brick_is_empty(&self, cell: [i64; 3]) -> bool {
+        let b = cell.map(|c| c as usize / BRICK_SIZE);
+        self.brick_counts[self.brick_index(b[0], b[1], b[2])] == 0
+    }
+
+    fn in_bounds(&self, cell: [i64; 3]) -> bool {
+        (0..3).all(|i| cell[i] >= 0 && (cell[i] as usize) < self.dims[i])
+    }
+
+    // Esquinas mínima y máxima de un vóxel en espacio mundo
+    fn voxel_box(&self, cell: [i64; 3]) -> (Vec3, Vec3) {
+        let min = self.origin + Vec3::new(cell[0] as f32, cell[1] as f32, cell[2] as f32) * self.voxel_size;
+        (min, min + Vec3::new(self.voxel_size, self.voxel_size, self.voxel_size))
+    }
+
+    // Construye el registro de colisión con la cara `axis` del vóxel `cell`
+    fn make_hit(&self, ray: &Ray, t: f32, cell: [i64; 3], axis: usize, outward: f32, value: u8) -> Option<HitRecord> {
+        let material = self.palette_material(value)?.clone();
+        let mut normal = Vec3::zero();
+        match axis {
+            0 => normal.x = outward,
+            1 => normal.y = outward,
+            _ => normal.z = outward,
+        }
+        let point = ray.at(t);
+        let (min, max) = self.voxel_box(cell);
+        let (u, v) = face_uv(min, max, point, normal);
+        Some(HitRecord::new(point, normal, t, ray, material, u, v))
+    }
+}
+
+impl Hittable for VoxelGrid {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
+        if self.filled == 0 {
+            return None;
+        }
+        let (t_enter, t_exit) = self.bounds().intersect(ray, t_min, t_max)?;
+
+        // Todo el recorrido se hace en coordenadas de rejilla (1 unidad = 1 vóxel); t no cambia
+        let inv_size = 1.0 / self.voxel_size;
+        let origin = [
+            (ray.origin.x - self.origin.x) * inv_size,
+            (ray.origin.y - self.origin.y) * inv_size,
+            (ray.origin.z - self.origin.z) * inv_size,
+        ];
+        let direction = [ray.direction.x * inv_size, ray.direction.y * inv_size, ray.direction.z * inv_size];
+        let step = direction.map(|d| if d > 0.0 { 1i64 } else if d < 0.0 { -1 } else { 0 });
+        let t_delta = direction.map(|d| if d != 0.0 { (1.0 / d).abs() } else { f32::INFINITY });
+
+        // Parámetro del siguiente plano de vóxel en cada eje
+        let next_boundary = |cell: [i64; 3]| -> [f32; 3] {
+            let mut t_next = [f32::INFINITY; 3];
+            for i in 0..3 {
+                if step[i] != 0 {
+                    let boundary = (cell[i] + if step[i] > 0 { 1 } else { 0 }) as f32;
+                    t_next[i] = (boundary - origin[i]) / direction[i];
+                }
+            }
+            t_next
+        };
+
+        // Celda inicial y eje por el que entra el rayo (None si empieza dentro de la rejilla)
+        let mut t = t_enter;
+        let mut cell = [0i64; 3];
+        for i in 0..3 {
+            let p = origin[i] + direction[i] * t;
+            cell[i] = (p.floor() as i64).clamp(0, self.dims[i] as i64 - 1);
+        }
+        let mut axis = None;
+        if t_enter > t_min {
+            let bounds = self.bounds();
+            let (min, max) = ([bounds.min.x, bounds.min.y, bounds.min.z], [bounds.max.x, bounds.max.y, bounds.max.z]);
+            let ray_origin = [ray.origin.x, ray.origin.y, ray.origin.z];
+            let ray_direction = [ray.direction.x, ray.direction.y, ray.direction.z];
+            let mut best = f32::NEG_INFINITY;
+            for i in 0..3 {
+                if step[i] == 0 {
+                    continue;
+                }
+                let plane = if step[i] > 0 { min[i] } else { max[i] };
+                let t_plane = (plane - ray_origin[i]) / ray_direction[i];
+                if t_plane > best {
+                    best = t_plane;
+                    axis = Some(i);
+                }
+            }
+            if let Some(a) = axis {
+                cell[a] = if step[a] > 0 { 0 } else { self.dims[a] as i64 - 1 };
+            }
+        }
+
+        // Si el rayo nace dentro de un vóxel ocupado (refracción), busca la cara de salida
+        let inside = if axis.is_none() { self.cell_value(cell) } else { 0 };
+        let mut t_next = next_boundary(cell);
+
+        loop {
+            let value = self.cell_value(cell);
+            if inside != 0 {
+                if value != inside
+                    && let Some(a) = axis
+                {
+                    let mut previous = cell;
+                    previous[a] -= step[a];
+                    return self.make_hit(ray, t, previous, a, step[a] as f32, inside);
+                }
+            } else if value != 0 {
+                if let Some(a) = axis {
+                    return self.make_hit(ray, t, cell, a, -step[a] as f32, value);
+                }
+            } else if self.brick_is_empty(cell) {
+                // Salta el bloque completo hasta su cara de salida
+                let brick_min = cell.map(|c| (c as usize / BRICK_SIZE * BRICK_SIZE) as i64);
+                let mut brick_exit = [f32::INFINITY; 3];
+                for i in 0..3 {
+                    if step[i] != 0 {
+                        let boundary = if step[i] > 0 {
+                            (brick_min[i] + BRICK_SIZE as i64).min(self.dims[i] as i64)
+                        } else {
+                            brick_min[i]
+                        };
+                        brick_exit[i] = (boundary as f32 - origin[i]) / direction[i];
+                    }
+                }
+                let a = (0..3).min_by(|&i, &j| brick_exit[i].total_cmp(&brick_exit[j]))?;
+                t = brick_exit[a].max(t);
+                if t > t_exit {
+                    return None;
+                }
+
+                let brick_max = brick_min.map(|c| c + BRICK_SIZE as i64 - 1);
+                for i in 0..3 {
+                    cell[i] = if i == a {
+                        if step[a] > 0 { brick_max[a] + 1 } else { brick_min[a] - 1 }
+                    } else {
+                        ((origin[i] + direction[i] * t).floor() as i64).clamp(brick_min[i], brick_max[i])
+                    };
+                }
+                if !self.in_bounds(cell) {
+                    return None;
+                }
+                axis = Some(a);
+                t_next = next_boundary(cell);
+                continue;
+            }
+
+            // Avanza al vóxel vecino a través del plano más cercano
+            let a = (0..3).min_by(|&i, &j| t_next[i].total_cmp(&t_next[j]))?;
+            t = t_next[a].max(t);
+            cell[a] += step[a];
+            if !self.in_bounds(cell) {
+                // Al salir de la rejilla desde dentro de un sólido, la cara de salida es el borde
+                if inside != 0 && t <= t_max {
+                    let mut previous = cell;
+                    previous[a] -= step[a];
+                    return self.make_hit(ray, t, previous, a, step[a] as f32, inside);
+                }
+                return None;
+            }
+            if t > t_exit {
+                return None;
+            }
+            t_next[a] += t_delta[a];
+            axis = Some(a);
+        }
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(self.bounds())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Cube;
+
+    fn approx(a: f32, b: f32) -> bool {
+        (a - b).abs() < 1e-4
+    }
+
+    #[test]
+    fn single_voxel_matches_cube() {
+        let origin = Vec3::new(-0.5, 1.0, 2.0);
+        let size = 0.5;
+        let mut grid = VoxelGrid::new(origin, (1, 1, 1), size).with_palette(vec![Material::new()]);
+        grid.set(0, 0, 0, 1);
+        let center = origin + Vec3::new(size, size, size) * 0.5;
+        let cube = Cube::new(center, Vec3::new(size, size, size), Material::new());
+
+        let directions = [
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(-1.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            Vec3::new(0.0, -1.0, 0.0),
+            Vec3::new(0.0, 0.0, 1.0),
+            Vec3::new(0.0, 0.0, -1.0),
+            Vec3::new(0.4, -0.7, 0.3),
+            Vec3::new(-0.2, 0.5, -0.9),
+        ];
+        for direction in directions {
+            let direction = direction.normalize();
+            // Rayos desde fuera hacia puntos desplazados del centro para cubrir toda la cara
+            for offset in [Vec3::zero(), Vec3::new(0.1, -0.05, 0.08), Vec3::new(-0.12, 0.1, -0.1)] {
+                let ray = Ray::new(center + offset - direction * 3.0, direction);
+                let expected = cube.hit(&ray, 1e-3, f32::INFINITY).unwrap();
+                let actual = grid.hit(&ray, 1e-3, f32::INFINITY).unwrap();
+                assert!(approx(actual.t, expected.t), "{:?}: t {} vs {}", direction, actual.t, expected.t);
+                assert!((actual.normal - expected.normal).length() < 1e-4, "{:?}: normal", direction);
+                assert!(approx(actual.u, expected.u) && approx(actual.v, expected.v), "{:?}: uv", direction);
+                assert_eq!(actual.front_face, expected.front_face);
+            }
+
+            // Desde dentro (rayos refractados) ambos devuelven la cara de salida
+            let ray = Ray::new(center, direction);
+            let expected = cube.hit(&ray, 1e-3, f32::INFINITY).unwrap();
+            let actual = grid.hit(&ray, 1e-3, f32::INFINITY).unwrap();
+            assert!(approx(actual.t, expected.t), "{:?}: t {} vs {}", direction, actual.t, expected.t);
+            assert!((actual.normal - expected.normal).length() < 1e-4, "{:?}: normal", direction);
+            assert!(approx(actual.u, expected.u) && approx(actual.v, expected.v), "{:?}: uv", direction);
+        }
+    }
+
+    #[test]
+    fn dense_and_sparse_storage_agree() {
+        let mut dense = VoxelGrid::new(Vec3::zero(), (4, 4, 4), 1.0).with_palette(vec![Material::new()]);
+        let mut sparse = VoxelGrid::sparse(Vec3::zero(), (4, 4, 4), 1.0).with_palette(vec![Material::new()]);
+        for grid in [&mut dense, &mut sparse] {
+            grid.set(2, 1, 3, 1);
+            grid.set(0, 0, 0, 1);
+            grid.set(0, 0, 0, 0);
+        }
+        assert_eq!(dense.filled_count(), 1);
+        assert_eq!(sparse.filled_count(), 1);
+        let ray = Ray::new(Vec3::new(2.5, 1.5, -5.0), Vec3::new(0.0, 0.0, 1.0));
+        let a = dense.hit(&ray, 1e-3, f32::INFINITY).unwrap();
+        let b = sparse.hit(&ray, 1e-3, f32::INFINITY).unwrap();
+        assert!(approx(a.t, 8.0) && approx(b.t, 8.0));
+    }
+}
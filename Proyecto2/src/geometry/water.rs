@@ -0,0 +1,151 @@
+// Superficie de agua: volumen en caja cuya cara superior ondula con olas de Gerstner
+
+use crate::math::{Vec3, Ray, Rng};
+use crate::material::Material;
+use super::{Aabb, Cube, HitInterval, HitRecord, Hittable};
+use std::f32::consts::PI;
+
+// Una ola de Gerstner que viaja sobre el plano XZ
+#[derive(Debug, Clone, Copy)]
+pub struct Wave {
+    // Dirección de avance en XZ (unitaria)
+    pub direction: (f32, f32),
+    // Altura de la cresta
+    pub amplitude: f32,
+    // Ciclos por unidad de distancia
+    pub frequency: f32,
+    // Velocidad de fase en unidades por segundo
+    pub speed: f32,
+    // Agudeza de la cresta (0 = sinusoidal, 1 = cresta en punta)
+    pub steepness: f32,
+}
+
+impl Wave {
+    pub fn new(direction: Vec3, amplitude: f32, frequency: f32, speed: f32) -> Self {
+        let length = (direction.x * direction.x + direction.z * direction.z).sqrt().max(1e-8);
+        Wave {
+            direction: (direction.x / length, direction.z / length),
+            amplitude,
+            frequency,
+            speed,
+            steepness: 0.5,
+        }
+    }
+
+    // Builder pattern para la agudeza de la cresta
+    pub fn with_steepness(mut self, steepness: f32) -> Self {
+        self.steepness = steepness.clamp(0.0, 1.0);
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WaterSurface {
+    // Volumen de agua (la geometría es plana; las olas solo desvían la normal)
+    body: Cube,
+    pub waves: Vec<Wave>,
+}
+
+impl WaterSurface {
+    // Crea un volumen de agua en calma desde el centro y el tamaño
+    pub fn new(center: Vec3, size: Vec3, material: Material) -> Self {
+        WaterSurface {
+            body: Cube::new(center, size, material),
+            waves: Vec::new(),
+        }
+    }
+
+    // Builder pattern para añadir una ola
+    pub fn with_wave(mut self, wave: Wave) -> Self {
+        self.waves.push(wave);
+        self
+    }
+
+    // Builder pattern: `count` olas en direcciones repartidas (ángulo áureo) con
+    // frecuencias crecientes y amplitudes decrecientes alrededor de los valores dados
+    pub fn with_waves(mut self, count: u32, amplitude: f32, frequency: f32, speed: f32) -> Self {
+        let golden_angle = PI * (3.0 - 5.0f32.sqrt());
+        for i in 0..count {
+            let angle = i as f32 * golden_angle;
+            let scale = 1.0 + 0.6 * i as f32;
+            self.waves.push(Wave::new(
+                Vec3::new(angle.cos(), 0.0, angle.sin()),
+                amplitude / scale,
+                frequency * scale,
+                speed / scale.sqrt(),
+            ));
+        }
+        self
+    }
+
+    // Altura de la superficie en calma
+    pub fn level(&self) -> f32 {
+        self.body.max.y
+    }
+
+    // Normal de la superficie ondulada en (x, z) en el instante `time`
+    pub fn normal_at(&self, x: f32, z: f32, time: f32) -> Vec3 {
+        let mut normal = Vec3::new(0.0, 1.0, 0.0);
+        let count = self.waves.len().max(1) as f32;
+        for wave in &self.waves {
+            let k = 2.0 * PI * wave.frequency;
+            let (dx, dz) = wave.direction;
+            let phase = k * (dx * x + dz * z - wave.speed * time);
+            let (sin, cos) = phase.sin_cos();
+            let slope = k * wave.amplitude;
+            normal.x -= dx * slope * cos;
+            normal.z -= dz * slope * cos;
+            // La agudeza se reparte entre las olas para que las crestas no se crucen
+            normal.y -= wave.steepness / count * sin;
+        }
+        normal.normalize()
+    }
+
+    // Sustituye la normal de la cara superior por la de las olas
+    fn ripple(&self, hit: &mut HitRecord, time: f32) {
+        let on_top = hit.normal.y.abs() > 0.5 && (hit.point.y - self.level()).abs() < 1e-3;
+        if !on_top || self.waves.is_empty() {
+            return;
+        }
+        let normal = self.normal_at(hit.point.x, hit.point.z, time);
+        // Se conserva el lado del que llegó el rayo (front_face no cambia)
+        hit.normal = if hit.normal.y > 0.0 { normal } else { -normal };
+    }
+}
+
+impl Hittable for WaterSurface {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
+        let mut hit = self.body.hit(ray, t_min, t_max)?;
+        self.ripple(&mut hit, ray.time);
+        Some(hit)
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        self.body.bounding_box()
+    }
+
+    fn intervals(&self, ray: &Ray) -> Vec<HitInterval> {
+        let mut intervals = self.body.intervals(ray);
+        for interval in &mut intervals {
+            self.ripple(&mut interval.enter, ray.time);
+            self.ripple(&mut interval.exit, ray.time);
+        }
+        intervals
+    }
+
+    fn material(&self) -> Option<&Material> {
+        self.body.material()
+    }
+
+    fn surface_area(&self) -> f32 {
+        self.body.surface_area()
+    }
+
+    fn sample_surface(&self, rng: &mut Rng) -> Option<(Vec3, Vec3)> {
+        self.body.sample_surface(rng)
+    }
+
+    fn is_time_dependent(&self) -> bool {
+        !self.waves.is_empty()
+    }
+}
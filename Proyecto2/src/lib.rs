@@ -10,13 +10,18 @@ pub mod raytracer;
 pub mod texture;
 pub mod framebuffer;
 pub mod adaptive_config;
+pub mod spectral;
+pub mod photon;
+pub mod volume;
+pub mod loaders;
+pub mod animation;
 
 // Re-exportar las estructuras principales 
-pub use math::{Vec3, Ray};
-pub use material::Material;
-pub use geometry::{Cube, HitRecord};
+pub use math::{Vec3, Ray, Mat4};
+pub use material::{Material, Dispersion};
+pub use geometry::{Cube, HitRecord, Instance};
 pub use lighting::Light;
 pub use camera::Camera;
 pub use scene::Scene;
-pub use raytracer::Raytracer;
+pub use raytracer::{Raytracer, RenderMode};
 pub use texture::Texture;
\ No newline at end of file
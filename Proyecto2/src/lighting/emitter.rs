@@ -0,0 +1,139 @@
+// Objetos emisivos muestreados como luces de área
+
+use crate::math::{Vec3, Rng};
+use crate::geometry::Hittable;
+use super::light_sampler::{luminance, LightDistribution};
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub struct Emitter {
+    // Geometría emisiva (compartida con la lista de objetos de la escena)
+    pub object: Arc<dyn Hittable + Send + Sync>,
+    // Radiancia emitida por la superficie
+    pub emission: Vec3,
+    // Área total de la superficie
+    pub area: f32,
+    // Potencia aproximada (luminancia × área), usada para elegir emisores
+    pub power: f32,
+}
+
+// Muestra de un punto de luz sobre un emisor
+#[derive(Debug, Clone, Copy)]
+pub struct EmitterSample {
+    pub point: Vec3,
+    pub normal: Vec3,
+    pub emission: Vec3,
+    // Densidad de probabilidad por unidad de área (incluye la selección del emisor)
+    pub pdf: f32,
+}
+
+impl Emitter {
+    // Crea un emisor si el objeto tiene material emisivo y superficie muestreable
+    pub fn from_object(object: Arc<dyn Hittable + Send + Sync>) -> Option<Self> {
+        let emission = object.material()?.emitted();
+        let area = object.surface_area();
+        let luminance = luminance(emission);
+        if luminance <= 0.0 || area <= 0.0 {
+            return None;
+        }
+
+        Some(Emitter {
+            object,
+            emission,
+            area,
+            power: luminance * area,
+        })
+    }
+}
+
+// Conjunto de emisores con selección proporcional a la potencia
+#[derive(Debug, Clone, Default)]
+pub struct EmitterList {
+    emitters: Vec<Emitter>,
+    // Selección por potencia (misma CDF que las luces puntuales)
+    distribution: LightDistribution,
+}
+
+impl EmitterList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, emitter: Emitter) {
+        self.distribution.push(emitter.power);
+        self.emitters.push(emitter);
+    }
+
+    pub fn len(&self) -> usize {
+        self.emitters.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.emitters.is_empty()
+    }
+
+    // Elige un emisor según su potencia y muestrea un punto sobre él
+    pub fn sample(&self, rng: &mut Rng) -> Option<EmitterSample> {
+        let (index, selection_pdf) = self.distribution.sample(rng)?;
+        let emitter = &self.emitters[index];
+        let (point, normal) = emitter.object.sample_surface(rng)?;
+
+        Some(EmitterSample {
+            point,
+            normal,
+            emission: emitter.emission,
+            pdf: selection_pdf / emitter.area,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Cube;
+    use crate::material::Material;
+
+    fn emissive_cube(x: f32, size: f32, emission: f32) -> Arc<dyn Hittable + Send + Sync> {
+        let material = Material::new().with_emission(Vec3::one() * emission);
+        Arc::new(Cube::new(Vec3::new(x, 0.0, 0.0), Vec3::one() * size, material))
+    }
+
+    #[test]
+    fn skips_objects_without_emission() {
+        let plain: Arc<dyn Hittable + Send + Sync> = Arc::new(Cube::new(Vec3::zero(), Vec3::one(), Material::new()));
+        assert!(Emitter::from_object(plain).is_none());
+
+        let emitter = Emitter::from_object(emissive_cube(0.0, 2.0, 0.5)).unwrap();
+        assert!((emitter.area - 24.0).abs() < 1e-5);
+        assert!((emitter.power - 12.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn sample_pdf_is_power_share_over_area() {
+        let mut list = EmitterList::new();
+        // Potencias: 1 × 6 y 2 × 24
+        for object in [emissive_cube(-5.0, 1.0, 1.0), emissive_cube(5.0, 2.0, 2.0)] {
+            list.add(Emitter::from_object(object).unwrap());
+        }
+        assert_eq!(list.len(), 2);
+
+        let mut rng = Rng::new(11);
+        let mut small_count = 0;
+        let samples = 4000;
+        for _ in 0..samples {
+            let sample = list.sample(&mut rng).unwrap();
+            let expected = if sample.point.x < 0.0 {
+                small_count += 1;
+                (6.0 / 54.0) / 6.0
+            } else {
+                (48.0 / 54.0) / 24.0
+            };
+            assert!((sample.pdf - expected).abs() < 1e-5);
+        }
+        // La frecuencia de selección sigue la proporción de potencia
+        let share = small_count as f32 / samples as f32;
+        assert!((share - 6.0 / 54.0).abs() < 0.03);
+
+        assert!(EmitterList::new().sample(&mut rng).is_none());
+    }
+}
@@ -2,14 +2,27 @@
 
 use crate::math::Vec3;
 
+// Cono de una luz focal (spot)
+#[derive(Debug, Clone, Copy)]
+pub struct SpotCone {
+    // Dirección hacia donde apunta el foco
+    pub direction: Vec3,
+    // Coseno del ángulo donde empieza la caída
+    pub inner_cos: f32,
+    // Coseno del ángulo donde la luz llega a cero
+    pub outer_cos: f32,
+}
+
 #[derive(Debug, Clone)]
 pub struct Light {
     // Posición de la luz en el espacio
     pub position: Vec3,
     // Color de la luz
     pub color: Vec3,
-    // Intensidad de la luz 
+    // Intensidad de la luz
     pub intensity: f32,
+    // Cono de la luz focal (None = luz puntual omnidireccional)
+    pub spot: Option<SpotCone>,
 }
 
 impl Light {
@@ -19,19 +32,35 @@ impl Light {
             position,
             color,
             intensity,
+            spot: None,
+        }
+    }
+
+    // Crea una luz focal con ángulos interior y exterior en grados
+    pub fn spot(position: Vec3, direction: Vec3, color: Vec3, intensity: f32, inner_angle: f32, outer_angle: f32) -> Self {
+        let outer_angle = outer_angle.max(inner_angle);
+        Light {
+            position,
+            color,
+            intensity,
+            spot: Some(SpotCone {
+                direction: direction.normalize(),
+                inner_cos: crate::math::degrees_to_radians(inner_angle).cos(),
+                outer_cos: crate::math::degrees_to_radians(outer_angle).cos(),
+            }),
         }
     }
-    
+
     // Obtiene la dirección de la luz desde un punto dado
     pub fn get_direction_from(&self, point: Vec3) -> Vec3 {
         (self.position - point).normalize()
     }
-    
+
     // Obtiene la distancia a la luz desde un punto (para atenuación)
     pub fn get_distance_from(&self, point: Vec3) -> f32 {
         (self.position - point).length()
     }
-    
+
     // Calcula la atenuación de la luz basada en la distancia
     pub fn get_attenuation(&self, distance: f32) -> f32 {
         // Atenuación cuadrática para luces puntuales
@@ -40,11 +69,30 @@ impl Light {
         let quadratic = 0.032;
         1.0 / (constant + linear * distance + quadratic * (distance * distance))
     }
-    
+
+    // Factor del cono de la luz focal (1.0 para luces puntuales)
+    pub fn get_spot_factor(&self, point: Vec3) -> f32 {
+        match &self.spot {
+            Some(cone) => {
+                let cos_angle = (point - self.position).normalize().dot(&cone.direction);
+                if cos_angle >= cone.inner_cos {
+                    1.0
+                } else if cos_angle <= cone.outer_cos {
+                    0.0
+                } else {
+                    // Transición suave entre el cono interior y el exterior
+                    let t = (cos_angle - cone.outer_cos) / (cone.inner_cos - cone.outer_cos);
+                    t * t * (3.0 - 2.0 * t)
+                }
+            }
+            None => 1.0,
+        }
+    }
+
     // Obtiene el color efectivo de la luz considerando intensidad y atenuación
     pub fn get_effective_color(&self, point: Vec3) -> Vec3 {
         let distance = self.get_distance_from(point);
         let attenuation = self.get_attenuation(distance);
-        self.color * self.intensity * attenuation
+        self.color * self.intensity * attenuation * self.get_spot_factor(point)
     }
-}
+}
\ No newline at end of file
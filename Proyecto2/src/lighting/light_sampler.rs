@@ -0,0 +1,157 @@
+// Selección estocástica de luces para escenas con muchas luces
+
+use crate::math::{Vec3, Rng};
+use super::Light;
+
+// Estrategia para elegir qué luces se evalúan en cada punto sombreado
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LightSampling {
+    // Todas las luces con un rayo de sombra cada una (costo lineal)
+    All,
+    // `samples` luces elegidas proporcionalmente a su potencia
+    PowerProportional { samples: u32 },
+    // Muestreo por reservorio (RIS): `candidates` candidatas, un solo rayo de sombra
+    Reservoir { candidates: u32 },
+}
+
+// Luminancia de un color lineal
+pub fn luminance(color: Vec3) -> f32 {
+    0.2126 * color.x + 0.7152 * color.y + 0.0722 * color.z
+}
+
+// Distribución de potencia de las luces (CDF para selección por búsqueda binaria)
+#[derive(Debug, Clone, Default)]
+pub struct LightDistribution {
+    cdf: Vec<f32>,
+    total: f32,
+}
+
+impl LightDistribution {
+    pub fn build(lights: &[Light]) -> Self {
+        let mut distribution = LightDistribution { cdf: Vec::with_capacity(lights.len()), total: 0.0 };
+        for light in lights {
+            distribution.push(luminance(light.color) * light.intensity);
+        }
+        distribution
+    }
+
+    // Añade una entrada con la potencia dada al final de la distribución
+    pub fn push(&mut self, power: f32) {
+        self.total += power.max(0.0);
+        self.cdf.push(self.total);
+    }
+
+    // Índice de luz elegido según su potencia y su probabilidad
+    pub fn sample(&self, rng: &mut Rng) -> Option<(usize, f32)> {
+        if self.cdf.is_empty() || self.total <= 0.0 {
+            return None;
+        }
+        let target = rng.next_f32() * self.total;
+        let index = self.cdf.partition_point(|&c| c <= target).min(self.cdf.len() - 1);
+        Some((index, self.pdf(index)))
+    }
+
+    pub fn pdf(&self, index: usize) -> f32 {
+        let previous = if index == 0 { 0.0 } else { self.cdf[index - 1] };
+        (self.cdf[index] - previous) / self.total
+    }
+}
+
+// Reservorio de un solo elemento para muestreo por importancia remuestreado
+#[derive(Debug, Clone, Copy)]
+pub struct Reservoir {
+    pub selected: Option<usize>,
+    // Suma de pesos de todas las candidatas vistas
+    pub weight_sum: f32,
+    // Número de candidatas vistas
+    pub count: u32,
+    // Función objetivo de la candidata elegida
+    pub target: f32,
+}
+
+impl Reservoir {
+    pub fn new() -> Self {
+        Reservoir {
+            selected: None,
+            weight_sum: 0.0,
+            count: 0,
+            target: 0.0,
+        }
+    }
+
+    // Procesa una candidata con peso `weight`; la conserva con probabilidad weight / weight_sum
+    pub fn update(&mut self, index: usize, weight: f32, target: f32, rng: &mut Rng) {
+        self.count += 1;
+        if weight <= 0.0 {
+            return;
+        }
+        self.weight_sum += weight;
+        if rng.next_f32() * self.weight_sum < weight {
+            self.selected = Some(index);
+            self.target = target;
+        }
+    }
+
+    // Peso de contribución W = weight_sum / (count · p̂(y))
+    pub fn contribution_weight(&self) -> f32 {
+        if self.target <= 0.0 || self.count == 0 {
+            0.0
+        } else {
+            self.weight_sum / (self.count as f32 * self.target)
+        }
+    }
+}
+
+impl Default for Reservoir {
+    fn default() -> Self {
+        Reservoir::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lights() -> Vec<Light> {
+        vec![
+            Light::new(Vec3::zero(), Vec3::one(), 1.0),
+            Light::new(Vec3::zero(), Vec3::new(1.0, 0.0, 0.0), 4.0),
+            Light::new(Vec3::zero(), Vec3::one(), 0.0),
+            Light::new(Vec3::zero(), Vec3::new(0.2, 0.4, 1.0), 2.0),
+        ]
+    }
+
+    #[test]
+    fn distribution_pdf_is_proportional_to_power() {
+        let lights = lights();
+        let distribution = LightDistribution::build(&lights);
+        let total: f32 = (0..lights.len()).map(|i| distribution.pdf(i)).sum();
+        assert!((total - 1.0).abs() < 1e-5);
+        assert_eq!(distribution.pdf(2), 0.0);
+        let power = |l: &Light| luminance(l.color) * l.intensity;
+        let ratio = distribution.pdf(1) / distribution.pdf(0);
+        assert!((ratio - power(&lights[1]) / power(&lights[0])).abs() < 1e-4);
+    }
+
+    #[test]
+    fn distribution_never_samples_dark_lights() {
+        let distribution = LightDistribution::build(&lights());
+        let mut rng = Rng::new(7);
+        for _ in 0..1000 {
+            assert_ne!(distribution.sample(&mut rng).unwrap().0, 2);
+        }
+        assert!(LightDistribution::build(&[]).sample(&mut rng).is_none());
+    }
+
+    #[test]
+    fn reservoir_keeps_only_weighted_candidates() {
+        let mut rng = Rng::new(3);
+        let mut reservoir = Reservoir::default();
+        reservoir.update(0, 0.0, 0.0, &mut rng);
+        assert_eq!(reservoir.selected, None);
+        assert_eq!(reservoir.contribution_weight(), 0.0);
+        reservoir.update(5, 2.0, 4.0, &mut rng);
+        assert_eq!(reservoir.selected, Some(5));
+        assert!((reservoir.contribution_weight() - 2.0 / (2.0 * 4.0)).abs() < 1e-6);
+    }
+}
@@ -1,5 +1,9 @@
 // Módulo de iluminación
 
 pub mod light;
+pub mod emitter;
+pub mod light_sampler;
 
 pub use light::Light;
+pub use emitter::{Emitter, EmitterList};
+pub use light_sampler::{LightSampling, LightDistribution};
@@ -0,0 +1,6 @@
+// Módulo de carga de recursos externos
+pub mod vox;
+pub mod scene_file;
+
+pub use vox::{VoxFile, VoxOptions};
+pub use scene_file::{load_scene, load_camera_path, save_camera_path};
@@ -0,0 +1,677 @@
+// Archivos de escena en texto: una directiva por línea seguida de pares clave-valor.
+//
+//   background 0.8 0.9 0.95
+//   ambient 0.1 0.1 0.1
+//   material agua color 0.1 0.4 0.8 specular 0.8 roughness 0.1 transparency 0.6 ior 1.33
+//   cube center 0 0.1 0 size 0.3 0.3 0.3 material agua
+//   water center 0 0.1 0 size 0.6 0.3 0.6 waves 3 amplitude 0.004 frequency 5 speed 0.12 material agua
+//   light position -4 6 -2 color 1 0.9 0.8 intensity 2.2
+//   spot position 0 3 0 direction 0 -1 0 color 1 1 1 intensity 3 inner 15 outer 25
+//   fog density 1.5 albedo 0.95 0.95 0.97 anisotropy 0.3 min -1 0 -1 max 1 1 1
+//   plane point 0 -0.2 0 normal 0 1 0 size 4 0 4 material marmol   (sin size: plano infinito; uv_scale s)
+//   disk center 0 0 0 normal 0 1 0 radius 0.5 material metal
+//   cylinder base 0 0 0 axis 0 1 0 radius 0.05 height 1 caps 1 material metal
+//   cone base 0 0 0 axis 0 1 0 radius 0.2 height 0.4 caps 1 material metal
+//   torus center 0 0 0 axis 0 1 0 major 0.5 minor 0.05 material metal
+//   heightfield path assets/img/colinas.png center 0 -0.4 0 size 8 1 8 resolution 256 material pasto
+//   sdf material piedra position 1 0 0 (smooth_union 0.1 (sphere 0.2) (box 0.1 0.3 0.1))
+//   vox path assets/models/decoracion.vox voxel_size 0.02 position -0.8 -0.1 -0.8
+//   camera position -1 1.5 1.5 target 0 0 0 fov 60 projection perspective
+//
+// Las proyecciones de `camera` son perspective, orthographic, fisheye (equidistante),
+// fisheye_equisolid y equirectangular (panorama de 360°).
+//
+// Los objetos aceptan `velocity x y z` (unidades por segundo, para motion blur) y `name`
+// para animarlos con fotogramas clave:
+//
+//   key camera 0 position -1 1.5 1.5 target 0 0 0 fov 60 easing smooth
+//   key light 0 2.5 intensity 3 color 1 0.8 0.6 easing ease_in_out
+//   key object pato 1 position 0 0.1 0 rotation 0 90 0 scale 1 1 1 color 1 0.8 0
+//
+// `key <objetivo> [índice|nombre] <segundos>`; las curvas son step, linear, ease_in,
+// ease_out, ease_in_out y smooth (Catmull-Rom).
+//
+// Las líneas vacías y las que empiezan con '#' se ignoran.
+
+use crate::math::{Vec3, Mat4, Quat};
+use crate::animation::{CameraTracks, Easing, Timeline, Transform};
+use crate::camera::{Camera, Projection};
+use crate::material::{Material, Dispersion};
+use crate::geometry::{Cube, Plane, Disk, Cylinder, Cone, Torus, Heightfield, Sdf, SdfObject, Hittable, Moving, Motion, WaterSurface};
+use crate::lighting::Light;
+use crate::scene::Scene;
+use crate::texture::Texture;
+use crate::volume::Medium;
+use super::{VoxFile, VoxOptions};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+// Carga una escena desde un archivo de texto
+pub fn load_scene(file_path: &str) -> Result<Scene> {
+    let text = std::fs::read_to_string(file_path)?;
+    parse_scene(&text)
+}
+
+// Carga un recorrido de cámara: las directivas `key camera` de un archivo de escena
+pub fn load_camera_path(file_path: &str) -> Result<CameraTracks> {
+    Ok(load_scene(file_path)?.timeline.camera)
+}
+
+// Guarda las pistas de cámara como directivas `key camera` (legibles por `load_camera_path`
+// y por `--scene` para renderizar el recorrido sin ventana)
+pub fn save_camera_path(tracks: &CameraTracks, file_path: &str) -> Result<()> {
+    use std::fmt::Write;
+
+    let mut text = String::from("# Recorrido de cámara grabado en el visor\n");
+    for key in tracks.position.keys() {
+        let p = key.value;
+        writeln!(text, "key camera {:.3} position {} {} {} easing {}", key.time, p.x, p.y, p.z, key.easing)?;
+    }
+    for key in tracks.target.keys() {
+        let t = key.value;
+        writeln!(text, "key camera {:.3} target {} {} {} easing {}", key.time, t.x, t.y, t.z, key.easing)?;
+    }
+    for key in tracks.fov.keys() {
+        writeln!(text, "key camera {:.3} fov {} easing {}", key.time, key.value, key.easing)?;
+    }
+    std::fs::write(file_path, text)?;
+    Ok(())
+}
+
+// Construye una escena a partir del texto de un archivo de escena
+pub fn parse_scene(text: &str) -> Result<Scene> {
+    let mut scene = Scene::new();
+    let mut materials: HashMap<String, Material> = HashMap::new();
+    materials.insert("default".to_string(), Material::new());
+
+    for (index, raw_line) in text.lines().enumerate() {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        parse_line(line, &mut scene, &mut materials)
+            .map_err(|e| format!("línea {}: {}", index + 1, e))?;
+    }
+
+    Ok(scene)
+}
+
+fn parse_line(line: &str, scene: &mut Scene, materials: &mut HashMap<String, Material>) -> Result<()> {
+    // La expresión SDF (si la hay) empieza en el primer paréntesis
+    let (head, expression) = match line.find('(') {
+        Some(i) => (&line[..i], Some(&line[i..])),
+        None => (line, None),
+    };
+    let mut tokens = head.split_whitespace();
+    let name = tokens.next().unwrap_or_default();
+    let rest: Vec<&str> = tokens.collect();
+
+    match name {
+        "background" => scene.set_background_color(positional_vec3(&rest)?),
+        "ambient" => scene.ambient_light = positional_vec3(&rest)?,
+        "material" => {
+            let (material_name, pairs) = rest.split_first().ok_or("falta el nombre del material")?;
+            let args = Args::parse(pairs)?;
+            materials.insert(material_name.to_string(), args.material()?);
+        }
+        "cube" => {
+            let args = Args::parse(&rest)?;
+            let cube = Cube::new(
+                args.vec3("center")?.unwrap_or(Vec3::zero()),
+                args.vec3("size")?.unwrap_or(Vec3::one()),
+                args.material_ref(materials)?,
+            );
+            add_object(scene, cube, &args)?;
+        }
+        "water" => {
+            let args = Args::parse(&rest)?;
+            let water = WaterSurface::new(
+                args.vec3("center")?.unwrap_or(Vec3::zero()),
+                args.vec3("size")?.unwrap_or(Vec3::one()),
+                args.material_ref(materials)?,
+            )
+            .with_waves(
+                args.number("waves")?.unwrap_or(3.0) as u32,
+                args.number("amplitude")?.unwrap_or(0.01),
+                args.number("frequency")?.unwrap_or(2.0),
+                args.number("speed")?.unwrap_or(0.2),
+            );
+            add_object(scene, water, &args)?;
+        }
+        "plane" => {
+            let args = Args::parse(&rest)?;
+            let mut plane = Plane::new(
+                args.vec3("point")?.unwrap_or(Vec3::zero()),
+                args.vec3("normal")?.unwrap_or(Vec3::up()),
+                args.material_ref(materials)?,
+            );
+            if let Some(size) = args.vec3("size")? {
+                plane = plane.with_size(size.x, size.z);
+            }
+            if let Some(uv_scale) = args.number("uv_scale")? {
+                plane = plane.with_uv_scale(uv_scale);
+            }
+            add_object(scene, plane, &args)?;
+        }
+        "disk" => {
+            let args = Args::parse(&rest)?;
+            let disk = Disk::new(
+                args.vec3("center")?.unwrap_or(Vec3::zero()),
+                args.vec3("normal")?.unwrap_or(Vec3::up()),
+                args.number("radius")?.unwrap_or(0.5),
+                args.material_ref(materials)?,
+            );
+            add_object(scene, disk, &args)?;
+        }
+        "cylinder" | "cone" => {
+            let args = Args::parse(&rest)?;
+            let base = args.vec3("base")?.unwrap_or(Vec3::zero());
+            let axis = args.vec3("axis")?.unwrap_or(Vec3::up());
+            let radius = args.number("radius")?.unwrap_or(0.1);
+            let height = args.number("height")?.unwrap_or(1.0);
+            let capped = args.number("caps")?.is_none_or(|caps| caps != 0.0);
+            let material = args.material_ref(materials)?;
+            if name == "cylinder" {
+                add_object(scene, Cylinder::new(base, axis, radius, height, material).with_caps(capped), &args)?;
+            } else {
+                add_object(scene, Cone::new(base, axis, radius, height, material).with_caps(capped), &args)?;
+            }
+        }
+        "torus" => {
+            let args = Args::parse(&rest)?;
+            let torus = Torus::new(
+                args.vec3("center")?.unwrap_or(Vec3::zero()),
+                args.vec3("axis")?.unwrap_or(Vec3::up()),
+                args.number("major")?.unwrap_or(0.5),
+                args.number("minor")?.unwrap_or(0.1),
+                args.material_ref(materials)?,
+            );
+            add_object(scene, torus, &args)?;
+        }
+        "heightfield" => {
+            let args = Args::parse(&rest)?;
+            let path = args.text("path").ok_or("falta 'path'")?;
+            let heightfield = Heightfield::from_file(
+                path,
+                args.vec3("center")?.unwrap_or(Vec3::zero()),
+                args.vec3("size")?.unwrap_or(Vec3::one()),
+                args.number("resolution")?.map(|n| n as usize),
+                args.material_ref(materials)?,
+            )?;
+            add_object(scene, heightfield, &args)?;
+        }
+        "light" => {
+            let args = Args::parse(&rest)?;
+            scene.add_light(Light::new(
+                args.required_vec3("position")?,
+                args.vec3("color")?.unwrap_or(Vec3::one()),
+                args.number("intensity")?.unwrap_or(1.0),
+            ));
+        }
+        "spot" => {
+            let args = Args::parse(&rest)?;
+            scene.add_light(Light::spot(
+                args.required_vec3("position")?,
+                args.vec3("direction")?.unwrap_or(Vec3::new(0.0, -1.0, 0.0)),
+                args.vec3("color")?.unwrap_or(Vec3::one()),
+                args.number("intensity")?.unwrap_or(1.0),
+                args.number("inner")?.unwrap_or(20.0),
+                args.number("outer")?.unwrap_or(30.0),
+            ));
+        }
+        "fog" => {
+            let args = Args::parse(&rest)?;
+            let mut medium = Medium::new(args.number("density")?.unwrap_or(1.0))
+                .with_albedo(args.vec3("albedo")?.unwrap_or(Vec3::one()))
+                .with_anisotropy(args.number("anisotropy")?.unwrap_or(0.0));
+            if let (Some(min), Some(max)) = (args.vec3("min")?, args.vec3("max")?) {
+                medium = medium.with_region(min, max);
+            }
+            scene.add_medium(medium);
+        }
+        "sdf" => {
+            let args = Args::parse(&rest)?;
+            let sdf: Sdf = expression.ok_or("falta la expresión SDF entre paréntesis")?.parse()?;
+            let mut object = SdfObject::new(sdf, args.vec3("position")?.unwrap_or(Vec3::zero()), args.material_ref(materials)?);
+            if let Some(steps) = args.number("steps")? {
+                object = object.with_max_steps(steps as u32);
+            }
+            add_object(scene, object, &args)?;
+        }
+        "vox" => {
+            let args = Args::parse(&rest)?;
+            let path = args.text("path").ok_or("falta 'path'")?;
+            let model = VoxFile::load(path)?;
+            let transform = Mat4::translation(args.vec3("position")?.unwrap_or(Vec3::zero()))
+                * Mat4::rotation_y(args.number("rotation_y")?.unwrap_or(0.0).to_radians());
+            let options = VoxOptions::new()
+                .with_voxel_size(args.number("voxel_size")?.unwrap_or(0.1))
+                .with_transform(transform);
+            if args.text("mode") == Some("cubes") {
+                if args.text("name").is_some() {
+                    return Err("'name' no se admite con 'mode cubes'".into());
+                }
+                for cube in model.to_cubes(0, &options) {
+                    add_object(scene, cube, &args)?;
+                }
+            } else if let Some(grid) = model.to_voxel_grid(0, &options) {
+                add_object(scene, grid, &args)?;
+            }
+        }
+        "camera" => {
+            let args = Args::parse(&rest)?;
+            let projection: Projection = args.text("projection").map(str::parse).transpose()?.unwrap_or_default();
+            // La proporción de aspecto la fija quien renderiza (ver `Camera::set_aspect_ratio`)
+            let camera = Camera::new(
+                args.vec3("position")?.unwrap_or(Vec3::new(-1.0, 1.5, 1.5)),
+                args.vec3("target")?.unwrap_or(Vec3::zero()),
+                args.vec3("up")?.unwrap_or(Vec3::up()),
+                args.number("fov")?.unwrap_or(60.0),
+                1.0,
+            )
+            .with_projection(projection);
+            scene.camera = Some(camera);
+        }
+        "key" => {
+            // key camera <t> ... | key light <índice> <t> ... | key object <nombre> <t> ...
+            let (target, rest) = rest.split_first().ok_or("falta el objetivo de la animación")?;
+            let (id, rest) = match *target {
+                "camera" => (None, rest),
+                _ => {
+                    let (id, rest) = rest.split_first().ok_or("falta el índice o nombre del objetivo")?;
+                    (Some(*id), rest)
+                }
+            };
+            let (time, pairs) = rest.split_first().ok_or("falta el instante del fotograma")?;
+            let args = Args::parse(pairs)?;
+            add_keyframes(&mut scene.timeline, target, id, parse_number(time)?, &args)?;
+        }
+        other => return Err(format!("directiva desconocida '{}'", other).into()),
+    }
+    Ok(())
+}
+
+// Añade el objeto, envuelto en `Moving` si la línea trae una velocidad y
+// registrado por nombre si trae `name` (para animarlo con `key`)
+fn add_object<T: Hittable + 'static>(scene: &mut Scene, object: T, args: &Args) -> Result<()> {
+    let object: Arc<dyn Hittable + Send + Sync> = match args.vec3("velocity")? {
+        Some(velocity) => Arc::new(Moving::from_object(object, Motion::linear(velocity))),
+        None => Arc::new(object),
+    };
+    match args.text("name") {
+        Some(name) => scene.add_named_object(name, object),
+        None => scene.add_shared_object(object),
+    }
+    Ok(())
+}
+
+// Añade a la línea de tiempo un fotograma por cada propiedad presente en la directiva
+fn add_keyframes(timeline: &mut Timeline, target: &str, id: Option<&str>, time: f32, args: &Args) -> Result<()> {
+    let easing: Easing = args.text("easing").map(str::parse).transpose()?.unwrap_or_default();
+    match (target, id) {
+        ("camera", _) => {
+            if let Some(position) = args.vec3("position")? {
+                timeline.camera.position.add_key(time, position, easing);
+            }
+            if let Some(target) = args.vec3("target")? {
+                timeline.camera.target.add_key(time, target, easing);
+            }
+            if let Some(fov) = args.number("fov")? {
+                timeline.camera.fov.add_key(time, fov, easing);
+            }
+        }
+        ("light", Some(index)) => {
+            let index = index.parse::<usize>().map_err(|_| format!("índice de luz inválido '{}'", index))?;
+            let tracks = timeline.light_mut(index);
+            if let Some(position) = args.vec3("position")? {
+                tracks.position.add_key(time, position, easing);
+            }
+            if let Some(color) = args.vec3("color")? {
+                tracks.color.add_key(time, color, easing);
+            }
+            if let Some(intensity) = args.number("intensity")? {
+                tracks.intensity.add_key(time, intensity, easing);
+            }
+        }
+        ("object", Some(name)) => {
+            let tracks = timeline.object_mut(name);
+            let (position, rotation, scale) = (args.vec3("position")?, args.vec3("rotation")?, args.vec3("scale")?);
+            if position.is_some() || rotation.is_some() || scale.is_some() {
+                // Rotación en grados (x, y, z) aplicada como yaw (Y), pitch (X), roll (Z)
+                let rotation = rotation.map_or(Quat::identity(), |r| {
+                    Quat::from_euler(r.y.to_radians(), r.x.to_radians(), r.z.to_radians())
+                });
+                let transform = Transform::new(position.unwrap_or(Vec3::zero()), rotation, scale.unwrap_or(Vec3::one()));
+                tracks.transform.add_key(time, transform, easing);
+            }
+            let material = &mut tracks.material;
+            if let Some(color) = args.vec3("color")? {
+                material.color.add_key(time, color, easing);
+            }
+            if let Some(emission) = args.vec3("emission")? {
+                material.emission.add_key(time, emission, easing);
+            }
+            for (key, track) in [
+                ("specular", &mut material.specular),
+                ("roughness", &mut material.roughness),
+                ("reflectivity", &mut material.reflectivity),
+                ("transparency", &mut material.transparency),
+            ] {
+                if let Some(value) = args.number(key)? {
+                    track.add_key(time, value, easing);
+                }
+            }
+        }
+        (other, _) => return Err(format!("objetivo de animación inválido '{}'", other).into()),
+    }
+    Ok(())
+}
+
+fn parse_number(token: &str) -> Result<f32> {
+    token.parse::<f32>().map_err(|_| format!("número inválido '{}'", token).into())
+}
+
+fn positional_vec3(tokens: &[&str]) -> Result<Vec3> {
+    match tokens {
+        [x, y, z] => Ok(Vec3::new(parse_number(x)?, parse_number(y)?, parse_number(z)?)),
+        _ => Err("se esperaban tres números".into()),
+    }
+}
+
+// Pares clave-valor de una directiva
+struct Args<'a> {
+    values: HashMap<&'a str, Vec<&'a str>>,
+}
+
+impl<'a> Args<'a> {
+    // Número de valores que acompaña a cada clave
+    fn arity(key: &str) -> usize {
+        match key {
+            "color" | "emission" | "center" | "size" | "position" | "direction" | "albedo" | "min" | "max"
+            | "point" | "normal" | "base" | "axis" | "velocity" | "target" | "rotation" | "scale" | "up" => 3,
+            _ => 1,
+        }
+    }
+
+    fn parse(tokens: &[&'a str]) -> Result<Self> {
+        let mut values = HashMap::new();
+        let mut i = 0;
+        while i < tokens.len() {
+            let key = tokens[i];
+            let count = Args::arity(key);
+            let end = i + 1 + count;
+            if end > tokens.len() {
+                return Err(format!("'{}' necesita {} valor(es)", key, count).into());
+            }
+            values.insert(key, tokens[i + 1..end].to_vec());
+            i = end;
+        }
+        Ok(Args { values })
+    }
+
+    fn text(&self, key: &str) -> Option<&'a str> {
+        self.values.get(key).and_then(|v| v.first().copied())
+    }
+
+    fn number(&self, key: &str) -> Result<Option<f32>> {
+        self.text(key).map(parse_number).transpose()
+    }
+
+    fn vec3(&self, key: &str) -> Result<Option<Vec3>> {
+        self.values.get(key).map(|v| positional_vec3(v)).transpose()
+    }
+
+    fn required_vec3(&self, key: &str) -> Result<Vec3> {
+        self.vec3(key)?.ok_or_else(|| format!("falta '{}'", key).into())
+    }
+
+    // Material referenciado por nombre (o el material por defecto)
+    fn material_ref(&self, materials: &HashMap<String, Material>) -> Result<Material> {
+        let name = self.text("material").unwrap_or("default");
+        materials.get(name).cloned().ok_or_else(|| format!("material desconocido '{}'", name).into())
+    }
+
+    // Construye un material con los mismos builders que la escena por código
+    fn material(&self) -> Result<Material> {
+        let mut material = Material::new();
+        if let Some(path) = self.text("texture") {
+            material = material.with_texture(Texture::from_file(path)?);
+        }
+        if let Some(color) = self.vec3("color")? {
+            material = material.with_color(color);
+        }
+        if let Some(value) = self.number("specular")? {
+            material = material.with_specular(value);
+        }
+        if let Some(value) = self.number("roughness")? {
+            material = material.with_roughness(value);
+        }
+        if let Some(value) = self.number("reflectivity")? {
+            material = material.with_reflectivity(value);
+        }
+        if let Some(value) = self.number("transparency")? {
+            material = material.with_transparency(value);
+        }
+        if let Some(value) = self.number("ior")? {
+            material = material.with_refractive_index(value);
+        }
+        if let Some(emission) = self.vec3("emission")? {
+            material = material.with_emission(emission);
+        }
+        match self.text("dispersion") {
+            Some("water") => material = material.with_dispersion(Dispersion::water()),
+            Some("bk7") => material = material.with_dispersion(Dispersion::bk7()),
+            Some("dense_flint") => material = material.with_dispersion(Dispersion::dense_flint()),
+            Some(other) => return Err(format!("dispersión desconocida '{}'", other).into()),
+            None => {}
+        }
+        Ok(material)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animation::track::Keyframe;
+    use crate::camera::FisheyeMapping;
+    use crate::math::Ray;
+
+    #[test]
+    fn key_directives_fill_timeline() {
+        let scene = parse_scene(
+            "cube center 0 0 0 size 1 1 1 name caja\n\
+             key camera 0 position 1 2 3 target 0 0 0 fov 50 easing smooth\n\
+             key light 2 1.5 intensity 3 color 1 0.5 0\n\
+             key object caja 2 position 0 1 0 rotation 0 90 0 emission 1 1 1 easing step\n",
+        )
+        .unwrap();
+        let timeline = &scene.timeline;
+
+        let position = &timeline.camera.position.keys()[0];
+        assert_eq!((position.time, position.easing), (0.0, Easing::CatmullRom));
+        assert!((position.value - Vec3::new(1.0, 2.0, 3.0)).length() < 1e-6);
+        assert_eq!(timeline.camera.fov.sample(0.0), Some(50.0));
+
+        assert_eq!(timeline.lights[0].index, 2);
+        assert_eq!(timeline.lights[0].intensity.keys()[0].time, 1.5);
+        assert!((timeline.lights[0].color.sample(1.5).unwrap() - Vec3::new(1.0, 0.5, 0.0)).length() < 1e-6);
+
+        let object = &timeline.objects[0];
+        assert_eq!(object.name, "caja");
+        let transform = object.transform.keys()[0].value;
+        assert!((transform.translation - Vec3::new(0.0, 1.0, 0.0)).length() < 1e-6);
+        assert_eq!(object.material.emission.keys()[0].easing, Easing::Step);
+        assert!(scene.named_object("caja").is_some());
+    }
+
+    #[test]
+    fn key_directive_errors_report_line() {
+        let error = parse_scene("background 0 0 0\nkey light x 1 intensity 2\n").unwrap_err().to_string();
+        assert!(error.starts_with("línea 2:"), "{}", error);
+        assert!(parse_scene("key sol 1 0 intensity 2").is_err());
+        assert!(parse_scene("key camera").is_err());
+    }
+
+    #[test]
+    fn camera_path_round_trips() {
+        let mut tracks = CameraTracks::default();
+        tracks.position.add_key(0.0, Vec3::new(1.0, 2.5, -3.0), Easing::CatmullRom);
+        tracks.position.add_key(1.25, Vec3::new(-0.5, 2.0, 4.125), Easing::EaseInOut);
+        tracks.target.add_key(0.0, Vec3::zero(), Easing::Linear);
+        tracks.target.add_key(2.5, Vec3::new(0.0, 1.0, 0.0), Easing::Step);
+        tracks.fov.add_key(0.75, 42.5, Easing::EaseOut);
+
+        let path = std::env::temp_dir().join(format!("camera_path_{}.txt", std::process::id()));
+        let path = path.to_str().unwrap();
+        save_camera_path(&tracks, path).unwrap();
+        let loaded = load_camera_path(path);
+        let _ = std::fs::remove_file(path);
+        let loaded = loaded.unwrap();
+
+        let same_vec3 = |a: &[Keyframe<Vec3>], b: &[Keyframe<Vec3>]| {
+            a.len() == b.len()
+                && a.iter().zip(b).all(|(a, b)| {
+                    a.time == b.time && a.easing == b.easing && (a.value - b.value).length() < 1e-6
+                })
+        };
+        assert!(same_vec3(tracks.position.keys(), loaded.position.keys()));
+        assert!(same_vec3(tracks.target.keys(), loaded.target.keys()));
+        let fov: Vec<(f32, f32, Easing)> = loaded.fov.keys().iter().map(|k| (k.time, k.value, k.easing)).collect();
+        assert_eq!(fov, vec![(0.75, 42.5, Easing::EaseOut)]);
+    }
+
+    #[test]
+    fn scene_settings_directives() {
+        let scene = parse_scene(
+            "background 0.1 0.2 0.3\n\
+             ambient 0.05 0.05 0.05\n\
+             fog density 2 albedo 0.9 0.9 0.9 anisotropy 0.3 min -1 0 -1 max 1 1 1\n\
+             camera position 0 1 5 target 0 1 0 fov 120 projection fisheye_equisolid\n",
+        )
+        .unwrap();
+        assert!((scene.background_color - Vec3::new(0.1, 0.2, 0.3)).length() < 1e-6);
+        assert!((scene.ambient_light - Vec3::new(0.05, 0.05, 0.05)).length() < 1e-6);
+
+        let fog = &scene.media[0];
+        assert_eq!((fog.density, fog.anisotropy), (2.0, 0.3));
+        let (min, max) = fog.region.unwrap();
+        assert!((min - Vec3::new(-1.0, 0.0, -1.0)).length() < 1e-6 && (max - Vec3::one()).length() < 1e-6);
+
+        let camera = scene.camera.unwrap();
+        assert_eq!(camera.projection, Projection::Fisheye(FisheyeMapping::Equisolid));
+        assert_eq!(camera.fov, 120.0);
+        assert!((camera.position - Vec3::new(0.0, 1.0, 5.0)).length() < 1e-6);
+    }
+
+    #[test]
+    fn materials_are_referenced_by_name() {
+        let scene = parse_scene(
+            "material vidrio color 0.9 0.9 1 reflectivity 0.1 transparency 0.8 ior 1.5\n\
+             material prisma transparency 1 dispersion bk7\n\
+             cube center 0 0 0 size 1 1 1 material vidrio\n\
+             cube center 3 0 0 size 1 1 1 material prisma\n",
+        )
+        .unwrap();
+        let material_at = |x: f32| {
+            let ray = Ray::new(Vec3::new(x, 0.0, 5.0), Vec3::new(0.0, 0.0, -1.0));
+            scene.hit(&ray, 0.001, f32::INFINITY).unwrap().material
+        };
+        let glass = material_at(0.0);
+        assert_eq!((glass.reflectivity, glass.transparency, glass.refractive_index), (0.1, 0.8, 1.5));
+        assert!(glass.dispersion.is_none());
+        // La dispersión fija el índice base al de 550nm
+        let prism = material_at(3.0);
+        assert!(prism.dispersion.is_some() && prism.refractive_index > 1.5);
+
+        let error = parse_scene("cube material piedra").unwrap_err().to_string();
+        assert!(error.contains("material desconocido 'piedra'"), "{}", error);
+        assert!(parse_scene("material vidrio dispersion diamante").is_err());
+    }
+
+    #[test]
+    fn geometry_directives_add_objects() {
+        let scene = parse_scene(
+            "cube center 0 0 0 size 1 1 1 name caja\n\
+             water center 0 0 0 size 1 0.2 1 waves 2 amplitude 0.01\n\
+             plane point 0 -1 0 normal 0 1 0 size 4 0 4 uv_scale 2\n\
+             disk center 0 2 0 normal 0 1 0 radius 0.5\n\
+             cylinder base 2 0 0 axis 0 1 0 radius 0.1 height 1 caps 0\n\
+             cone base -2 0 0 axis 0 1 0 radius 0.2 height 0.4\n\
+             torus center 0 3 0 axis 0 1 0 major 0.5 minor 0.05\n\
+             sdf position 3 0 0 steps 64 (smooth_union 0.1 (sphere 0.2) (box 0.1 0.3 0.1))\n\
+             cube center 0 5 0 velocity 1 0 0\n",
+        )
+        .unwrap();
+        assert_eq!(scene.objects.len(), 9);
+        assert!(scene.named_object("caja").is_some());
+
+        // El plano limitado ocupa x, z ∈ [-2, 2] a la altura -1
+        let down = |x: f32| Ray::new(Vec3::new(x, -0.5, 1.5), Vec3::new(0.0, -1.0, 0.0));
+        assert!((scene.hit(&down(1.5), 0.001, f32::INFINITY).unwrap().t - 0.5).abs() < 1e-4);
+        assert!(scene.hit(&down(2.5), 0.001, f32::INFINITY).is_none());
+    }
+
+    #[test]
+    fn light_directives() {
+        let scene = parse_scene(
+            "light position 0 4 0 color 1 0.9 0.8 intensity 2\n\
+             spot position 0 3 0 direction 0 -2 0 intensity 3 inner 15 outer 25\n",
+        )
+        .unwrap();
+        let lights = scene.lights();
+        assert_eq!(lights.len(), 2);
+        assert_eq!(lights[0].intensity, 2.0);
+        assert!(lights[0].spot.is_none());
+        let spot = lights[1].spot.as_ref().unwrap();
+        assert!((spot.direction - Vec3::new(0.0, -1.0, 0.0)).length() < 1e-6);
+        assert!(spot.inner_cos > spot.outer_cos);
+    }
+
+    #[test]
+    fn sdf_expressions_parse() {
+        let distance = |text: &str, p: Vec3| text.parse::<Sdf>().unwrap().distance(p);
+        assert!((distance("(sphere 0.5)", Vec3::zero()) + 0.5).abs() < 1e-6);
+        assert!((distance("(translate 1 0 0 (box 0.5 0.5 0.5))", Vec3::new(1.0, 0.0, 0.0)) + 0.5).abs() < 1e-6);
+        assert!((distance("(scale 2 (sphere 0.5))", Vec3::zero()) + 1.0).abs() < 1e-6);
+        assert!((distance("(subtract (sphere 1) (sphere 0.5))", Vec3::zero()) - 0.5).abs() < 1e-6);
+        assert!((distance("(intersection (sphere 1) (translate 1 0 0 (sphere 1)))", Vec3::zero())).abs() < 1e-6);
+        // Una unión admite más de dos operandos
+        let union = "(union (sphere 0.1) (translate 1 0 0 (sphere 0.1)) (translate 2 0 0 (sphere 0.1)))";
+        assert!((distance(union, Vec3::new(2.0, 0.0, 0.0)) + 0.1).abs() < 1e-6);
+        assert!(distance("(twist 2 (torus 0.5 0.1))", Vec3::new(0.5, 0.0, 0.0)) < 0.0);
+        assert!(distance("(round_box 0.5 0.5 0.5 0.1)", Vec3::zero()) < 0.0);
+
+        for invalid in ["", "sphere 1", "(sphere)", "(sphere uno)", "(cilindro 1)", "(sphere 1", "(sphere 1) (sphere 2)"] {
+            assert!(invalid.parse::<Sdf>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn errors_report_line_number() {
+        let error_at = |text: &str| parse_scene(text).unwrap_err().to_string();
+
+        let error = error_at("# comentario\n\nbackground 0 0 0\nesfera center 0 0 0\n");
+        assert!(error.starts_with("línea 4:") && error.contains("directiva desconocida 'esfera'"), "{}", error);
+
+        let error = error_at("ambient 0 0 0\ncube center 0 0\n");
+        assert!(error.starts_with("línea 2:") && error.contains("'center' necesita 3 valor(es)"), "{}", error);
+
+        let error = error_at("background 0 0\n");
+        assert!(error.starts_with("línea 1:"), "{}", error);
+
+        for (text, message) in [
+            ("light color 1 1 1", "falta 'position'"),
+            ("heightfield size 1 1 1", "falta 'path'"),
+            ("sdf position 0 0 0", "falta la expresión SDF"),
+            ("sdf (sphere 1", "se esperaba ')'"),
+            ("cube size 1 uno 1", "número inválido 'uno'"),
+            ("camera projection cilindrica", "proyección desconocida"),
+        ] {
+            let error = error_at(&format!("background 0 0 0\n{}\n", text));
+            assert!(error.starts_with("línea 2:") && error.contains(message), "{}", error);
+        }
+        assert!(error_at("vox path no_existe.vox").starts_with("línea 1:"));
+    }
+}
@@ -0,0 +1,419 @@
+// Importador de modelos MagicaVoxel (.vox)
+
+use crate::math::{Vec3, Mat4};
+use crate::material::Material;
+use crate::geometry::{Cube, Instance, VoxelGrid};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+// Un modelo del archivo, ya convertido a Y arriba
+#[derive(Debug, Clone)]
+pub struct VoxModel {
+    // Tamaño en vóxeles (x, y, z) con Y arriba
+    pub size: (usize, usize, usize),
+    // Vóxeles ocupados: (x, y, z, índice de color 1..=255)
+    pub voxels: Vec<(usize, usize, usize, u8)>,
+}
+
+// Opciones de colocación en la escena
+#[derive(Debug, Clone, Copy)]
+pub struct VoxOptions {
+    // Lado de cada vóxel en unidades de la escena
+    pub voxel_size: f32,
+    // Transformación local -> mundo aplicada al modelo
+    pub transform: Mat4,
+    // Usa almacenamiento disperso para la rejilla
+    pub sparse: bool,
+}
+
+impl VoxOptions {
+    pub fn new() -> Self {
+        VoxOptions {
+            voxel_size: 0.1,
+            transform: Mat4::identity(),
+            sparse: false,
+        }
+    }
+
+    // Builder pattern para configurar el tamaño de vóxel
+    pub fn with_voxel_size(mut self, voxel_size: f32) -> Self {
+        self.voxel_size = voxel_size.max(1e-4);
+        self
+    }
+
+    // Builder pattern para configurar la transformación de colocación
+    pub fn with_transform(mut self, transform: Mat4) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    // Builder pattern para elegir almacenamiento disperso
+    pub fn with_sparse(mut self, sparse: bool) -> Self {
+        self.sparse = sparse;
+        self
+    }
+}
+
+impl Default for VoxOptions {
+    fn default() -> Self {
+        VoxOptions::new()
+    }
+}
+
+// Contenido de un archivo .vox
+#[derive(Debug, Clone)]
+pub struct VoxFile {
+    pub models: Vec<VoxModel>,
+    // Paleta RGBA; el índice de color i usa palette[i - 1]
+    pub palette: Vec<[u8; 4]>,
+    // Propiedades de material (chunk MATL) por índice de color
+    pub materials: HashMap<u8, HashMap<String, String>>,
+}
+
+impl VoxFile {
+    // Carga un archivo .vox desde disco
+    pub fn load(file_path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let bytes = std::fs::read(file_path)?;
+        VoxFile::parse(&bytes)
+    }
+
+    // Interpreta el contenido binario de un archivo .vox
+    pub fn parse(bytes: &[u8]) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut reader = Reader::new(bytes);
+        if reader.bytes(4)? != b"VOX " {
+            return Err("no es un archivo .vox".into());
+        }
+        let _version = reader.u32()?;
+
+        let (id, content_size, _children_size) = reader.chunk_header()?;
+        if id != *b"MAIN" {
+            return Err("falta el chunk MAIN".into());
+        }
+        reader.bytes(content_size)?;
+
+        let mut file = VoxFile {
+            models: Vec::new(),
+            palette: default_palette(),
+            materials: HashMap::new(),
+        };
+        let mut pending_size = None;
+
+        // Los hijos de MAIN se leen en secuencia; los chunks desconocidos se ignoran
+        while !reader.is_empty() {
+            let (id, content_size, children_size) = reader.chunk_header()?;
+            let mut content = Reader::new(reader.bytes(content_size)?);
+            reader.bytes(children_size)?;
+
+            match &id {
+                b"SIZE" => {
+                    let (x, y, z) = (content.u32()? as usize, content.u32()? as usize, content.u32()? as usize);
+                    pending_size = Some((x, y, z));
+                }
+                b"XYZI" => {
+                    let (sx, sy, sz) = pending_size.take().ok_or("chunk XYZI sin SIZE")?;
+                    let count = content.u32()? as usize;
+                    let mut voxels = Vec::with_capacity(count);
+                    for _ in 0..count {
+                        let v = content.bytes(4)?;
+                        let (x, y, z, color) = (v[0] as usize, v[1] as usize, v[2] as usize, v[3]);
+                        if color == 0 || x >= sx || y >= sy || z >= sz {
+                            continue;
+                        }
+                        // MagicaVoxel usa Z arriba: (x, y, z) -> (x, z, -y) conserva la orientación
+                        voxels.push((x, z, sy - 1 - y, color));
+                    }
+                    file.models.push(VoxModel { size: (sx, sz, sy), voxels });
+                }
+                b"RGBA" => {
+                    let mut palette = Vec::with_capacity(255);
+                    for _ in 0..255 {
+                        let c = content.bytes(4)?;
+                        palette.push([c[0], c[1], c[2], c[3]]);
+                    }
+                    file.palette = palette;
+                }
+                b"MATL" => {
+                    let material_id = content.u32()?;
+                    let properties = content.dict()?;
+                    if (1..=255).contains(&material_id) {
+                        file.materials.insert(material_id as u8, properties);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if file.models.is_empty() {
+            return Err("el archivo .vox no contiene modelos".into());
+        }
+        Ok(file)
+    }
+
+    // Material equivalente a un índice de color de la paleta
+    pub fn material(&self, color_index: u8) -> Material {
+        let rgba = self.palette.get((color_index as usize).wrapping_sub(1)).copied().unwrap_or([255, 255, 255, 255]);
+        let color = Vec3::new(rgba[0] as f32 / 255.0, rgba[1] as f32 / 255.0, rgba[2] as f32 / 255.0);
+        let mut material = Material::new().with_color(color);
+
+        let Some(properties) = self.materials.get(&color_index) else {
+            return material;
+        };
+        let number = |key: &str| properties.get(key).and_then(|v| v.parse::<f32>().ok());
+        let kind = properties.get("_type").map(String::as_str).unwrap_or("_diffuse");
+
+        if let Some(rough) = number("_rough") {
+            material = material.with_roughness(rough);
+        }
+        if let Some(spec) = number("_sp").or_else(|| number("_spec")) {
+            material = material.with_specular(spec);
+        }
+
+        if kind == "_metal" || kind == "_blend" {
+            let metal = number("_metal").unwrap_or(if kind == "_metal" { 1.0 } else { 0.0 });
+            let specular = material.specular.max(metal);
+            material = material.with_reflectivity(metal).with_specular(specular);
+        }
+
+        if kind == "_glass" || kind == "_blend" {
+            let transparency = number("_trans").or_else(|| number("_alpha")).unwrap_or(if kind == "_glass" { 1.0 } else { 0.0 });
+            // MagicaVoxel guarda el índice de refracción menos uno
+            let ior = number("_ior").map(|v| if v < 1.0 { v + 1.0 } else { v }).unwrap_or(1.5);
+            material = material.with_transparency(transparency).with_refractive_index(ior);
+        }
+
+        if kind == "_emit" || kind == "_blend" {
+            let emit = number("_emit").unwrap_or(if kind == "_emit" { 1.0 } else { 0.0 });
+            // _flux (0..4) escala la potencia; aproximación lineal
+            let flux = number("_flux").unwrap_or(0.0);
+            if emit > 0.0 {
+                material = material.with_emission(color * (emit * (1.0 + flux)));
+            }
+        }
+
+        material
+    }
+
+    // Convierte un modelo en una rejilla de vóxeles colocada con la transformación de las opciones.
+    // En espacio local el modelo queda centrado en x/z y apoyado en y = 0.
+    pub fn to_voxel_grid(&self, model_index: usize, options: &VoxOptions) -> Option<Instance> {
+        let model = self.models.get(model_index)?;
+        let origin = local_origin(model, options.voxel_size);
+        let mut grid = if options.sparse {
+            VoxelGrid::sparse(origin, model.size, options.voxel_size)
+        } else {
+            VoxelGrid::new(origin, model.size, options.voxel_size)
+        };
+        grid = grid.with_palette((1..=255).map(|i| self.material(i)).collect());
+
+        for &(x, y, z, color) in &model.voxels {
+            grid.set(x, y, z, color);
+        }
+        Some(Instance::from_object(grid, options.transform))
+    }
+
+    // Convierte un modelo en instancias de un cubo unitario compartido por color.
+    // Cada cubo es un objeto independiente (los vóxeles emisivos se vuelven luces de área).
+    pub fn to_cubes(&self, model_index: usize, options: &VoxOptions) -> Vec<Instance> {
+        let Some(model) = self.models.get(model_index) else {
+            return Vec::new();
+        };
+        let origin = local_origin(model, options.voxel_size);
+        let size = options.voxel_size;
+        let mut shared: HashMap<u8, Arc<Cube>> = HashMap::new();
+
+        model.voxels.iter().map(|&(x, y, z, color)| {
+            let cube = shared.entry(color).or_insert_with(|| {
+                Arc::new(Cube::new(Vec3::zero(), Vec3::one(), self.material(color)))
+            }).clone();
+            let center = origin + Vec3::new(x as f32 + 0.5, y as f32 + 0.5, z as f32 + 0.5) * size;
+            let placement = Mat4::translation(center) * Mat4::scaling(Vec3::new(size, size, size));
+            Instance::new(cube, options.transform * placement)
+        }).collect()
+    }
+}
+
+// Esquina mínima del modelo en espacio local (centrado en x/z, base en y = 0)
+fn local_origin(model: &VoxModel, voxel_size: f32) -> Vec3 {
+    Vec3::new(-(model.size.0 as f32) * 0.5, 0.0, -(model.size.2 as f32) * 0.5) * voxel_size
+}
+
+// Paleta por defecto de MagicaVoxel (cuando el archivo no trae chunk RGBA)
+fn default_palette() -> Vec<[u8; 4]> {
+    let steps = [0xff, 0xcc, 0x99, 0x66, 0x33, 0x00];
+    let mut palette = Vec::with_capacity(255);
+
+    // Cubo de color 6x6x6 sin el negro (el azul varía más rápido)
+    for &r in &steps {
+        for &g in &steps {
+            for &b in &steps {
+                if r != 0 || g != 0 || b != 0 {
+                    palette.push([r, g, b, 0xff]);
+                }
+            }
+        }
+    }
+
+    // Rampas de rojo, verde, azul y gris
+    let ramp = [0xee, 0xdd, 0xbb, 0xaa, 0x88, 0x77, 0x55, 0x44, 0x22, 0x11];
+    for channel in 0..4 {
+        for &value in &ramp {
+            palette.push(match channel {
+                0 => [value, 0, 0, 0xff],
+                1 => [0, value, 0, 0xff],
+                2 => [0, 0, value, 0xff],
+                _ => [value, value, value, 0xff],
+            });
+        }
+    }
+    palette
+}
+
+// Lector little-endian sobre un buffer de bytes
+struct Reader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Reader { data, position: 0 }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.position >= self.data.len()
+    }
+
+    fn bytes(&mut self, count: usize) -> Result<&'a [u8], Box<dyn std::error::Error>> {
+        let end = self.position.checked_add(count).filter(|&end| end <= self.data.len()).ok_or("archivo .vox truncado")?;
+        let slice = &self.data[self.position..end];
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn u32(&mut self) -> Result<u32, Box<dyn std::error::Error>> {
+        let b = self.bytes(4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn string(&mut self) -> Result<String, Box<dyn std::error::Error>> {
+        let length = self.u32()? as usize;
+        Ok(String::from_utf8_lossy(self.bytes(length)?).into_owned())
+    }
+
+    fn dict(&mut self) -> Result<HashMap<String, String>, Box<dyn std::error::Error>> {
+        let count = self.u32()?;
+        let mut dict = HashMap::new();
+        for _ in 0..count {
+            let key = self.string()?;
+            let value = self.string()?;
+            dict.insert(key, value);
+        }
+        Ok(dict)
+    }
+
+    // (id, tamaño del contenido, tamaño de los hijos)
+    fn chunk_header(&mut self) -> Result<([u8; 4], usize, usize), Box<dyn std::error::Error>> {
+        let id = self.bytes(4)?;
+        let content_size = self.u32()? as usize;
+        let children_size = self.u32()? as usize;
+        Ok(([id[0], id[1], id[2], id[3]], content_size, children_size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(id: &[u8; 4], content: &[u8]) -> Vec<u8> {
+        let mut bytes = id.to_vec();
+        bytes.extend_from_slice(&(content.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&0u32.to_le_bytes());
+        bytes.extend_from_slice(content);
+        bytes
+    }
+
+    fn material_chunk(id: u32, properties: &[(&str, &str)]) -> Vec<u8> {
+        let mut content = id.to_le_bytes().to_vec();
+        content.extend_from_slice(&(properties.len() as u32).to_le_bytes());
+        for text in properties.iter().flat_map(|&(key, value)| [key, value]) {
+            content.extend_from_slice(&(text.len() as u32).to_le_bytes());
+            content.extend_from_slice(text.as_bytes());
+        }
+        chunk(b"MATL", &content)
+    }
+
+    // Archivo con un modelo de 2x3x4 (Z arriba), paleta propia y tres materiales
+    fn sample_file() -> Vec<u8> {
+        let size: Vec<u8> = [2u32, 3, 4].iter().flat_map(|v| v.to_le_bytes()).collect();
+        let mut voxels = 4u32.to_le_bytes().to_vec();
+        voxels.extend_from_slice(&[1, 0, 2, 1, 0, 1, 0, 2, 1, 2, 3, 3, 5, 0, 0, 4]);
+        let mut palette = Vec::new();
+        for i in 0..255u32 {
+            palette.extend_from_slice(&[i as u8, 100, 200, 255]);
+        }
+
+        let mut children = chunk(b"SIZE", &size);
+        children.extend(chunk(b"XYZI", &voxels));
+        children.extend(chunk(b"RGBA", &palette));
+        children.extend(material_chunk(2, &[("_type", "_glass"), ("_alpha", "0.8"), ("_ior", "0.3")]));
+        children.extend(material_chunk(3, &[("_type", "_metal"), ("_metal", "0.7"), ("_rough", "0.2")]));
+        children.extend(material_chunk(4, &[("_type", "_emit"), ("_emit", "2"), ("_flux", "1")]));
+
+        let mut bytes = b"VOX ".to_vec();
+        bytes.extend_from_slice(&150u32.to_le_bytes());
+        bytes.extend_from_slice(b"MAIN");
+        bytes.extend_from_slice(&0u32.to_le_bytes());
+        bytes.extend_from_slice(&(children.len() as u32).to_le_bytes());
+        bytes.extend(children);
+        bytes
+    }
+
+    fn approx_vec(a: Vec3, b: Vec3) -> bool {
+        (a - b).length() < 1e-5
+    }
+
+    #[test]
+    fn parses_model_with_y_up() {
+        let file = VoxFile::parse(&sample_file()).unwrap();
+        let model = &file.models[0];
+        assert_eq!(model.size, (2, 4, 3));
+        // (1, 0, 2) -> (1, 2, 2); el vóxel fuera del tamaño se descarta
+        assert_eq!(model.voxels, vec![(1, 2, 2, 1), (0, 0, 1, 2), (1, 3, 0, 3)]);
+    }
+
+    #[test]
+    fn palette_maps_to_material_color() {
+        let file = VoxFile::parse(&sample_file()).unwrap();
+        // El índice de color i usa palette[i - 1]
+        let material = file.material(1);
+        assert!(approx_vec(material.texture.value(0.0, 0.0), Vec3::new(0.0, 100.0 / 255.0, 200.0 / 255.0)));
+        assert_eq!(material.transparency, 0.0);
+        assert_eq!(material.reflectivity, 0.0);
+        assert!(approx_vec(material.emission, Vec3::zero()));
+    }
+
+    #[test]
+    fn matl_types_map_to_glass_metal_and_emissive() {
+        let file = VoxFile::parse(&sample_file()).unwrap();
+
+        let glass = file.material(2);
+        assert!((glass.transparency - 0.8).abs() < 1e-6);
+        assert!((glass.refractive_index - 1.3).abs() < 1e-6);
+
+        let metal = file.material(3);
+        assert!((metal.reflectivity - 0.7).abs() < 1e-6);
+        assert!(metal.specular >= 0.7);
+        assert!((metal.roughness - 0.2).abs() < 1e-6);
+
+        let emissive = file.material(4);
+        let color = Vec3::new(3.0 / 255.0, 100.0 / 255.0, 200.0 / 255.0);
+        assert!(approx_vec(emissive.emission, color * 4.0));
+    }
+
+    #[test]
+    fn rejects_invalid_files() {
+        assert!(VoxFile::parse(b"RIFF0000").is_err());
+        let bytes = sample_file();
+        assert!(VoxFile::parse(&bytes[..bytes.len() - 3]).is_err());
+    }
+}
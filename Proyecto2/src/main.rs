@@ -12,17 +12,26 @@ mod raytracer;
 mod texture;
 mod framebuffer;
 mod adaptive_config;
+mod spectral;
+mod photon;
+mod volume;
+mod loaders;
+mod animation;
 
 use math::Vec3;
-use material::Material;
-use geometry::{Cube};
-use lighting::Light;
-use camera::Camera;
+use material::{Material, Dispersion};
+use geometry::{Cube, WaterSurface};
+use lighting::{Light, LightSampling};
+use camera::{Camera, Projection, StereoRig, StereoLayout, CameraBookmarks};
 use scene::Scene;
-use raytracer::Raytracer;
+use raytracer::{Raytracer, RenderMode, BackgroundRender, TileOrder};
 use texture::Texture;
+use volume::Medium;
 use framebuffer::Framebuffer;
-use adaptive_config::{RenderQuality, AdaptiveConfig};
+use photon::PhotonConfig;
+use loaders::{load_scene, load_camera_path, save_camera_path};
+use adaptive_config::{RenderQuality, RenderResolution, AdaptiveConfig, AdaptiveController, FrameSettings};
+use animation::{Timeline, CameraTracks, CameraRecorder, SequenceConfig, render_sequence};
 
 fn main() {
     println!("Inicializando Raytracer Ultra-Optimizado con Rotación Automática...");
@@ -36,22 +45,56 @@ fn main() {
     println!("⚡ Usando {} threads para paralelización", num_threads);
     println!("🎮 Calidad: {}", current_quality.description());
 
-    // Usar dimensiones dinámicas basadas en la calidad
-    let (render_width, render_height) = current_quality.dimensions();
-    let (display_width, display_height) = (800, 600); // Ventana fija
+    // Tamaño inicial de la ventana (redimensionable; la resolución de render se deriva de él)
+    let (display_width, display_height) = (800, 600);
 
-    // Inicializar ventana con tamaño fijo
-    let (mut rl, thread) = raylib::init()
-        .size(display_width, display_height)
-        .title("Raytracer Ultra-Optimizado - Rotación Auto")
-        .build();
+    // Escena desde archivo (--scene ruta) o la escena del spa por defecto
+    let args: Vec<String> = std::env::args().collect();
+    
+    // Resolución de render: --resolution ANCHOxALTO (fija) o PORCENTAJE% de la ventana
+    let custom_resolution = args.iter().position(|a| a == "--resolution")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|v| v.parse::<RenderResolution>().map_err(|e| println!("{}", e)).ok());
+    
+    // Presupuesto por fotograma en movimiento: --frame-budget MILISEGUNDOS
+    if let Some(budget) = args.iter().position(|a| a == "--frame-budget")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|v| v.parse::<f32>().ok())
+    {
+        config.frame_budget = budget / 1000.0;
+    }
+    if let Some(resolution) = custom_resolution {
+        config.resolution = resolution;
+    }
 
     // Crear raytracer con configuración adaptativa
-    let mut raytracer = Raytracer::with_config(&config);
-
-    // Crear escena optimizada con cubos más pequeños
-    let scene = create_optimized_scene();
-    println!("Escena optimizada creada con {} luces", scene.lights.len());
+    let mut raytracer = Raytracer::with_config(&config, display_width as u32, display_height as u32);
+    let (render_width, render_height) = (raytracer.width, raytracer.height);
+    println!("Resolución de render: {} ({}x{})", config.resolution.description(), render_width, render_height);
+    let scene_path = args.iter().position(|a| a == "--scene").and_then(|i| args.get(i + 1));
+    let mut scene = match scene_path.map(|path| (path, load_scene(path))) {
+        Some((path, Ok(scene))) => {
+            println!("Escena cargada desde {}", path);
+            scene
+        }
+        Some((path, Err(e))) => {
+            println!("Error cargando {}: {} (se usa la escena por defecto)", path, e);
+            create_optimized_scene()
+        }
+        None => create_optimized_scene(),
+    };
+    println!("Escena optimizada creada con {} luces", scene.lights().len());
+    
+    // Pre-paso de fotones: cáusticas del agua sobre el mármol
+    if config.photon_count > 0 {
+        let mut photon_config = PhotonConfig::new()
+            .with_photon_count(config.photon_count)
+            .with_gather_radius(config.photon_gather_radius);
+        if scene_path.is_none() {
+            photon_config = photon_config.with_focus(Vec3::new(0.0, 0.1, 0.0), 0.45); // Esfera que envuelve el jacuzzi
+        }
+        scene.build_photon_map(&photon_config);
+    }
     
     // Crear cámara optimizada para jacuzzi compacto
     let mut camera = Camera::new(
@@ -59,12 +102,137 @@ fn main() {
         Vec3::new(0.0, 0.0, 0.0),     // Mirando al centro del jacuzzi
         Vec3::up(),                     
         60.0,                         // FOV amplio para captar toda la escena compacta
-        display_width as f32 / display_height as f32, 
+        render_width as f32 / render_height as f32, 
     );
+    
+    // Cámara propia del archivo de escena (directiva `camera`)
+    if let Some(scene_camera) = scene.camera.clone() {
+        camera = scene_camera;
+        camera.set_aspect_ratio(render_width as f32 / render_height as f32);
+    }
+    
+    // Proyección desde la línea de comandos: --projection NOMBRE [--fov GRADOS]
+    if let Some(name) = args.iter().position(|a| a == "--projection").and_then(|i| args.get(i + 1)) {
+        match name.parse::<Projection>() {
+            Ok(projection) => camera.set_projection(projection),
+            Err(e) => println!("{} (se mantiene {:?})", e, camera.projection),
+        }
+    }
+    if let Some(fov) = args.iter().position(|a| a == "--fov")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|v| v.parse::<f32>().ok())
+    {
+        camera.set_fov(fov);
+    }
+    println!("Proyección: {:?} (FOV {:.0}°)", camera.projection, camera.fov);
+    
+    // Recorrido de cámara grabado (tecla G): se reproduce con V y guía la secuencia de --frames
+    let mut camera_path: Option<CameraTracks> = None;
+    if let Some(path) = args.iter().position(|a| a == "--camera-path").and_then(|i| args.get(i + 1)) {
+        match load_camera_path(path) {
+            Ok(tracks) if !tracks.is_empty() => {
+                println!("Recorrido de cámara cargado desde {} ({:.1}s)", path, tracks.duration());
+                scene.timeline.camera = tracks.clone();
+                camera_path = Some(tracks);
+            }
+            Ok(_) => println!("{} no contiene fotogramas 'key camera'", path),
+            Err(e) => println!("Error cargando el recorrido {}: {}", path, e),
+        }
+    }
+    
+    // Estéreo para visores VR: --stereo sbs|ou [--ipd M] [--convergence D]
+    if let Some(layout) = args.iter().position(|a| a == "--stereo").and_then(|i| args.get(i + 1)) {
+        let arg_number = |name: &str| args.iter().position(|a| a == name)
+            .and_then(|i| args.get(i + 1))
+            .and_then(|v| v.parse::<f32>().ok());
+        match layout.parse::<StereoLayout>() {
+            Ok(layout) => {
+                let mut rig = StereoRig::new(layout);
+                if let Some(ipd) = arg_number("--ipd") {
+                    rig = rig.with_interpupillary_distance(ipd);
+                }
+                if let Some(convergence) = arg_number("--convergence") {
+                    rig = rig.with_convergence(convergence);
+                }
+                println!("Estéreo: {:?} (IPD {})", rig.layout, rig.interpupillary_distance);
+                raytracer.stereo = Some(rig);
+            }
+            Err(e) => println!("{} (se renderiza sin estéreo)", e),
+        }
+    }
+
+    // Renderizado por tiles: --tile-order spiral|hilbert|scanline [--tile-size N]
+    if let Some(order) = args.iter().position(|a| a == "--tile-order").and_then(|i| args.get(i + 1)) {
+        match order.parse::<TileOrder>() {
+            Ok(order) => raytracer.tile_order = order,
+            Err(e) => println!("{} (se mantiene {:?})", e, raytracer.tile_order),
+        }
+    }
+    if let Some(size) = args.iter().position(|a| a == "--tile-size")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|v| v.parse::<u32>().ok())
+    {
+        raytracer.tile_size = size.max(1);
+    }
+
+    // Secuencia sin ventana: --frames INICIO FIN [--fps N] [--output carpeta] [--motion-blur]
+    if let Some(i) = args.iter().position(|a| a == "--frames") {
+        let frame_arg = |offset: usize| args.get(i + offset).and_then(|v| v.parse::<u32>().ok()).unwrap_or(0);
+        let fps = args.iter().position(|a| a == "--fps")
+            .and_then(|i| args.get(i + 1))
+            .and_then(|v| v.parse::<f32>().ok())
+            .unwrap_or(24.0);
+        let output = args.iter().position(|a| a == "--output").and_then(|i| args.get(i + 1));
+        let mut sequence = SequenceConfig::new(frame_arg(1), frame_arg(2))
+            .with_fps(fps)
+            .with_output_dir(output.map_or("frames", |o| o.as_str()));
+        
+        raytracer.set_quality(RenderQuality::High);
+        if custom_resolution.is_none() {
+            raytracer.set_resolution(display_width as u32, display_height as u32); // 100% como la calidad alta
+        }
+        if args.iter().any(|a| a == "--motion-blur") {
+            sequence = sequence.with_shutter(0.5); // Obturador de 180°
+            raytracer.samples_per_pixel = 8;
+        }
+        
+        // Sin animación en la escena: una vuelta de cámara durante toda la secuencia
+        if scene.timeline.is_empty() {
+            let duration = sequence.frame_time(sequence.end + 1);
+            scene.timeline = Timeline::orbit(camera.target, camera.position, duration);
+        }
+        
+        match render_sequence(&raytracer, &mut scene, &mut camera, &sequence) {
+            Ok(frames) => println!("Secuencia completa: {} fotogramas en {}", frames.len(), sequence.output_dir.display()),
+            Err(e) => println!("Error renderizando la secuencia: {}", e),
+        }
+        return;
+    }
+
+    // Captura de alta resolución (tecla H): --capture-size ANCHOxALTO [--capture-samples N]
+    let capture_size = args.iter().position(|a| a == "--capture-size")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|v| v.split_once('x'))
+        .and_then(|(w, h)| Some((w.parse::<u32>().ok()?, h.parse::<u32>().ok()?)))
+        .unwrap_or((1600, 1200));
+    let capture_samples = args.iter().position(|a| a == "--capture-samples")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|v| v.parse::<u32>().ok())
+        .unwrap_or(16);
+
+    // Inicializar ventana redimensionable
+    let (mut rl, thread) = raylib::init()
+        .size(display_width, display_height)
+        .resizable()
+        .title("Raytracer Ultra-Optimizado - Rotación Auto")
+        .build();
+    let mut window_size = (display_width, display_height);
 
     // Variables de control mejoradas
     let mut needs_rerender = true;
-    let mut image_buffer = vec![vec![Color::BLACK; display_width as usize]; display_height as usize];
+    // Textura en GPU con la última imagen; se escala a la ventana en una sola llamada
+    let mut display_texture: Option<Texture2D> = None;
+    let mut linear_filter = false;
     let mouse_sensitivity = 0.003; // Más suave
     let zoom_speed = 1.5;
     
@@ -75,6 +243,30 @@ fn main() {
     let mut manual_control = false; // Si el usuario está controlando manualmente
     let mut last_auto_rotation_time = 0.0; 
     let mut rotation_counter = 0;
+    
+    // Reloj de la escena (olas del agua y obturador del motion blur)
+    let mut scene_time: f32 = 0.0;
+    let mut last_wave_update = 0.0;
+    let mut animate_scene = true;
+    let mut motion_blur = false;
+    
+    // Marcadores (Ctrl + 4..9 guarda, 4..9 restaura) y grabación/reproducción del recorrido
+    let mut bookmarks = CameraBookmarks::new();
+    let mut recorder: Option<CameraRecorder> = None;
+    let mut replay_time: Option<f32> = None;
+    
+    // Render de alta resolución en curso (tecla H)
+    let mut background_render: Option<BackgroundRender> = None;
+    
+    // Calidad adaptativa: mientras la cámara se mueve se ajustan resolución, rebotes y
+    // muestras al presupuesto de tiempo; al detenerse se vuelve a la calidad completa
+    let mut adaptive = AdaptiveController::new(config.frame_budget);
+    let mut still_time: f32 = 0.0;
+    let mut degraded_frame = false;
+    
+    // Modo de vuelo libre (primera persona)
+    let mut fly_mode = false;
+    let mut fly_speed: f32 = 1.0;
 
     // Framebuffer dinámico
     let mut framebuffer = Framebuffer::new(render_width, render_height);
@@ -86,9 +278,67 @@ fn main() {
         
         // Control manual vs automático
         let mut camera_changed = false;
+        let mut camera_moved = false;
         
-        // Controles manuales (desactivan temporalmente la rotación automática)
-        if rl.is_mouse_button_down(MouseButton::MOUSE_BUTTON_LEFT) {
+        // Al redimensionar la ventana la resolución y el aspecto siguen al nuevo tamaño
+        if rl.is_window_resized() {
+            window_size = (rl.get_screen_width(), rl.get_screen_height());
+            apply_resolution(config.resolution, window_size, &mut raytracer, &mut framebuffer, &mut camera);
+            camera_changed = true;
+        }
+        
+        // Modo vuelo libre: WASD/QE mueven, arrastrar con el mouse mira alrededor, Z/C alabeo
+        if fly_mode {
+            let boost = if rl.is_key_down(KeyboardKey::KEY_LEFT_SHIFT) { 4.0 } else { 1.0 };
+            let mut moved = false;
+            
+            // Movimiento en ejes locales (adelante, derecha, arriba)
+            let mut movement = Vec3::zero();
+            if rl.is_key_down(KeyboardKey::KEY_W) || rl.is_key_down(KeyboardKey::KEY_UP) { movement.x += 1.0; }
+            if rl.is_key_down(KeyboardKey::KEY_S) || rl.is_key_down(KeyboardKey::KEY_DOWN) { movement.x -= 1.0; }
+            if rl.is_key_down(KeyboardKey::KEY_D) || rl.is_key_down(KeyboardKey::KEY_RIGHT) { movement.y += 1.0; }
+            if rl.is_key_down(KeyboardKey::KEY_A) || rl.is_key_down(KeyboardKey::KEY_LEFT) { movement.y -= 1.0; }
+            if rl.is_key_down(KeyboardKey::KEY_E) { movement.z += 1.0; }
+            if rl.is_key_down(KeyboardKey::KEY_Q) { movement.z -= 1.0; }
+            if movement.length_squared() > 0.0 {
+                let movement = movement.normalize() * fly_speed * boost * frame_time;
+                camera.fly(movement.x, movement.y, movement.z);
+                moved = true;
+            }
+            
+            if rl.is_mouse_button_down(MouseButton::MOUSE_BUTTON_LEFT) {
+                let mouse_delta = rl.get_mouse_delta();
+                if mouse_delta.x.abs() > 0.2 || mouse_delta.y.abs() > 0.2 {
+                    camera.look(mouse_delta.x, -mouse_delta.y, mouse_sensitivity);
+                    moved = true;
+                }
+            }
+            
+            // Alabeo (Z/C) y nivelado del horizonte (X)
+            let mut roll = 0.0;
+            if rl.is_key_down(KeyboardKey::KEY_Z) { roll -= 1.0; }
+            if rl.is_key_down(KeyboardKey::KEY_C) { roll += 1.0; }
+            if roll != 0.0 {
+                camera.roll(roll * frame_time);
+                moved = true;
+            }
+            if rl.is_key_pressed(KeyboardKey::KEY_X) {
+                camera.reset_roll();
+                moved = true;
+            }
+            
+            // La rueda ajusta la velocidad de vuelo
+            let wheel = rl.get_mouse_wheel_move();
+            if wheel.abs() > 0.05 {
+                fly_speed = (fly_speed * 1.25f32.powf(wheel)).clamp(0.05, 50.0);
+                println!("Velocidad de vuelo: {:.2}", fly_speed);
+            }
+            
+            camera_changed |= moved;
+            manual_control = moved;
+        } else {
+            // Controles manuales (desactivan temporalmente la rotación automática)
+            if rl.is_mouse_button_down(MouseButton::MOUSE_BUTTON_LEFT) {
                 let mouse_delta = rl.get_mouse_delta();
                 if mouse_delta.x.abs() > 0.2 || mouse_delta.y.abs() > 0.2 {
                     camera.rotate_around_target(mouse_delta.x, -mouse_delta.y, mouse_sensitivity);
@@ -120,6 +370,7 @@ fn main() {
             if zoom_delta.abs() > 0.05 {
                 camera.zoom(zoom_delta, zoom_speed * frame_time);
                 camera_changed = true;
+                camera_moved = true;
             }
 
             // Rotación automática más conservadora (solo si no hay control manual)
@@ -129,6 +380,7 @@ fn main() {
                     let rotation_amount = config.rotation_speed * 0.5; // Rotación más lenta
                     camera.rotate_around_target(rotation_amount, 0.0, 1.0);
                     camera_changed = true;
+                    camera_moved = true;
                     last_auto_rotation_time = auto_time;
                     rotation_counter += 1;
                     
@@ -141,14 +393,140 @@ fn main() {
                     camera_change_timer = camera_debounce_time;
                 }
             }
+        }
+        
+            // Toggle modo vuelo libre (al salir se nivela el horizonte y se vuelve a orbitar)
+            if rl.is_key_pressed(KeyboardKey::KEY_F) {
+                fly_mode = !fly_mode;
+                if !fly_mode {
+                    camera.reset_roll();
+                }
+                manual_control = false;
+                camera_changed = true;
+                println!("Modo vuelo: {}", if fly_mode { "ON" } else { "OFF" });
+            }
+            
+            // Marcadores de cámara en las teclas 4 a 9 (1-3 cambian la calidad)
+            let bookmark_keys = [
+                (4, KeyboardKey::KEY_FOUR),
+                (5, KeyboardKey::KEY_FIVE),
+                (6, KeyboardKey::KEY_SIX),
+                (7, KeyboardKey::KEY_SEVEN),
+                (8, KeyboardKey::KEY_EIGHT),
+                (9, KeyboardKey::KEY_NINE),
+            ];
+            for (slot, key) in bookmark_keys {
+                if !rl.is_key_pressed(key) {
+                    continue;
+                }
+                if rl.is_key_down(KeyboardKey::KEY_LEFT_CONTROL) {
+                    bookmarks.store(slot, &camera);
+                    println!("Marcador {} guardado", slot);
+                } else if bookmarks.recall(slot, &mut camera) {
+                    camera_changed = true;
+                    println!("Marcador {} restaurado", slot);
+                } else {
+                    println!("Marcador {} vacío (Ctrl + {} para guardarlo)", slot, slot);
+                }
+            }
+            
+            // Grabar el recorrido de la cámara (al detenerse se guarda en camera_path.txt)
+            if rl.is_key_pressed(KeyboardKey::KEY_G) {
+                match recorder.take() {
+                    Some(active) => {
+                        let tracks = active.finish(&camera);
+                        match save_camera_path(&tracks, "camera_path.txt") {
+                            Ok(()) => println!("Recorrido de {:.1}s guardado en camera_path.txt", tracks.duration()),
+                            Err(e) => println!("Error guardando el recorrido: {}", e),
+                        }
+                        camera_path = Some(tracks);
+                    }
+                    None => {
+                        recorder = Some(CameraRecorder::new());
+                        replay_time = None;
+                        println!("Grabando recorrido de cámara...");
+                    }
+                }
+            }
+            if let Some(active) = recorder.as_mut() {
+                active.record(frame_time, &camera);
+            }
+            
+            // Reproducir el último recorrido grabado o cargado
+            if rl.is_key_pressed(KeyboardKey::KEY_V) && recorder.is_none() {
+                replay_time = match (&camera_path, replay_time) {
+                    (Some(_), None) => Some(0.0),
+                    _ => None,
+                };
+                println!("Reproducción del recorrido: {}", if replay_time.is_some() { "ON" } else { "OFF" });
+            }
+            if let (Some(tracks), Some(time)) = (&camera_path, replay_time) {
+                tracks.apply(&mut camera, time);
+                camera_changed = true;
+                let next_time = time + frame_time;
+                replay_time = (next_time <= tracks.duration()).then_some(next_time);
+            }
+            
+            // Captura de la imagen actual (resolución de render) con marca de tiempo
+            if rl.is_key_pressed(KeyboardKey::KEY_P) {
+                let path = format!("captura_{}.png", timestamp());
+                match framebuffer.save(&path) {
+                    Ok(()) => println!("Captura guardada en {}", path),
+                    Err(e) => println!("Error guardando la captura: {}", e),
+                }
+            }
+            
+            // Render de alta calidad de la cámara actual en segundo plano
+            if rl.is_key_pressed(KeyboardKey::KEY_H) && background_render.is_none() {
+                let (width, height) = capture_size;
+                let mut capture = raytracer.clone();
+                capture.set_quality(RenderQuality::High);
+                capture.width = width;
+                capture.height = height;
+                capture.samples_per_pixel = capture_samples;
+                
+                let mut capture_camera = camera.clone();
+                capture_camera.set_aspect_ratio(width as f32 / height as f32);
+                capture_camera.set_shutter(scene_time, scene_time + if motion_blur { 0.5 } else { 0.0 });
+                println!("Render de alta resolución {}x{} con {} muestras por pixel...", width, height, capture_samples);
+                background_render = Some(BackgroundRender::start(capture, scene.clone(), capture_camera));
+            }
+            if background_render.as_ref().is_some_and(|job| job.is_finished()) {
+                let job = background_render.take().unwrap();
+                let path = format!("render_{}.png", timestamp());
+                match job.finish().map(|image| image.save(&path)) {
+                    Some(Ok(())) => println!("Render de alta resolución guardado en {}", path),
+                    Some(Err(e)) => println!("Error guardando el render: {}", e),
+                    None => println!("El render de alta resolución falló"),
+                }
+            }
+            
+            // Escalado de la imagen a la ventana: nearest (pixels nítidos) o lineal
+            if rl.is_key_pressed(KeyboardKey::KEY_N) {
+                linear_filter = !linear_filter;
+                if let Some(texture) = &display_texture {
+                    texture.set_texture_filter(&thread, texture_filter(linear_filter));
+                }
+                println!("Escalado: {}", if linear_filter { "lineal" } else { "nearest" });
+            }
+            
+            // Campo de visión (+/-), en ambos modos
+            let mut fov_delta = 0.0;
+            if rl.is_key_down(KeyboardKey::KEY_EQUAL) { fov_delta -= 1.0; }
+            if rl.is_key_down(KeyboardKey::KEY_MINUS) { fov_delta += 1.0; }
+            if fov_delta != 0.0 {
+                camera.set_fov(camera.fov + fov_delta * 30.0 * frame_time);
+                camera_changed = true;
+                manual_control = true;
+            }
 
             // Cambios de calidad dinámicos
             if rl.is_key_pressed(KeyboardKey::KEY_ONE) {
                 current_quality = RenderQuality::Low;
                 raytracer.set_quality(current_quality);
                 config.quality = current_quality;
-                let (new_w, new_h) = current_quality.dimensions();
-                framebuffer = Framebuffer::new(new_w, new_h);
+                config.resolution = RenderResolution::Scale(current_quality.render_scale());
+                apply_resolution(config.resolution, window_size, &mut raytracer, &mut framebuffer, &mut camera);
                 camera_changed = true;
                 println!("Calidad: {}", current_quality.description());
             }
@@ -156,8 +534,8 @@ fn main() {
                 current_quality = RenderQuality::Medium;
                 raytracer.set_quality(current_quality);
                 config.quality = current_quality;
-                let (new_w, new_h) = current_quality.dimensions();
-                framebuffer = Framebuffer::new(new_w, new_h);
+                config.resolution = RenderResolution::Scale(current_quality.render_scale());
+                apply_resolution(config.resolution, window_size, &mut raytracer, &mut framebuffer, &mut camera);
                 camera_changed = true;
                 println!("Calidad: {}", current_quality.description());
             }
@@ -165,17 +543,102 @@ fn main() {
                 current_quality = RenderQuality::High;
                 raytracer.set_quality(current_quality);
                 config.quality = current_quality;
-                let (new_w, new_h) = current_quality.dimensions();
-                framebuffer = Framebuffer::new(new_w, new_h);
+                config.resolution = RenderResolution::Scale(current_quality.render_scale());
+                apply_resolution(config.resolution, window_size, &mut raytracer, &mut framebuffer, &mut camera);
                 camera_changed = true;
                 println!("Calidad: {}", current_quality.description());
             }
             
+            // Escala de render respecto a la ventana ([ / ]) en pasos de 25%
+            let scale_step = if rl.is_key_pressed(KeyboardKey::KEY_RIGHT_BRACKET) {
+                25
+            } else if rl.is_key_pressed(KeyboardKey::KEY_LEFT_BRACKET) {
+                -25
+            } else {
+                0
+            };
+            if scale_step != 0 {
+                let current_scale = match config.resolution {
+                    RenderResolution::Scale(percent) => percent as i32,
+                    RenderResolution::Fixed(width, _) => (width * 100 / window_size.0.max(1) as u32) as i32,
+                };
+                config.resolution = RenderResolution::scale((current_scale + scale_step).max(0) as u32);
+                apply_resolution(config.resolution, window_size, &mut raytracer, &mut framebuffer, &mut camera);
+                camera_changed = true;
+                println!("Resolución de render: {} ({}x{})", config.resolution.description(), raytracer.width, raytracer.height);
+            }
+            
             // Toggle rotación automática
             if rl.is_key_pressed(KeyboardKey::KEY_R) {
                 config.enable_auto_rotation = !config.enable_auto_rotation;
                 println!("Rotación automática: {}", if config.enable_auto_rotation { "ON" } else { "OFF" });
             }
+            
+            // Toggle modo espectral (dispersión en materiales transparentes)
+            if rl.is_key_pressed(KeyboardKey::KEY_M) {
+                raytracer.render_mode = match raytracer.render_mode {
+                    RenderMode::Rgb => RenderMode::Spectral { wavelengths: 8 },
+                    RenderMode::Spectral { .. } => RenderMode::Rgb,
+                };
+                camera_changed = true;
+                println!("Modo de renderizado: {:?}", raytracer.render_mode);
+            }
+            
+            // Ciclar la estrategia de muestreo de luces
+            if rl.is_key_pressed(KeyboardKey::KEY_L) {
+                scene.light_sampling = match scene.light_sampling {
+                    LightSampling::All => LightSampling::PowerProportional { samples: 8 },
+                    LightSampling::PowerProportional { .. } => LightSampling::Reservoir { candidates: 16 },
+                    LightSampling::Reservoir { .. } => LightSampling::All,
+                };
+                camera_changed = true;
+                println!("Muestreo de luces: {:?}", scene.light_sampling);
+            }
+
+            // Toggle motion blur (obturador abierto medio segundo con varias muestras por pixel)
+            if rl.is_key_pressed(KeyboardKey::KEY_B) {
+                motion_blur = !motion_blur;
+                raytracer.samples_per_pixel = if motion_blur { 8 } else { 1 };
+                camera_changed = true;
+                println!("Motion blur: {}", if motion_blur { "ON" } else { "OFF" });
+            }
+            
+            // Toggle de la calidad adaptativa al presupuesto de tiempo por fotograma
+            if rl.is_key_pressed(KeyboardKey::KEY_T) {
+                adaptive.enabled = !adaptive.enabled;
+                camera_changed = true;
+                println!("Calidad adaptativa ({:.0} ms): {}", adaptive.budget * 1000.0, if adaptive.enabled { "ON" } else { "OFF" });
+            }
+            
+            // Toggle animación de la escena (olas del jacuzzi)
+            if rl.is_key_pressed(KeyboardKey::KEY_O) {
+                animate_scene = !animate_scene;
+                println!("Animación de la escena: {}", if animate_scene { "ON" } else { "OFF" });
+            }
+            
+            // Las olas avanzan con el tiempo real; se re-renderiza cada 500ms como la rotación
+            if animate_scene {
+                scene_time += frame_time;
+                if !manual_control && scene_time - last_wave_update > 0.5 {
+                    last_wave_update = scene_time;
+                    // Las cáusticas siguen a las olas
+                    if scene.has_time_dependent_photons() {
+                        scene.rebuild_photon_map(scene_time);
+                    }
+                    camera_changed = true;
+                }
+            }
+        
+        // La cámara sigue en movimiento hasta 0.25s después del último cambio real de vista
+        if manual_control || camera_moved || replay_time.is_some() {
+            still_time = 0.0;
+        } else {
+            still_time += frame_time;
+        }
+        let camera_moving = still_time < 0.25;
+        if !camera_moving && degraded_frame {
+            needs_rerender = true;
+        }
         
         // Sistema de debounce optimizado (más agresivo para rotación automática)
         if camera_changed {
@@ -190,6 +653,15 @@ fn main() {
         
         // Renderizado ultra-optimizado con escalado dinámico
         if needs_rerender || (camera_change_timer <= 0.0 && camera_change_timer > -0.1) {
+            // Parámetros del fotograma: completos en reposo, ajustados al presupuesto en movimiento
+            let full_settings = FrameSettings {
+                resolution_factor: 1.0,
+                max_depth: current_quality.max_depth(),
+                samples: if motion_blur { 8 } else { 1 },
+            };
+            let settings = adaptive.settings(full_settings, camera_moving);
+            apply_frame_settings(settings, config.resolution, window_size, &mut raytracer, &mut framebuffer);
+            
             // Menos verbose para rotación automática
             if manual_control || needs_rerender {
                 println!("\nIniciando renderizado paralelo {} ({}x{})...", 
@@ -197,10 +669,15 @@ fn main() {
             }
             
             // Renderizado directo al framebuffer apropiado
-            raytracer.render_to_framebuffer(&scene, &camera, &mut framebuffer);
+            camera.set_shutter(scene_time, scene_time + if motion_blur { 0.5 } else { 0.0 });
+            let trace_time = render_live(&mut rl, &thread, &raytracer, &scene, &camera, &mut framebuffer, &mut display_texture, linear_filter);
+            if camera_moving {
+                adaptive.record(trace_time, full_settings);
+            }
+            degraded_frame = settings != full_settings;
             
-            // Convertir y escalar al tamaño de ventana
-            image_buffer = scale_framebuffer_to_window(&framebuffer, display_width as u32, display_height as u32);
+            // Subir la imagen a la textura de la ventana
+            upload_framebuffer(&mut rl, &thread, &mut display_texture, &framebuffer, linear_filter);
             
             needs_rerender = false;
             camera_change_timer = -1.0;
@@ -210,12 +687,9 @@ fn main() {
         let mut d = rl.begin_drawing(&thread);
         d.clear_background(Color::new(15, 15, 25, 255)); // Fondo más elegante
 
-        // Dibujar imagen escalada
-        for y in 0..display_height {
-            for x in 0..display_width {
-                let pixel = image_buffer[y as usize][x as usize];
-                d.draw_pixel(x, y, pixel);
-            }
+        // Dibujar imagen escalada (el filtro de la textura decide nearest o lineal)
+        if let Some(texture) = &display_texture {
+            draw_display_texture(&mut d, texture);
         }
         
         // UI Ultra-mejorada con información completa
@@ -224,7 +698,23 @@ fn main() {
         } else if camera_change_timer > -0.1 {
             draw_ready_ui(&mut d);
         } else {
-            draw_controls_ui(&mut d, &camera, &num_threads, &current_quality, &config, manual_control);
+            draw_controls_ui(&mut d, &camera, &num_threads, &current_quality, &config, manual_control, fly_mode);
+        }
+        
+        // Progreso del render de alta resolución
+        if let Some(job) = &background_render {
+            let width = d.get_screen_width();
+            let bar_width = ((width - 20) as f32 * job.progress()) as i32;
+            d.draw_rectangle(0, 40, width, 30, Color::new(0, 0, 0, 150));
+            d.draw_rectangle(10, 60, bar_width, 6, Color::SKYBLUE);
+            d.draw_text(&format!("Render alta resolución {:.0}%", job.progress() * 100.0), 10, 44, 14, Color::WHITE);
+        }
+        
+        // Indicador de grabación del recorrido
+        if let Some(active) = &recorder {
+            let x = d.get_screen_width() - 130;
+            d.draw_rectangle(x - 10, 0, 140, 30, Color::new(0, 0, 0, 150));
+            d.draw_text(&format!("REC {:.1}s", active.elapsed()), x, 8, 16, Color::RED);
         }
     }
 }
@@ -242,14 +732,19 @@ fn draw_ready_ui(d: &mut RaylibDrawHandle) {
     d.draw_text("Listo!", 10, 8, 14, Color::LIME);
 }
 
-fn draw_controls_ui(d: &mut RaylibDrawHandle, _camera: &Camera, _num_threads: &usize, _quality: &RenderQuality, config: &AdaptiveConfig, _manual: bool) {
+fn draw_controls_ui(d: &mut RaylibDrawHandle, camera: &Camera, _num_threads: &usize, _quality: &RenderQuality, config: &AdaptiveConfig, _manual: bool, fly_mode: bool) {
     let screen_height = d.get_screen_height();
     
     // UI súper simplificada - solo lo esencial en una línea
     d.draw_rectangle(0, screen_height - 50, d.get_screen_width(), 50, Color::new(0, 0, 0, 120));
     
     // Solo controles básicos
-    d.draw_text("W/S para zoom | Scroll para zoom", 10, screen_height - 40, 14, Color::WHITE);
+    if fly_mode {
+        d.draw_text("WASD/QE mover | Mouse mirar | Z/C alabeo | Scroll velocidad", 10, screen_height - 40, 14, Color::WHITE);
+        d.draw_text(&format!("MODO VUELO (F para orbitar) | FOV {:.0}°", camera.fov), 10, screen_height - 22, 16, Color::SKYBLUE);
+        return;
+    }
+    d.draw_text("W/S para zoom | Scroll para zoom | F modo vuelo", 10, screen_height - 40, 14, Color::WHITE);
     
     // Estado de rotación automática (lo más importante)
     let rotation_status = if config.enable_auto_rotation { 
@@ -262,31 +757,123 @@ fn draw_controls_ui(d: &mut RaylibDrawHandle, _camera: &Camera, _num_threads: &u
     d.draw_text(rotation_status, 10, screen_height - 22, 16, rotation_color);
 }
 
-// Función para escalar framebuffer al tamaño de ventana
-fn scale_framebuffer_to_window(framebuffer: &Framebuffer, target_width: u32, target_height: u32) -> Vec<Vec<raylib::prelude::Color>> {
-    let fb_buffer = framebuffer.to_raylib_buffer();
-    let fb_height = fb_buffer.len();
-    let fb_width = if fb_height > 0 { fb_buffer[0].len() } else { 0 };
+// Marca de tiempo UTC para nombres de archivo (AAAAMMDD_HHMMSS)
+fn timestamp() -> String {
+    let seconds = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs()) as i64;
+    let (days, time) = (seconds.div_euclid(86400), seconds.rem_euclid(86400));
     
-    let mut scaled_buffer = Vec::with_capacity(target_height as usize);
+    // Días desde 1970 a fecha civil (algoritmo de Howard Hinnant)
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
     
-    for y in 0..target_height {
-        let mut row = Vec::with_capacity(target_width as usize);
-        for x in 0..target_width {
-            // Mapeo bilinear simple
-            let fb_x = ((x as f32 / target_width as f32) * fb_width as f32) as usize;
-            let fb_y = ((y as f32 / target_height as f32) * fb_height as f32) as usize;
-            
-            let fb_x = fb_x.min(fb_width.saturating_sub(1));
-            let fb_y = fb_y.min(fb_height.saturating_sub(1));
-            
-            let pixel = fb_buffer[fb_y][fb_x];
-            row.push(pixel);
+    format!("{:04}{:02}{:02}_{:02}{:02}{:02}", year, month, day, time / 3600, time / 60 % 60, time % 60)
+}
+
+// Ajusta raytracer, framebuffer y aspecto de la cámara a la resolución de render
+fn apply_resolution(resolution: RenderResolution, window_size: (i32, i32), raytracer: &mut Raytracer, framebuffer: &mut Framebuffer, camera: &mut Camera) {
+    let (width, height) = resolution.dimensions(window_size.0.max(1) as u32, window_size.1.max(1) as u32);
+    raytracer.set_resolution(width, height);
+    if framebuffer.width != width || framebuffer.height != height {
+        *framebuffer = Framebuffer::new(width, height);
+    }
+    camera.set_aspect_ratio(width as f32 / height as f32);
+}
+
+// Reduce la resolución completa según el controlador adaptativo y fija rebotes y muestras
+fn apply_frame_settings(settings: FrameSettings, resolution: RenderResolution, window_size: (i32, i32), raytracer: &mut Raytracer, framebuffer: &mut Framebuffer) {
+    let (width, height) = resolution.dimensions(window_size.0.max(1) as u32, window_size.1.max(1) as u32);
+    let width = ((width as f32 * settings.resolution_factor) as u32).max(1);
+    let height = ((height as f32 * settings.resolution_factor) as u32).max(1);
+    raytracer.set_resolution(width, height);
+    if framebuffer.width != width || framebuffer.height != height {
+        *framebuffer = Framebuffer::new(width, height);
+    }
+    raytracer.max_depth = settings.max_depth;
+    raytracer.samples_per_pixel = settings.samples;
+}
+
+// Renderiza por tiles mostrando en la ventana los que van terminando (unas 30 veces por
+// segundo); los tiles pendientes conservan la imagen anterior. Devuelve los segundos de
+// trazado, sin contar el tiempo de subir y dibujar la textura
+#[allow(clippy::too_many_arguments)]
+fn render_live(rl: &mut RaylibHandle, thread: &RaylibThread, raytracer: &Raytracer, scene: &Scene, camera: &Camera, framebuffer: &mut Framebuffer, texture: &mut Option<Texture2D>, linear_filter: bool) -> f32 {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    std::thread::scope(|scope| {
+        let tracing = scope.spawn(move || {
+            let start = std::time::Instant::now();
+            raytracer.render_tiles(scene, camera, sender);
+            start.elapsed().as_secs_f32()
+        });
+        
+        let mut last_display = std::time::Instant::now();
+        for result in receiver {
+            result.write_to(framebuffer);
+            if last_display.elapsed().as_secs_f32() > 1.0 / 30.0 {
+                upload_framebuffer(rl, thread, texture, framebuffer, linear_filter);
+                let mut d = rl.begin_drawing(thread);
+                d.clear_background(Color::new(15, 15, 25, 255));
+                if let Some(texture) = texture.as_ref() {
+                    draw_display_texture(&mut d, texture);
+                }
+                last_display = std::time::Instant::now();
+            }
         }
-        scaled_buffer.push(row);
+        tracing.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+    })
+}
+
+// Dibuja la textura ajustada a la ventana conservando el aspecto (bandas si la resolución es fija)
+fn draw_display_texture(d: &mut RaylibDrawHandle, texture: &Texture2D) {
+    let (window_width, window_height) = (d.get_screen_width() as f32, d.get_screen_height() as f32);
+    let (texture_width, texture_height) = (texture.width as f32, texture.height as f32);
+    let fit = (window_width / texture_width).min(window_height / texture_height);
+    let (width, height) = (texture_width * fit, texture_height * fit);
+    let source = Rectangle::new(0.0, 0.0, texture_width, texture_height);
+    let destination = Rectangle::new((window_width - width) / 2.0, (window_height - height) / 2.0, width, height);
+    d.draw_texture_pro(texture, source, destination, Vector2::zero(), 0.0, Color::WHITE);
+}
+
+// Copia el framebuffer a la textura de la ventana (se recrea si cambió la resolución)
+fn upload_framebuffer(rl: &mut RaylibHandle, thread: &RaylibThread, texture: &mut Option<Texture2D>, framebuffer: &Framebuffer, linear_filter: bool) {
+    let size_changed = texture.as_ref().is_none_or(|t| {
+        t.width != framebuffer.width as i32 || t.height != framebuffer.height as i32
+    });
+    if size_changed {
+        let image = Image::gen_image_color(framebuffer.width as i32, framebuffer.height as i32, Color::BLACK);
+        *texture = match rl.load_texture_from_image(thread, &image) {
+            Ok(new_texture) => {
+                new_texture.set_texture_filter(thread, texture_filter(linear_filter));
+                Some(new_texture)
+            }
+            Err(e) => {
+                println!("Error creando la textura de la ventana: {}", e);
+                None
+            }
+        };
     }
     
-    scaled_buffer
+    if let Some(texture) = texture.as_mut()
+        && let Err(e) = texture.update_texture(&framebuffer.to_rgba_bytes())
+    {
+        println!("Error actualizando la textura: {}", e);
+    }
+}
+
+fn texture_filter(linear: bool) -> TextureFilter {
+    if linear {
+        TextureFilter::TEXTURE_FILTER_BILINEAR
+    } else {
+        TextureFilter::TEXTURE_FILTER_POINT
+    }
 }
 
 // Crea una escena ultra-optimizada estilo jacuzzi spa
@@ -305,7 +892,8 @@ fn create_optimized_scene() -> Scene {
         .with_roughness(0.1)
         .with_reflectivity(0.3)      
         .with_transparency(0.6)      // Semi-transparente para ver profundidad
-        .with_refractive_index(1.33); // Índice del agua real
+        .with_refractive_index(1.33)  // Índice del agua real
+        .with_dispersion(Dispersion::water()); // Dispersión visible en modo espectral
     
     // MADERA DE SPA (Deck del jacuzzi)
     let madera_texture = match Texture::from_file("assets/img/wood.jpg") {
@@ -354,34 +942,33 @@ fn create_optimized_scene() -> Scene {
         .with_roughness(0.05)
         .with_reflectivity(0.75);
 
+    // LÁMPARA EMISIVA (Se muestrea como luz de área)
+    let lampara_material = Material::new()
+        .with_color(Vec3::new(1.0, 0.85, 0.6))
+        .with_specular(0.0)
+        .with_emission(Vec3::new(1.6, 1.2, 0.7)); // Brillo cálido
+
+    // CRISTAL DECORATIVO (Dispersión tipo prisma en modo espectral)
+    let cristal_material = Material::new()
+        .with_color(Vec3::new(0.95, 0.95, 1.0))
+        .with_specular(0.9)
+        .with_roughness(0.02)
+        .with_reflectivity(0.1)
+        .with_transparency(0.9)
+        .with_dispersion(Dispersion::dense_flint());
+
     // === CONSTRUCCIÓN DE JACUZZI COMPACTO REAL ===
     
     // Cubos SÚPER PEQUEÑOS para ultra-performance y realismo
     let mini_cube = Vec3::new(0.3, 0.3, 0.3);     // Extra pequeños
     let small_cube = Vec3::new(0.4, 0.4, 0.4);    // Pequeños
     
-    // JACUZZI CENTRAL - 4 cubos de AGUA AZUL PEGADOS (2x2)
-    // ¡COMPLETAMENTE PEGADOS SIN ESPACIO!
-    scene.add_cube(Cube::new(
-        Vec3::new(-0.15, 0.1, -0.15), // PEGADOS - separación de solo 0.3 
-        mini_cube,
-        agua_material.clone(),
-    ));
-    scene.add_cube(Cube::new(
-        Vec3::new(0.15, 0.1, -0.15),  // PEGADOS completamente
-        mini_cube,
-        agua_material.clone(),
-    ));
-    scene.add_cube(Cube::new(
-        Vec3::new(-0.15, 0.1, 0.15),  // PEGADOS completamente
-        mini_cube,
-        agua_material.clone(),
-    ));
-    scene.add_cube(Cube::new(
-        Vec3::new(0.15, 0.1, 0.15),   // PEGADOS completamente
-        mini_cube,
-        agua_material,
-    ));
+    // JACUZZI CENTRAL - bloque de AGUA AZUL de 2x2 cubos con olas suaves
+    // (las olas de Gerstner solo ondulan la normal de la cara superior)
+    scene.add_object(
+        WaterSurface::new(Vec3::new(0.0, 0.1, 0.0), mini_cube * Vec3::new(2.0, 1.0, 2.0), agua_material)
+            .with_waves(3, 0.004, 5.0, 0.12),
+    );
 
     // DECK DE MADERA COMPACTO (Marco perfecto alrededor)
     let deck_y = -0.1; // Justo debajo del agua
@@ -425,12 +1012,26 @@ fn create_optimized_scene() -> Scene {
     scene.add_cube(Cube::new(Vec3::new(-1.2, 0.1, 1.2), mini_cube, ladrillo_material.clone()));
     scene.add_cube(Cube::new(Vec3::new(1.2, 0.1, 1.2), mini_cube, ladrillo_material));
     
+    // Lámparas sobre las torres delanteras
+    scene.add_cube(Cube::new(Vec3::new(-1.2, 0.32, 1.2), Vec3::new(0.14, 0.14, 0.14), lampara_material.clone()));
+    scene.add_cube(Cube::new(Vec3::new(1.2, 0.32, 1.2), Vec3::new(0.14, 0.14, 0.14), lampara_material));
+    
     // Rocas decorativas pequeñas
     scene.add_cube(Cube::new(Vec3::new(-1.0, -0.2, 0.0), mini_cube, piedra_material.clone()));
     scene.add_cube(Cube::new(Vec3::new(1.0, -0.2, 0.0), mini_cube, piedra_material));
     
     // Accesorio metálico pequeño (como grifo o lámpara)
     scene.add_cube(Cube::new(Vec3::new(0.0, 0.3, -0.8), mini_cube, metal_material));
+    
+    // Cristal decorativo frente al accesorio metálico
+    scene.add_cube(Cube::new(Vec3::new(0.0, 0.0, 0.8), Vec3::new(0.2, 0.2, 0.2), cristal_material));
+
+    // === VAPOR SOBRE EL AGUA ===
+    // Medio homogéneo limitado a una caja sobre el jacuzzi
+    scene.add_medium(Medium::new(1.5)
+        .with_albedo(Vec3::new(0.95, 0.95, 0.97))
+        .with_anisotropy(0.3) // El vapor dispersa ligeramente hacia adelante
+        .with_region(Vec3::new(-0.35, 0.25, -0.35), Vec3::new(0.35, 0.55, 0.35)));
 
     // === ILUMINACIÓN TIPO SPA RELAJANTE ===
     
@@ -460,6 +1061,8 @@ fn create_optimized_scene() -> Scene {
     println!("   Deck de madera natural");
     println!("   Piso de mármol reflectivo");
     println!("   Elementos decorativos (ladrillo + piedra + metal)");
+    println!("   Vapor sobre el agua (medio participante)");
+    println!("   Lámparas emisivas como luces de área");
     println!("   Iluminación tipo spa (3 luces ambientales)");
     println!("Optimizada para máximo rendimiento visual");
     
@@ -0,0 +1,49 @@
+// Índice de refracción dependiente de la longitud de onda (dispersión)
+
+// Modelos de dispersión; las longitudes de onda se reciben en nanómetros
+#[derive(Debug, Clone, Copy)]
+pub enum Dispersion {
+    // Ecuación de Cauchy: n(λ) = A + B / λ²  (λ en micrómetros, B en µm²)
+    Cauchy { a: f32, b: f32 },
+    // Ecuación de Sellmeier: n²(λ) = 1 + Σ Bᵢ λ² / (λ² - Cᵢ)  (Cᵢ en µm²)
+    Sellmeier { b: [f32; 3], c: [f32; 3] },
+}
+
+impl Dispersion {
+    // Agua a temperatura ambiente
+    pub fn water() -> Self {
+        Dispersion::Cauchy { a: 1.3199, b: 0.006878 }
+    }
+
+    // Vidrio crown BK7 (cristales y prismas suaves)
+    pub fn bk7() -> Self {
+        Dispersion::Sellmeier {
+            b: [1.039_612, 0.231_792_34, 1.010_469_5],
+            c: [0.006_000_699, 0.020_017_914, 103.560_65],
+        }
+    }
+
+    // Vidrio flint denso SF10 (dispersión fuerte, arcoíris muy marcados)
+    pub fn dense_flint() -> Self {
+        Dispersion::Sellmeier {
+            b: [1.621_539, 0.256_287_84, 1.644_475_5],
+            c: [0.012_224_146, 0.059_573_678, 147.468_8],
+        }
+    }
+
+    // Índice de refracción para una longitud de onda en nanómetros
+    pub fn index_at(&self, wavelength_nm: f32) -> f32 {
+        let l = wavelength_nm / 1000.0;
+        let l2 = l * l;
+        match self {
+            Dispersion::Cauchy { a, b } => a + b / l2,
+            Dispersion::Sellmeier { b, c } => {
+                let mut n2 = 1.0;
+                for i in 0..3 {
+                    n2 += b[i] * l2 / (l2 - c[i]);
+                }
+                n2.max(1.0).sqrt()
+            }
+        }
+    }
+}
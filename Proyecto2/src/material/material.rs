@@ -2,6 +2,7 @@
 
 use crate::math::Vec3;
 use crate::texture::Texture;
+use super::Dispersion;
 
 #[derive(Debug, Clone)]
 pub struct Material {
@@ -17,6 +18,8 @@ pub struct Material {
     pub transparency: f32,
     // Índice de refracción (usado cuando transparency > 0)
     pub refractive_index: f32,
+    // Dispersión opcional (solo se usa en modo espectral)
+    pub dispersion: Option<Dispersion>,
     // Factor de emisión (para materiales que emiten luz)
     pub emission: Vec3,
 }
@@ -31,6 +34,7 @@ impl Material {
             reflectivity: 0.0,
             transparency: 0.0,
             refractive_index: 1.0,
+            dispersion: None,
             emission: Vec3::zero(),
         }
     }
@@ -77,6 +81,27 @@ impl Material {
         self
     }
     
+    // Builder pattern para configurar emisión (los objetos emisivos actúan como luces de área)
+    pub fn with_emission(mut self, emission: Vec3) -> Self {
+        self.emission = Vec3::new(emission.x.max(0.0), emission.y.max(0.0), emission.z.max(0.0));
+        self
+    }
+    
+    // Builder pattern para configurar dispersión; el índice base pasa a ser el de 550nm
+    pub fn with_dispersion(mut self, dispersion: Dispersion) -> Self {
+        self.refractive_index = dispersion.index_at(550.0).max(1.0);
+        self.dispersion = Some(dispersion);
+        self
+    }
+    
+    // Índice de refracción para la longitud de onda del rayo (si la tiene)
+    pub fn refractive_index_at(&self, wavelength: Option<f32>) -> f32 {
+        match (self.dispersion, wavelength) {
+            (Some(dispersion), Some(lambda)) => dispersion.index_at(lambda).max(1.0),
+            _ => self.refractive_index,
+        }
+    }
+    
     // Obtiene el color de emisión
     pub fn emitted(&self) -> Vec3 {
         self.emission
@@ -1,5 +1,7 @@
 // Módulo de materiales
 
 pub mod material;
+pub mod dispersion;
 
 pub use material::Material;
+pub use dispersion::Dispersion;
@@ -0,0 +1,193 @@
+// Matriz 3x3 para rotaciones, escalas y transformación de normales
+
+use super::{Vec3, Mat4};
+
+// Matriz en orden de filas: m[fila][columna]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mat3 {
+    pub m: [[f32; 3]; 3],
+}
+
+impl Mat3 {
+    pub fn new(m: [[f32; 3]; 3]) -> Self {
+        Mat3 { m }
+    }
+
+    pub fn identity() -> Self {
+        Mat3::new([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]])
+    }
+
+    // Construye la matriz a partir de tres columnas (por ejemplo, una base ortonormal)
+    pub fn from_columns(x: Vec3, y: Vec3, z: Vec3) -> Self {
+        Mat3::new([[x.x, y.x, z.x], [x.y, y.y, z.y], [x.z, y.z, z.z]])
+    }
+
+    pub fn scaling(scale: Vec3) -> Self {
+        Mat3::new([[scale.x, 0.0, 0.0], [0.0, scale.y, 0.0], [0.0, 0.0, scale.z]])
+    }
+
+    // Rotación alrededor de un eje arbitrario (ángulo en radianes)
+    pub fn rotation_axis(axis: Vec3, angle: f32) -> Self {
+        Mat3::from_mat4(&Mat4::rotation_axis(axis, angle))
+    }
+
+    // Bloque 3x3 superior izquierdo de una matriz 4x4
+    pub fn from_mat4(matrix: &Mat4) -> Self {
+        let m = &matrix.m;
+        Mat3::new([
+            [m[0][0], m[0][1], m[0][2]],
+            [m[1][0], m[1][1], m[1][2]],
+            [m[2][0], m[2][1], m[2][2]],
+        ])
+    }
+
+    // Extiende a 4x4 sin traslación
+    pub fn to_mat4(&self) -> Mat4 {
+        let m = &self.m;
+        Mat4::new([
+            [m[0][0], m[0][1], m[0][2], 0.0],
+            [m[1][0], m[1][1], m[1][2], 0.0],
+            [m[2][0], m[2][1], m[2][2], 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub fn column(&self, index: usize) -> Vec3 {
+        Vec3::new(self.m[0][index], self.m[1][index], self.m[2][index])
+    }
+
+    pub fn determinant(&self) -> f32 {
+        let m = &self.m;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    pub fn transpose(&self) -> Self {
+        let m = &self.m;
+        Mat3::new([
+            [m[0][0], m[1][0], m[2][0]],
+            [m[0][1], m[1][1], m[2][1]],
+            [m[0][2], m[1][2], m[2][2]],
+        ])
+    }
+
+    // Inversa por la adjunta; None si la matriz es singular
+    pub fn inverse(&self) -> Option<Self> {
+        let det = self.determinant();
+        if det.abs() < 1e-12 {
+            return None;
+        }
+        let m = &self.m;
+        let inv_det = 1.0 / det;
+        Some(Mat3::new([
+            [
+                (m[1][1] * m[2][2] - m[1][2] * m[2][1]) * inv_det,
+                (m[0][2] * m[2][1] - m[0][1] * m[2][2]) * inv_det,
+                (m[0][1] * m[1][2] - m[0][2] * m[1][1]) * inv_det,
+            ],
+            [
+                (m[1][2] * m[2][0] - m[1][0] * m[2][2]) * inv_det,
+                (m[0][0] * m[2][2] - m[0][2] * m[2][0]) * inv_det,
+                (m[0][2] * m[1][0] - m[0][0] * m[1][2]) * inv_det,
+            ],
+            [
+                (m[1][0] * m[2][1] - m[1][1] * m[2][0]) * inv_det,
+                (m[0][1] * m[2][0] - m[0][0] * m[2][1]) * inv_det,
+                (m[0][0] * m[1][1] - m[0][1] * m[1][0]) * inv_det,
+            ],
+        ]))
+    }
+
+    pub fn transform_vector(&self, v: Vec3) -> Vec3 {
+        let m = &self.m;
+        Vec3::new(
+            m[0][0] * v.x + m[0][1] * v.y + m[0][2] * v.z,
+            m[1][0] * v.x + m[1][1] * v.y + m[1][2] * v.z,
+            m[2][0] * v.x + m[2][1] * v.y + m[2][2] * v.z,
+        )
+    }
+
+    // Matriz de normales (transpuesta de la inversa)
+    pub fn normal_matrix(&self) -> Option<Self> {
+        self.inverse().map(|inverse| inverse.transpose())
+    }
+}
+
+impl std::ops::Mul for Mat3 {
+    type Output = Mat3;
+    fn mul(self, other: Mat3) -> Mat3 {
+        let mut result = [[0.0; 3]; 3];
+        for (i, row) in result.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = (0..3).map(|k| self.m[i][k] * other.m[k][j]).sum();
+            }
+        }
+        Mat3::new(result)
+    }
+}
+
+impl std::ops::Mul<Vec3> for Mat3 {
+    type Output = Vec3;
+    fn mul(self, v: Vec3) -> Vec3 {
+        self.transform_vector(v)
+    }
+}
+
+impl Default for Mat3 {
+    fn default() -> Self {
+        Mat3::identity()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx_vec(a: Vec3, b: Vec3) -> bool {
+        (a - b).length() < 1e-4
+    }
+
+    fn approx_mat(a: &Mat3, b: &Mat3) -> bool {
+        (0..3).all(|i| (0..3).all(|j| (a.m[i][j] - b.m[i][j]).abs() < 1e-4))
+    }
+
+    #[test]
+    fn inverse_times_matrix_is_identity() {
+        let m = Mat3::rotation_axis(Vec3::new(1.0, 2.0, 0.5), 0.8) * Mat3::scaling(Vec3::new(2.0, 0.5, 3.0));
+        let inverse = m.inverse().unwrap();
+        assert!(approx_mat(&(m * inverse), &Mat3::identity()));
+        assert!(approx_mat(&(inverse * m), &Mat3::identity()));
+    }
+
+    #[test]
+    fn singular_matrix_has_no_inverse() {
+        assert!(Mat3::scaling(Vec3::new(1.0, 0.0, 1.0)).inverse().is_none());
+    }
+
+    #[test]
+    fn rotation_is_orthonormal() {
+        let r = Mat3::rotation_axis(Vec3::new(0.3, 1.0, -0.2), 1.1);
+        assert!(approx_mat(&(r * r.transpose()), &Mat3::identity()));
+        assert!((r.determinant() - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn normal_matrix_keeps_normals_perpendicular() {
+        let m = Mat3::scaling(Vec3::new(4.0, 1.0, 1.0));
+        let tangent = Vec3::new(1.0, 1.0, 0.0);
+        let normal = Vec3::new(1.0, -1.0, 0.0);
+        let transformed_tangent = m * tangent;
+        let transformed_normal = m.normal_matrix().unwrap() * normal;
+        assert!(transformed_tangent.dot(&transformed_normal).abs() < 1e-4);
+    }
+
+    #[test]
+    fn columns_round_trip() {
+        let (x, y, z) = (Vec3::new(1.0, 2.0, 3.0), Vec3::new(4.0, 5.0, 6.0), Vec3::new(7.0, 8.0, 10.0));
+        let m = Mat3::from_columns(x, y, z);
+        assert!(approx_vec(m.column(0), x));
+        assert!(approx_vec(m.column(2), z));
+        assert!(approx_vec(m * Vec3::new(0.0, 1.0, 0.0), y));
+    }
+}
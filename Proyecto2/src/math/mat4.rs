@@ -0,0 +1,305 @@
+// Matriz 4x4 para transformaciones afines
+
+use super::{Vec3, Quat};
+
+// Matriz en orden de filas: m[fila][columna]; los puntos son vectores columna
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mat4 {
+    pub m: [[f32; 4]; 4],
+}
+
+impl Mat4 {
+    pub fn new(m: [[f32; 4]; 4]) -> Self {
+        Mat4 { m }
+    }
+
+    pub fn identity() -> Self {
+        Mat4::new([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub fn translation(offset: Vec3) -> Self {
+        Mat4::new([
+            [1.0, 0.0, 0.0, offset.x],
+            [0.0, 1.0, 0.0, offset.y],
+            [0.0, 0.0, 1.0, offset.z],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub fn scaling(scale: Vec3) -> Self {
+        Mat4::new([
+            [scale.x, 0.0, 0.0, 0.0],
+            [0.0, scale.y, 0.0, 0.0],
+            [0.0, 0.0, scale.z, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    // Rotación alrededor del eje X (ángulo en radianes)
+    pub fn rotation_x(angle: f32) -> Self {
+        let (s, c) = angle.sin_cos();
+        Mat4::new([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, c, -s, 0.0],
+            [0.0, s, c, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    // Rotación alrededor del eje Y (ángulo en radianes)
+    pub fn rotation_y(angle: f32) -> Self {
+        let (s, c) = angle.sin_cos();
+        Mat4::new([
+            [c, 0.0, s, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [-s, 0.0, c, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    // Rotación alrededor del eje Z (ángulo en radianes)
+    pub fn rotation_z(angle: f32) -> Self {
+        let (s, c) = angle.sin_cos();
+        Mat4::new([
+            [c, -s, 0.0, 0.0],
+            [s, c, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    // Rotación alrededor de un eje arbitrario (fórmula de Rodrigues, ángulo en radianes)
+    pub fn rotation_axis(axis: Vec3, angle: f32) -> Self {
+        let a = axis.normalize();
+        let (s, c) = angle.sin_cos();
+        let t = 1.0 - c;
+        Mat4::new([
+            [t * a.x * a.x + c, t * a.x * a.y - s * a.z, t * a.x * a.z + s * a.y, 0.0],
+            [t * a.x * a.y + s * a.z, t * a.y * a.y + c, t * a.y * a.z - s * a.x, 0.0],
+            [t * a.x * a.z - s * a.y, t * a.y * a.z + s * a.x, t * a.z * a.z + c, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    // Traslación · rotación · escala (el orden habitual para colocar objetos)
+    pub fn from_trs(translation: Vec3, rotation: Quat, scale: Vec3) -> Self {
+        Mat4::translation(translation) * rotation.to_mat4() * Mat4::scaling(scale)
+    }
+
+    // Matriz de vista (mundo -> cámara) con la cámara mirando hacia -Z local
+    pub fn look_at(eye: Vec3, target: Vec3, up: Vec3) -> Self {
+        let forward = (target - eye).normalize();
+        let right = forward.cross(&up).normalize();
+        let camera_up = right.cross(&forward);
+        Mat4::new([
+            [right.x, right.y, right.z, -right.dot(&eye)],
+            [camera_up.x, camera_up.y, camera_up.z, -camera_up.dot(&eye)],
+            [-forward.x, -forward.y, -forward.z, forward.dot(&eye)],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut result = [[0.0; 4]; 4];
+        for (i, row) in result.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = self.m[j][i];
+            }
+        }
+        Mat4::new(result)
+    }
+
+    // Inversa por cofactores; None si la matriz es singular
+    pub fn inverse(&self) -> Option<Self> {
+        let m = &self.m;
+        let s0 = m[0][0] * m[1][1] - m[1][0] * m[0][1];
+        let s1 = m[0][0] * m[1][2] - m[1][0] * m[0][2];
+        let s2 = m[0][0] * m[1][3] - m[1][0] * m[0][3];
+        let s3 = m[0][1] * m[1][2] - m[1][1] * m[0][2];
+        let s4 = m[0][1] * m[1][3] - m[1][1] * m[0][3];
+        let s5 = m[0][2] * m[1][3] - m[1][2] * m[0][3];
+
+        let c5 = m[2][2] * m[3][3] - m[3][2] * m[2][3];
+        let c4 = m[2][1] * m[3][3] - m[3][1] * m[2][3];
+        let c3 = m[2][1] * m[3][2] - m[3][1] * m[2][2];
+        let c2 = m[2][0] * m[3][3] - m[3][0] * m[2][3];
+        let c1 = m[2][0] * m[3][2] - m[3][0] * m[2][2];
+        let c0 = m[2][0] * m[3][1] - m[3][0] * m[2][1];
+
+        let det = s0 * c5 - s1 * c4 + s2 * c3 + s3 * c2 - s4 * c1 + s5 * c0;
+        if det.abs() < 1e-12 {
+            return None;
+        }
+        let inv_det = 1.0 / det;
+
+        Some(Mat4::new([
+            [
+                (m[1][1] * c5 - m[1][2] * c4 + m[1][3] * c3) * inv_det,
+                (-m[0][1] * c5 + m[0][2] * c4 - m[0][3] * c3) * inv_det,
+                (m[3][1] * s5 - m[3][2] * s4 + m[3][3] * s3) * inv_det,
+                (-m[2][1] * s5 + m[2][2] * s4 - m[2][3] * s3) * inv_det,
+            ],
+            [
+                (-m[1][0] * c5 + m[1][2] * c2 - m[1][3] * c1) * inv_det,
+                (m[0][0] * c5 - m[0][2] * c2 + m[0][3] * c1) * inv_det,
+                (-m[3][0] * s5 + m[3][2] * s2 - m[3][3] * s1) * inv_det,
+                (m[2][0] * s5 - m[2][2] * s2 + m[2][3] * s1) * inv_det,
+            ],
+            [
+                (m[1][0] * c4 - m[1][1] * c2 + m[1][3] * c0) * inv_det,
+                (-m[0][0] * c4 + m[0][1] * c2 - m[0][3] * c0) * inv_det,
+                (m[3][0] * s4 - m[3][1] * s2 + m[3][3] * s0) * inv_det,
+                (-m[2][0] * s4 + m[2][1] * s2 - m[2][3] * s0) * inv_det,
+            ],
+            [
+                (-m[1][0] * c3 + m[1][1] * c1 - m[1][2] * c0) * inv_det,
+                (m[0][0] * c3 - m[0][1] * c1 + m[0][2] * c0) * inv_det,
+                (-m[3][0] * s3 + m[3][1] * s1 - m[3][2] * s0) * inv_det,
+                (m[2][0] * s3 - m[2][1] * s1 + m[2][2] * s0) * inv_det,
+            ],
+        ]))
+    }
+
+    // Transforma un punto (w = 1, incluye traslación)
+    pub fn transform_point(&self, p: Vec3) -> Vec3 {
+        let m = &self.m;
+        let x = m[0][0] * p.x + m[0][1] * p.y + m[0][2] * p.z + m[0][3];
+        let y = m[1][0] * p.x + m[1][1] * p.y + m[1][2] * p.z + m[1][3];
+        let z = m[2][0] * p.x + m[2][1] * p.y + m[2][2] * p.z + m[2][3];
+        let w = m[3][0] * p.x + m[3][1] * p.y + m[3][2] * p.z + m[3][3];
+        if w != 1.0 && w != 0.0 {
+            Vec3::new(x / w, y / w, z / w)
+        } else {
+            Vec3::new(x, y, z)
+        }
+    }
+
+    // Transforma una dirección (w = 0, sin traslación)
+    pub fn transform_vector(&self, v: Vec3) -> Vec3 {
+        let m = &self.m;
+        Vec3::new(
+            m[0][0] * v.x + m[0][1] * v.y + m[0][2] * v.z,
+            m[1][0] * v.x + m[1][1] * v.y + m[1][2] * v.z,
+            m[2][0] * v.x + m[2][1] * v.y + m[2][2] * v.z,
+        )
+    }
+
+    // Transforma una normal; `self` debe ser la inversa de la transformación del objeto
+    pub fn transform_normal(&self, n: Vec3) -> Vec3 {
+        // Equivale a multiplicar por la transpuesta de la inversa
+        let m = &self.m;
+        Vec3::new(
+            m[0][0] * n.x + m[1][0] * n.y + m[2][0] * n.z,
+            m[0][1] * n.x + m[1][1] * n.y + m[2][1] * n.z,
+            m[0][2] * n.x + m[1][2] * n.y + m[2][2] * n.z,
+        )
+        .normalize()
+    }
+}
+
+impl std::ops::Mul for Mat4 {
+    type Output = Mat4;
+    fn mul(self, other: Mat4) -> Mat4 {
+        let mut result = [[0.0; 4]; 4];
+        for (i, row) in result.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = (0..4).map(|k| self.m[i][k] * other.m[k][j]).sum();
+            }
+        }
+        Mat4::new(result)
+    }
+}
+
+impl Default for Mat4 {
+    fn default() -> Self {
+        Mat4::identity()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx_vec(a: Vec3, b: Vec3) -> bool {
+        (a - b).length() < 1e-4
+    }
+
+    fn approx_mat(a: &Mat4, b: &Mat4) -> bool {
+        (0..4).all(|i| (0..4).all(|j| (a.m[i][j] - b.m[i][j]).abs() < 1e-4))
+    }
+
+    fn sample_transform() -> Mat4 {
+        Mat4::translation(Vec3::new(1.0, -2.0, 3.0))
+            * Mat4::rotation_axis(Vec3::new(1.0, 1.0, 0.3), 0.7)
+            * Mat4::scaling(Vec3::new(2.0, 0.5, 1.5))
+    }
+
+    #[test]
+    fn inverse_times_matrix_is_identity() {
+        let m = sample_transform();
+        let inverse = m.inverse().unwrap();
+        assert!(approx_mat(&(m * inverse), &Mat4::identity()));
+        assert!(approx_mat(&(inverse * m), &Mat4::identity()));
+    }
+
+    #[test]
+    fn singular_matrix_has_no_inverse() {
+        assert!(Mat4::scaling(Vec3::new(0.0, 1.0, 1.0)).inverse().is_none());
+    }
+
+    #[test]
+    fn transpose_is_involution() {
+        let m = sample_transform();
+        assert!(approx_mat(&m.transpose().transpose(), &m));
+        assert!((m.transpose().m[0][3] - m.m[3][0]).abs() < 1e-6);
+    }
+
+    #[test]
+    fn points_translate_but_vectors_do_not() {
+        let m = Mat4::translation(Vec3::new(1.0, 2.0, 3.0));
+        assert!(approx_vec(m.transform_point(Vec3::zero()), Vec3::new(1.0, 2.0, 3.0)));
+        assert!(approx_vec(m.transform_vector(Vec3::up()), Vec3::up()));
+    }
+
+    #[test]
+    fn axis_rotations_match_rodrigues() {
+        let v = Vec3::new(0.3, -0.7, 1.2);
+        assert!(approx_vec(Mat4::rotation_x(0.5).transform_vector(v), Mat4::rotation_axis(Vec3::right(), 0.5).transform_vector(v)));
+        assert!(approx_vec(Mat4::rotation_y(0.5).transform_vector(v), Mat4::rotation_axis(Vec3::up(), 0.5).transform_vector(v)));
+        assert!(approx_vec(Mat4::rotation_z(0.5).transform_vector(v), Mat4::rotation_axis(Vec3::new(0.0, 0.0, 1.0), 0.5).transform_vector(v)));
+    }
+
+    #[test]
+    fn normals_stay_perpendicular_under_non_uniform_scale() {
+        let m = sample_transform();
+        let inverse = m.inverse().unwrap();
+        let tangent = Vec3::new(1.0, 1.0, 0.0);
+        let normal = Vec3::new(1.0, -1.0, 0.0);
+        let transformed = inverse.transform_normal(normal);
+        assert!(m.transform_vector(tangent).dot(&transformed).abs() < 1e-4);
+        assert!((transformed.length() - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn trs_matches_quaternion_rotation() {
+        let rotation = Quat::from_axis_angle(Vec3::new(0.0, 1.0, 1.0), 1.2);
+        let m = Mat4::from_trs(Vec3::new(5.0, 0.0, 0.0), rotation, Vec3::one());
+        let p = Vec3::new(1.0, 2.0, 3.0);
+        assert!(approx_vec(m.transform_point(p), rotation * p + Vec3::new(5.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn look_at_puts_target_in_front() {
+        let eye = Vec3::new(3.0, 2.0, 5.0);
+        let view = Mat4::look_at(eye, Vec3::zero(), Vec3::up());
+        assert!(approx_vec(view.transform_point(eye), Vec3::zero()));
+        let target = view.transform_point(Vec3::zero());
+        assert!(target.x.abs() < 1e-4 && target.y.abs() < 1e-4);
+        assert!((target.z + eye.length()).abs() < 1e-4);
+    }
+}
@@ -2,9 +2,18 @@
 
 pub mod vec3;
 pub mod ray;
+pub mod random;
+pub mod mat3;
+pub mod mat4;
+pub mod quat;
+pub mod polynomial;
 
 pub use vec3::Vec3;
 pub use ray::Ray;
+pub use random::Rng;
+pub use mat3::Mat3;
+pub use mat4::Mat4;
+pub use quat::Quat;
 
 // Utilidades matemáticas
 pub fn degrees_to_radians(degrees: f32) -> f32 {
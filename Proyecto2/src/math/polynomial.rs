@@ -0,0 +1,171 @@
+// Raíces reales de polinomios de grado 2, 3 y 4 (coeficientes del mayor al menor grado).
+// Se trabaja en f64 porque la ecuación del toro es muy sensible al redondeo.
+
+const EPSILON: f64 = 1e-12;
+
+fn is_zero(x: f64) -> bool {
+    x.abs() < EPSILON
+}
+
+// a·x² + b·x + c = 0 (degenera a lineal si a = 0)
+pub fn solve_quadratic(a: f64, b: f64, c: f64) -> Vec<f64> {
+    if is_zero(a) {
+        return if is_zero(b) { Vec::new() } else { vec![-c / b] };
+    }
+
+    let p = b / (2.0 * a);
+    let q = c / a;
+    let discriminant = p * p - q;
+
+    if is_zero(discriminant) {
+        vec![-p]
+    } else if discriminant < 0.0 {
+        Vec::new()
+    } else {
+        let root = discriminant.sqrt();
+        vec![-p - root, -p + root]
+    }
+}
+
+// a·x³ + b·x² + c·x + d = 0 (método de Cardano)
+pub fn solve_cubic(a: f64, b: f64, c: f64, d: f64) -> Vec<f64> {
+    if is_zero(a) {
+        return solve_quadratic(b, c, d);
+    }
+
+    // Forma normal x³ + A·x² + B·x + C y sustitución x = y - A/3
+    let (a2, b2, c2) = (b / a, c / a, d / a);
+    let sq_a = a2 * a2;
+    let p = (-sq_a / 3.0 + b2) / 3.0;
+    let q = (2.0 / 27.0 * a2 * sq_a - a2 * b2 / 3.0 + c2) / 2.0;
+    let cb_p = p * p * p;
+    let discriminant = q * q + cb_p;
+
+    let mut roots = if is_zero(discriminant) {
+        if is_zero(q) {
+            vec![0.0]
+        } else {
+            let u = (-q).cbrt();
+            vec![2.0 * u, -u]
+        }
+    } else if discriminant < 0.0 {
+        // Tres raíces reales (caso trigonométrico)
+        let phi = (-q / (-cb_p).sqrt()).clamp(-1.0, 1.0).acos() / 3.0;
+        let t = 2.0 * (-p).sqrt();
+        vec![
+            t * phi.cos(),
+            -t * (phi + std::f64::consts::PI / 3.0).cos(),
+            -t * (phi - std::f64::consts::PI / 3.0).cos(),
+        ]
+    } else {
+        let root = discriminant.sqrt();
+        vec![(root - q).cbrt() - (root + q).cbrt()]
+    };
+
+    for x in &mut roots {
+        *x -= a2 / 3.0;
+    }
+    roots
+}
+
+// a·x⁴ + b·x³ + c·x² + d·x + e = 0 (método de Ferrari, con refinamiento de Newton)
+pub fn solve_quartic(a: f64, b: f64, c: f64, d: f64, e: f64) -> Vec<f64> {
+    if is_zero(a) {
+        return solve_cubic(b, c, d, e);
+    }
+
+    // Forma normal y sustitución x = y - A/4 para eliminar el término cúbico
+    let (a2, b2, c2, d2) = (b / a, c / a, d / a, e / a);
+    let sq_a = a2 * a2;
+    let p = -3.0 / 8.0 * sq_a + b2;
+    let q = sq_a * a2 / 8.0 - a2 * b2 / 2.0 + c2;
+    let r = -3.0 / 256.0 * sq_a * sq_a + sq_a * b2 / 16.0 - a2 * c2 / 4.0 + d2;
+
+    let mut roots = if is_zero(r) {
+        // y·(y³ + p·y + q) = 0
+        let mut roots = solve_cubic(1.0, 0.0, p, q);
+        roots.push(0.0);
+        roots
+    } else {
+        // Una raíz real de la cúbica resolvente
+        let Some(&z) = solve_cubic(1.0, -p / 2.0, -r, r * p / 2.0 - q * q / 8.0).first() else {
+            return Vec::new();
+        };
+
+        let u = z * z - r;
+        let v = 2.0 * z - p;
+        let u = if is_zero(u) { 0.0 } else if u > 0.0 { u.sqrt() } else { return Vec::new() };
+        let v = if is_zero(v) { 0.0 } else if v > 0.0 { v.sqrt() } else { return Vec::new() };
+
+        let mut roots = solve_quadratic(1.0, if q < 0.0 { -v } else { v }, z - u);
+        roots.extend(solve_quadratic(1.0, if q < 0.0 { v } else { -v }, z + u));
+        roots
+    };
+
+    for x in &mut roots {
+        *x -= a2 / 4.0;
+        // Dos pasos de Newton sobre el polinomio original
+        for _ in 0..2 {
+            let value = (((a * *x + b) * *x + c) * *x + d) * *x + e;
+            let slope = ((4.0 * a * *x + 3.0 * b) * *x + 2.0 * c) * *x + d;
+            if slope.abs() > EPSILON {
+                *x -= value / slope;
+            }
+        }
+    }
+    roots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(mut roots: Vec<f64>) -> Vec<f64> {
+        roots.sort_by(|a, b| a.total_cmp(b));
+        roots
+    }
+
+    fn approx(roots: &[f64], expected: &[f64]) -> bool {
+        roots.len() == expected.len() && roots.iter().zip(expected).all(|(r, e)| (r - e).abs() < 1e-6)
+    }
+
+    #[test]
+    fn quadratic_roots() {
+        assert!(approx(&sorted(solve_quadratic(1.0, -3.0, 2.0)), &[1.0, 2.0]));
+        assert!(solve_quadratic(1.0, 0.0, 1.0).is_empty());
+        assert!(approx(&solve_quadratic(0.0, 2.0, -4.0), &[2.0]));
+    }
+
+    #[test]
+    fn cubic_three_real_roots() {
+        // (x + 1)(x - 2)(x - 3)
+        let roots = sorted(solve_cubic(1.0, -4.0, 1.0, 6.0));
+        assert!(approx(&roots, &[-1.0, 2.0, 3.0]));
+    }
+
+    #[test]
+    fn cubic_single_real_root() {
+        // (x - 1)(x² + 1)
+        assert!(approx(&solve_cubic(1.0, -1.0, 1.0, -1.0), &[1.0]));
+    }
+
+    #[test]
+    fn quartic_four_real_roots() {
+        // (x - 1)(x - 2)(x - 3)(x - 4)
+        let roots = sorted(solve_quartic(1.0, -10.0, 35.0, -50.0, 24.0));
+        assert!(approx(&roots, &[1.0, 2.0, 3.0, 4.0]));
+    }
+
+    #[test]
+    fn quartic_two_real_roots() {
+        // (x² - 4)(x² + 1)
+        let roots = sorted(solve_quartic(2.0, 0.0, -6.0, 0.0, -8.0));
+        assert!(approx(&roots, &[-2.0, 2.0]));
+    }
+
+    #[test]
+    fn quartic_without_real_roots() {
+        // (x² + 1)(x² + 4)
+        assert!(solve_quartic(1.0, 0.0, 5.0, 0.0, 4.0).is_empty());
+    }
+}
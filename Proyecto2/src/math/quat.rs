@@ -0,0 +1,223 @@
+// Cuaterniones unitarios para orientaciones e interpolación esférica
+
+use super::{Vec3, Mat3, Mat4};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quat {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
+}
+
+impl Quat {
+    pub fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
+        Quat { x, y, z, w }
+    }
+
+    pub fn identity() -> Self {
+        Quat::new(0.0, 0.0, 0.0, 1.0)
+    }
+
+    // Rotación de `angle` radianes alrededor de `axis`
+    pub fn from_axis_angle(axis: Vec3, angle: f32) -> Self {
+        let a = axis.normalize();
+        let (s, c) = (angle * 0.5).sin_cos();
+        Quat::new(a.x * s, a.y * s, a.z * s, c)
+    }
+
+    // Ángulos de Euler en radianes (aplicados en orden yaw (Y), pitch (X), roll (Z))
+    pub fn from_euler(yaw: f32, pitch: f32, roll: f32) -> Self {
+        Quat::from_axis_angle(Vec3::up(), yaw)
+            * Quat::from_axis_angle(Vec3::right(), pitch)
+            * Quat::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), roll)
+    }
+
+    // Extrae la rotación de una matriz ortonormal (método de Shepperd)
+    pub fn from_mat3(matrix: &Mat3) -> Self {
+        let m = &matrix.m;
+        let trace = m[0][0] + m[1][1] + m[2][2];
+        let q = if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0;
+            Quat::new((m[2][1] - m[1][2]) / s, (m[0][2] - m[2][0]) / s, (m[1][0] - m[0][1]) / s, 0.25 * s)
+        } else if m[0][0] > m[1][1] && m[0][0] > m[2][2] {
+            let s = (1.0 + m[0][0] - m[1][1] - m[2][2]).sqrt() * 2.0;
+            Quat::new(0.25 * s, (m[0][1] + m[1][0]) / s, (m[0][2] + m[2][0]) / s, (m[2][1] - m[1][2]) / s)
+        } else if m[1][1] > m[2][2] {
+            let s = (1.0 + m[1][1] - m[0][0] - m[2][2]).sqrt() * 2.0;
+            Quat::new((m[0][1] + m[1][0]) / s, 0.25 * s, (m[1][2] + m[2][1]) / s, (m[0][2] - m[2][0]) / s)
+        } else {
+            let s = (1.0 + m[2][2] - m[0][0] - m[1][1]).sqrt() * 2.0;
+            Quat::new((m[0][2] + m[2][0]) / s, (m[1][2] + m[2][1]) / s, 0.25 * s, (m[1][0] - m[0][1]) / s)
+        };
+        q.normalize()
+    }
+
+    pub fn dot(&self, other: &Quat) -> f32 {
+        self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
+    }
+
+    pub fn length(&self) -> f32 {
+        self.dot(self).sqrt()
+    }
+
+    pub fn normalize(&self) -> Self {
+        let len = self.length();
+        if len == 0.0 {
+            Quat::identity()
+        } else {
+            Quat::new(self.x / len, self.y / len, self.z / len, self.w / len)
+        }
+    }
+
+    pub fn conjugate(&self) -> Self {
+        Quat::new(-self.x, -self.y, -self.z, self.w)
+    }
+
+    pub fn inverse(&self) -> Self {
+        let len_sq = self.dot(self);
+        let c = self.conjugate();
+        Quat::new(c.x / len_sq, c.y / len_sq, c.z / len_sq, c.w / len_sq)
+    }
+
+    // Rota un vector: q · v · q⁻¹
+    pub fn rotate(&self, v: Vec3) -> Vec3 {
+        let u = Vec3::new(self.x, self.y, self.z);
+        let t = u.cross(&v) * 2.0;
+        v + t * self.w + u.cross(&t)
+    }
+
+    // Interpolación esférica por el camino más corto
+    pub fn slerp(&self, other: &Quat, t: f32) -> Self {
+        let mut end = *other;
+        let mut cos_theta = self.dot(other);
+        if cos_theta < 0.0 {
+            end = Quat::new(-end.x, -end.y, -end.z, -end.w);
+            cos_theta = -cos_theta;
+        }
+
+        // Casi colineales: interpolación lineal normalizada
+        if cos_theta > 0.9995 {
+            return Quat::new(
+                self.x + (end.x - self.x) * t,
+                self.y + (end.y - self.y) * t,
+                self.z + (end.z - self.z) * t,
+                self.w + (end.w - self.w) * t,
+            )
+            .normalize();
+        }
+
+        let theta = cos_theta.acos();
+        let sin_theta = theta.sin();
+        let a = ((1.0 - t) * theta).sin() / sin_theta;
+        let b = (t * theta).sin() / sin_theta;
+        Quat::new(
+            a * self.x + b * end.x,
+            a * self.y + b * end.y,
+            a * self.z + b * end.z,
+            a * self.w + b * end.w,
+        )
+    }
+
+    pub fn to_mat3(&self) -> Mat3 {
+        let q = self.normalize();
+        let (x, y, z, w) = (q.x, q.y, q.z, q.w);
+        Mat3::new([
+            [1.0 - 2.0 * (y * y + z * z), 2.0 * (x * y - w * z), 2.0 * (x * z + w * y)],
+            [2.0 * (x * y + w * z), 1.0 - 2.0 * (x * x + z * z), 2.0 * (y * z - w * x)],
+            [2.0 * (x * z - w * y), 2.0 * (y * z + w * x), 1.0 - 2.0 * (x * x + y * y)],
+        ])
+    }
+
+    pub fn to_mat4(&self) -> Mat4 {
+        self.to_mat3().to_mat4()
+    }
+}
+
+// Composición: (a * b) aplica primero b y luego a
+impl std::ops::Mul for Quat {
+    type Output = Quat;
+    fn mul(self, o: Quat) -> Quat {
+        Quat::new(
+            self.w * o.x + self.x * o.w + self.y * o.z - self.z * o.y,
+            self.w * o.y - self.x * o.z + self.y * o.w + self.z * o.x,
+            self.w * o.z + self.x * o.y - self.y * o.x + self.z * o.w,
+            self.w * o.w - self.x * o.x - self.y * o.y - self.z * o.z,
+        )
+    }
+}
+
+impl std::ops::Mul<Vec3> for Quat {
+    type Output = Vec3;
+    fn mul(self, v: Vec3) -> Vec3 {
+        self.rotate(v)
+    }
+}
+
+impl Default for Quat {
+    fn default() -> Self {
+        Quat::identity()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_2;
+
+    fn approx_vec(a: Vec3, b: Vec3) -> bool {
+        (a - b).length() < 1e-4
+    }
+
+    #[test]
+    fn rotates_like_the_matrix() {
+        let axis = Vec3::new(0.2, 1.0, -0.4);
+        let q = Quat::from_axis_angle(axis, 0.9);
+        let v = Vec3::new(1.0, -2.0, 0.5);
+        assert!(approx_vec(q * v, Mat3::rotation_axis(axis, 0.9) * v));
+        assert!(approx_vec(q * v, q.to_mat3() * v));
+    }
+
+    #[test]
+    fn quarter_turn_around_y() {
+        let q = Quat::from_axis_angle(Vec3::up(), FRAC_PI_2);
+        assert!(approx_vec(q * Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 0.0, -1.0)));
+    }
+
+    #[test]
+    fn composition_applies_right_first() {
+        let a = Quat::from_axis_angle(Vec3::up(), 0.7);
+        let b = Quat::from_axis_angle(Vec3::right(), -0.3);
+        let v = Vec3::new(0.3, 0.4, 1.0);
+        assert!(approx_vec((a * b) * v, a * (b * v)));
+        assert!(approx_vec((a.to_mat3() * b.to_mat3()) * v, (a * b) * v));
+    }
+
+    #[test]
+    fn inverse_undoes_rotation() {
+        let q = Quat::from_euler(0.4, -0.2, 1.3);
+        let v = Vec3::new(-1.0, 2.0, 3.0);
+        assert!(approx_vec(q.inverse() * (q * v), v));
+    }
+
+    #[test]
+    fn matrix_round_trip() {
+        let q = Quat::from_euler(2.5, 0.3, -0.8);
+        let back = Quat::from_mat3(&q.to_mat3());
+        // q y -q representan la misma rotación
+        assert!(q.dot(&back).abs() > 0.9999);
+    }
+
+    #[test]
+    fn slerp_endpoints_and_midpoint() {
+        let a = Quat::identity();
+        let b = Quat::from_axis_angle(Vec3::up(), FRAC_PI_2);
+        assert!(a.slerp(&b, 0.0).dot(&a) > 0.9999);
+        assert!(a.slerp(&b, 1.0).dot(&b) > 0.9999);
+
+        let mid = a.slerp(&b, 0.5);
+        let expected = Quat::from_axis_angle(Vec3::up(), FRAC_PI_2 * 0.5);
+        assert!(mid.dot(&expected) > 0.9999);
+        assert!((mid.length() - 1.0).abs() < 1e-4);
+    }
+}
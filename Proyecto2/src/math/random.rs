@@ -0,0 +1,80 @@
+// Generador de números pseudoaleatorios ligero (sin dependencias externas)
+
+use super::Vec3;
+
+// Generador xorshift32: rápido, determinista y suficiente para muestreo
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u32,
+}
+
+impl Rng {
+    // Crea un generador a partir de una semilla (nunca queda en cero)
+    pub fn new(seed: u32) -> Self {
+        Rng {
+            state: hash_u32(seed) | 1,
+        }
+    }
+
+    // Crea un generador único por pixel y por muestra
+    pub fn for_pixel(x: u32, y: u32, sample: u32) -> Self {
+        Rng::new(x.wrapping_mul(73856093) ^ y.wrapping_mul(19349663) ^ sample.wrapping_mul(83492791))
+    }
+
+    // Crea un generador a partir de un punto del espacio (útil durante el sombreado)
+    pub fn for_point(point: Vec3, salt: u32) -> Self {
+        Rng::new(
+            point.x.to_bits().wrapping_mul(73856093)
+                ^ point.y.to_bits().wrapping_mul(19349663)
+                ^ point.z.to_bits().wrapping_mul(83492791)
+                ^ salt,
+        )
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.state = x;
+        x
+    }
+
+    // Número uniforme en [0, 1)
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u32() >> 8) as f32 / (1u32 << 24) as f32
+    }
+
+    // Dirección uniforme sobre la esfera unitaria
+    pub fn unit_vector(&mut self) -> Vec3 {
+        let z = 1.0 - 2.0 * self.next_f32();
+        let r = (1.0 - z * z).max(0.0).sqrt();
+        let phi = 2.0 * std::f32::consts::PI * self.next_f32();
+        Vec3::new(r * phi.cos(), r * phi.sin(), z)
+    }
+
+    // Dirección uniforme dentro de un cono alrededor de `axis` (ángulo dado por su coseno)
+    pub fn cone_vector(&mut self, axis: Vec3, cos_max: f32) -> Vec3 {
+        let cos_theta = 1.0 - self.next_f32() * (1.0 - cos_max);
+        let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+        let phi = 2.0 * std::f32::consts::PI * self.next_f32();
+
+        // Base ortonormal alrededor del eje
+        let w = axis.normalize();
+        let helper = if w.x.abs() > 0.9 { Vec3::up() } else { Vec3::right() };
+        let u = helper.cross(&w).normalize();
+        let v = w.cross(&u);
+
+        u * (sin_theta * phi.cos()) + v * (sin_theta * phi.sin()) + w * cos_theta
+    }
+}
+
+// Mezcla de bits para decorrelacionar semillas consecutivas
+pub fn hash_u32(mut x: u32) -> u32 {
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x7feb352d);
+    x ^= x >> 15;
+    x = x.wrapping_mul(0x846ca68b);
+    x ^= x >> 16;
+    x
+}
@@ -6,6 +6,10 @@ use super::Vec3;
 pub struct Ray {
     pub origin: Vec3,
     pub direction: Vec3,
+    // Longitud de onda en nanómetros (solo en modo espectral)
+    pub wavelength: Option<f32>,
+    // Instante dentro del obturador en que viaja el rayo (motion blur)
+    pub time: f32,
 }
 
 impl Ray {
@@ -13,6 +17,29 @@ impl Ray {
         Ray {
             origin,
             direction: direction.normalize(),
+            wavelength: None,
+            time: 0.0,
+        }
+    }
+
+    // Builder pattern para asignar una longitud de onda al rayo
+    pub fn with_wavelength(mut self, wavelength: f32) -> Self {
+        self.wavelength = Some(wavelength);
+        self
+    }
+
+    // Builder pattern para asignar el instante del rayo
+    pub fn with_time(mut self, time: f32) -> Self {
+        self.time = time;
+        self
+    }
+
+    /// Crea un rayo secundario (reflexión, refracción, sombra) que hereda la longitud de onda y el instante
+    pub fn spawn(&self, origin: Vec3, direction: Vec3) -> Ray {
+        Ray {
+            wavelength: self.wavelength,
+            time: self.time,
+            ..Ray::new(origin, direction)
         }
     }
 
@@ -0,0 +1,172 @@
+// Árbol kd balanceado para búsquedas de fotones por radio
+
+use crate::math::Vec3;
+use super::Photon;
+
+// Árbol implícito: cada sub-arreglo tiene su nodo en la mediana
+#[derive(Debug, Clone)]
+pub struct KdTree {
+    photons: Vec<Photon>,
+    // Eje de partición de cada nodo (0 = x, 1 = y, 2 = z)
+    axes: Vec<u8>,
+}
+
+fn axis_value(v: &Vec3, axis: u8) -> f32 {
+    match axis {
+        0 => v.x,
+        1 => v.y,
+        _ => v.z,
+    }
+}
+
+impl KdTree {
+    // Construye el árbol reordenando los fotones alrededor de las medianas
+    pub fn build(mut photons: Vec<Photon>) -> Self {
+        let mut axes = vec![0u8; photons.len()];
+        Self::build_recursive(&mut photons, &mut axes);
+        KdTree { photons, axes }
+    }
+
+    fn build_recursive(photons: &mut [Photon], axes: &mut [u8]) {
+        if photons.len() <= 1 {
+            return;
+        }
+
+        // Elegir el eje con mayor extensión
+        let mut min = photons[0].position;
+        let mut max = photons[0].position;
+        for photon in photons.iter() {
+            let p = photon.position;
+            min = Vec3::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z));
+            max = Vec3::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z));
+        }
+        let extent = max - min;
+        let axis = if extent.x >= extent.y && extent.x >= extent.z {
+            0
+        } else if extent.y >= extent.z {
+            1
+        } else {
+            2
+        };
+
+        let mid = photons.len() / 2;
+        photons.select_nth_unstable_by(mid, |a, b| {
+            axis_value(&a.position, axis).total_cmp(&axis_value(&b.position, axis))
+        });
+        axes[mid] = axis;
+
+        let (left, right) = photons.split_at_mut(mid);
+        let (left_axes, right_axes) = axes.split_at_mut(mid);
+        Self::build_recursive(left, left_axes);
+        Self::build_recursive(&mut right[1..], &mut right_axes[1..]);
+    }
+
+    pub fn len(&self) -> usize {
+        self.photons.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.photons.is_empty()
+    }
+
+    // Visita todos los fotones a distancia menor que `radius` de `point`
+    pub fn for_each_in_radius<F: FnMut(&Photon, f32)>(&self, point: Vec3, radius: f32, mut visit: F) {
+        Self::search(&self.photons, &self.axes, point, radius * radius, &mut visit);
+    }
+
+    fn search<F: FnMut(&Photon, f32)>(photons: &[Photon], axes: &[u8], point: Vec3, radius_sq: f32, visit: &mut F) {
+        if photons.is_empty() {
+            return;
+        }
+
+        let mid = photons.len() / 2;
+        let node = &photons[mid];
+        let distance_sq = node.position.distance_squared(&point);
+        if distance_sq < radius_sq {
+            visit(node, distance_sq);
+        }
+
+        if photons.len() == 1 {
+            return;
+        }
+
+        let axis = axes[mid];
+        let delta = axis_value(&point, axis) - axis_value(&node.position, axis);
+        let (left, right) = photons.split_at(mid);
+        let (left_axes, right_axes) = axes.split_at(mid);
+
+        // Recorrer primero el lado que contiene el punto
+        if delta < 0.0 {
+            Self::search(left, left_axes, point, radius_sq, visit);
+            if delta * delta < radius_sq {
+                Self::search(&right[1..], &right_axes[1..], point, radius_sq, visit);
+            }
+        } else {
+            Self::search(&right[1..], &right_axes[1..], point, radius_sq, visit);
+            if delta * delta < radius_sq {
+                Self::search(left, left_axes, point, radius_sq, visit);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::Rng;
+
+    fn random_photons(count: usize, rng: &mut Rng) -> Vec<Photon> {
+        (0..count)
+            .map(|_| Photon {
+                // Nube alargada en x para que el árbol use varios ejes
+                position: Vec3::new(rng.next_f32() * 4.0, rng.next_f32(), rng.next_f32() * 2.0),
+                power: Vec3::one(),
+                direction: Vec3::new(0.0, -1.0, 0.0),
+            })
+            .collect()
+    }
+
+    // Distancias al cuadrado ordenadas, para comparar conjuntos de fotones
+    fn sorted(mut distances: Vec<f32>) -> Vec<f32> {
+        distances.sort_by(f32::total_cmp);
+        distances
+    }
+
+    #[test]
+    fn radius_search_matches_brute_force() {
+        let mut rng = Rng::new(11);
+        let photons = random_photons(2000, &mut rng);
+        let tree = KdTree::build(photons.clone());
+        assert_eq!(tree.len(), photons.len());
+
+        for _ in 0..50 {
+            let point = Vec3::new(rng.next_f32() * 4.0, rng.next_f32(), rng.next_f32() * 2.0);
+            for radius in [0.01, 0.1, 0.35] {
+                let mut found = Vec::new();
+                tree.for_each_in_radius(point, radius, |photon, distance_sq| {
+                    assert!((photon.position.distance_squared(&point) - distance_sq).abs() < 1e-6);
+                    found.push(distance_sq);
+                });
+                let expected: Vec<f32> = photons
+                    .iter()
+                    .map(|p| p.position.distance_squared(&point))
+                    .filter(|&d| d < radius * radius)
+                    .collect();
+                assert_eq!(sorted(found), sorted(expected));
+            }
+        }
+    }
+
+    #[test]
+    fn handles_empty_and_duplicate_photons() {
+        let empty = KdTree::build(Vec::new());
+        assert!(empty.is_empty());
+        empty.for_each_in_radius(Vec3::zero(), 1.0, |_, _| panic!("árbol vacío"));
+
+        let photon = Photon { position: Vec3::one(), power: Vec3::one(), direction: Vec3::one() };
+        let tree = KdTree::build(vec![photon; 9]);
+        let mut count = 0;
+        tree.for_each_in_radius(Vec3::one(), 0.01, |_, _| count += 1);
+        assert_eq!(count, 9);
+    }
+}
@@ -0,0 +1,20 @@
+// Mapeo de fotones para cáusticas (luz enfocada por superficies especulares)
+
+pub mod kdtree;
+pub mod photon_map;
+
+pub use kdtree::KdTree;
+pub use photon_map::{PhotonConfig, PhotonMap};
+
+use crate::math::Vec3;
+
+// Fotón almacenado en una superficie difusa
+#[derive(Debug, Clone, Copy)]
+pub struct Photon {
+    // Posición donde el fotón impactó la superficie
+    pub position: Vec3,
+    // Potencia (flujo) que transporta el fotón
+    pub power: Vec3,
+    // Dirección de llegada del fotón
+    pub direction: Vec3,
+}
@@ -0,0 +1,226 @@
+// Pre-paso de emisión de fotones y estimación de radiancia para cáusticas
+
+use crate::math::{Vec3, Ray, Rng};
+use crate::scene::Scene;
+use crate::lighting::Light;
+use super::{KdTree, Photon};
+use rayon::prelude::*;
+
+// Parámetros configurables del mapa de fotones
+#[derive(Debug, Clone)]
+pub struct PhotonConfig {
+    // Número total de fotones emitidos por todas las luces
+    pub photon_count: usize,
+    // Radio de búsqueda para la estimación de radiancia
+    pub gather_radius: f32,
+    // Máximo de rebotes especulares por fotón
+    pub max_bounces: u32,
+    // Multiplicador de brillo de las cáusticas
+    pub intensity: f32,
+    // Esfera (centro, radio) hacia la que se enfocan los fotones; None = todas las direcciones
+    pub focus: Option<(Vec3, f32)>,
+    // Instante de los rayos de fotón (estado de olas y objetos en movimiento)
+    pub time: f32,
+}
+
+impl PhotonConfig {
+    pub fn new() -> Self {
+        PhotonConfig {
+            photon_count: 100_000,
+            gather_radius: 0.05,
+            max_bounces: 6,
+            intensity: 1.0,
+            focus: None,
+            time: 0.0,
+        }
+    }
+
+    // Builder pattern para configurar el número de fotones
+    pub fn with_photon_count(mut self, photon_count: usize) -> Self {
+        self.photon_count = photon_count;
+        self
+    }
+
+    // Builder pattern para configurar el radio de búsqueda
+    pub fn with_gather_radius(mut self, gather_radius: f32) -> Self {
+        self.gather_radius = gather_radius.max(0.0001);
+        self
+    }
+
+    // Builder pattern para configurar el máximo de rebotes
+    pub fn with_max_bounces(mut self, max_bounces: u32) -> Self {
+        self.max_bounces = max_bounces;
+        self
+    }
+
+    // Builder pattern para configurar el brillo de las cáusticas
+    pub fn with_intensity(mut self, intensity: f32) -> Self {
+        self.intensity = intensity.max(0.0);
+        self
+    }
+
+    // Builder pattern para enfocar la emisión hacia los objetos especulares
+    pub fn with_focus(mut self, center: Vec3, radius: f32) -> Self {
+        self.focus = Some((center, radius));
+        self
+    }
+
+    // Builder pattern para el instante en que se emiten los fotones
+    pub fn with_time(mut self, time: f32) -> Self {
+        self.time = time;
+        self
+    }
+}
+
+impl Default for PhotonConfig {
+    fn default() -> Self {
+        PhotonConfig::new()
+    }
+}
+
+// Mapa de fotones cáusticos (caminos luz -> especular+ -> difuso)
+#[derive(Debug, Clone)]
+pub struct PhotonMap {
+    tree: KdTree,
+    gather_radius: f32,
+    intensity: f32,
+}
+
+// Constante del filtro cónico (Jensen): k >= 1
+const CONE_FILTER_K: f32 = 1.1;
+
+impl PhotonMap {
+    // Emite fotones desde las luces de la escena y almacena los cáusticos
+    pub fn build(scene: &Scene, config: &PhotonConfig) -> Self {
+        let total_power: f32 = scene.lights().iter().map(|l| l.intensity).sum();
+        let mut photons = Vec::new();
+
+        if total_power > 0.0 {
+            for (light_index, light) in scene.lights().iter().enumerate() {
+                // Fotones proporcionales a la intensidad de cada luz
+                let count = ((config.photon_count as f32) * light.intensity / total_power) as usize;
+                if count == 0 {
+                    continue;
+                }
+
+                // Dirección de emisión (toda la esfera o un cono hacia el foco)
+                let (axis, cos_max) = match config.focus {
+                    Some((center, radius)) => {
+                        let to_center = center - light.position;
+                        let distance = to_center.length();
+                        if distance > radius {
+                            let sin_max = radius / distance;
+                            (to_center / distance, (1.0 - sin_max * sin_max).sqrt())
+                        } else {
+                            (Vec3::up(), -1.0)
+                        }
+                    }
+                    None => (Vec3::up(), -1.0),
+                };
+
+                // Flujo de una luz puntual (4π·I) repartido sobre el ángulo sólido del cono
+                let solid_angle = 2.0 * std::f32::consts::PI * (1.0 - cos_max);
+                let photon_power = light.color * light.intensity * (solid_angle / count as f32);
+
+                let light_photons: Vec<Photon> = (0..count)
+                    .into_par_iter()
+                    .flat_map_iter(|i| {
+                        let mut rng = Rng::new((light_index as u32).wrapping_mul(0x9e3779b9) ^ i as u32);
+                        let direction = if cos_max <= -1.0 {
+                            rng.unit_vector()
+                        } else {
+                            rng.cone_vector(axis, cos_max)
+                        };
+                        let ray = Ray::new(light.position, direction).with_time(config.time);
+                        // Las luces focales solo emiten dentro de su cono
+                        let power = photon_power * light.get_spot_factor(light.position + direction);
+                        Self::trace_photon(scene, light, ray, power, config.max_bounces, &mut rng)
+                    })
+                    .collect();
+                photons.extend(light_photons);
+            }
+        }
+
+        println!("Mapa de fotones: {} fotones cáusticos almacenados", photons.len());
+
+        PhotonMap {
+            tree: KdTree::build(photons),
+            gather_radius: config.gather_radius,
+            intensity: config.intensity,
+        }
+    }
+
+    // Sigue un fotón por la escena; solo se guarda tras al menos un rebote especular
+    fn trace_photon(scene: &Scene, light: &Light, mut ray: Ray, mut power: Vec3, max_bounces: u32, rng: &mut Rng) -> Option<Photon> {
+        let mut specular_path = false;
+
+        for _ in 0..=max_bounces {
+            let hit = scene.hit(&ray, 0.001, f32::INFINITY)?;
+            let material = &hit.material;
+
+            // Primer impacto: cambiar la caída 1/d² física por la atenuación de `Light`,
+            // para que las cáusticas tengan el mismo brillo que la iluminación directa
+            if !specular_path {
+                power *= light.get_attenuation(hit.t) * hit.t * hit.t;
+            }
+
+            // Superficie difusa: almacenar si llegó por un camino especular
+            if material.transparency < 0.5 && specular_path {
+                return Some(Photon {
+                    position: hit.point,
+                    power,
+                    direction: ray.direction,
+                });
+            }
+
+            // Ruleta rusa entre refracción, reflexión y absorción
+            let surface_color = material.texture.value(hit.u, hit.v);
+            let xi = rng.next_f32();
+            if xi < material.transparency {
+                let refractive_index = material.refractive_index_at(ray.wavelength);
+                let eta = if hit.front_face { 1.0 / refractive_index } else { refractive_index };
+                let direction = match ray.direction.refract(&hit.normal, eta) {
+                    Some(refracted) => refracted,
+                    None => ray.direction.reflect(&hit.normal),
+                };
+                let offset = if direction.dot(&hit.normal) < 0.0 { -hit.normal } else { hit.normal };
+                // El medio transparente tiñe el fotón con su color
+                power = power * surface_color;
+                ray = ray.spawn(hit.point + offset * 0.001, direction);
+            } else if xi < material.transparency + material.reflectivity {
+                let reflected = ray.direction.reflect(&hit.normal);
+                power = power * surface_color;
+                ray = ray.spawn(hit.point + hit.normal * 0.001, reflected);
+            } else {
+                return None;
+            }
+            specular_path = true;
+        }
+
+        None
+    }
+
+    pub fn photon_count(&self) -> usize {
+        self.tree.len()
+    }
+
+    // Estimación de radiancia en un punto difuso (filtro cónico sobre el radio de búsqueda)
+    pub fn radiance_estimate(&self, point: Vec3, normal: Vec3) -> Vec3 {
+        if self.tree.is_empty() {
+            return Vec3::zero();
+        }
+
+        let radius = self.gather_radius;
+        let mut flux = Vec3::zero();
+        self.tree.for_each_in_radius(point, radius, |photon, distance_sq| {
+            // Solo fotones que llegan por el lado visible de la superficie
+            if photon.direction.dot(&normal) < 0.0 {
+                let weight = 1.0 - distance_sq.sqrt() / (CONE_FILTER_K * radius);
+                flux += photon.power * weight;
+            }
+        });
+
+        let area = (1.0 - 2.0 / (3.0 * CONE_FILTER_K)) * std::f32::consts::PI * radius * radius;
+        flux * (self.intensity / area)
+    }
+}
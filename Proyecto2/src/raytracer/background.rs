@@ -0,0 +1,75 @@
+// Renderizado en segundo plano (capturas de alta resolución sin bloquear el visor)
+
+use crate::camera::Camera;
+use crate::framebuffer::Framebuffer;
+use crate::scene::Scene;
+use super::Raytracer;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread::{self, JoinHandle};
+
+pub struct BackgroundRender {
+    progress: Arc<AtomicUsize>,
+    total_pixels: usize,
+    handle: JoinHandle<Framebuffer>,
+}
+
+impl BackgroundRender {
+    // Lanza el render en otro hilo con copias del raytracer, la escena y la cámara.
+    // Usa su propio pool con la mitad de los hilos para que el visor siga respondiendo
+    pub fn start(raytracer: Raytracer, scene: Scene, camera: Camera) -> Self {
+        let progress = Arc::new(AtomicUsize::new(0));
+        let total_pixels = (raytracer.width * raytracer.height) as usize;
+        let counter = Arc::clone(&progress);
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads((rayon::current_num_threads() / 2).max(1))
+            .thread_name(|i| format!("captura-{}", i))
+            .build();
+        let handle = thread::spawn(move || {
+            let mut framebuffer = Framebuffer::new(raytracer.width, raytracer.height);
+            let mut render = || raytracer.render_with_progress(&scene, &camera, &mut framebuffer, &counter);
+            match pool {
+                Ok(pool) => pool.install(render),
+                // Sin pool propio se comparte el global (más lento para el visor, pero funciona)
+                Err(_) => render(),
+            }
+            framebuffer
+        });
+        BackgroundRender { progress, total_pixels, handle }
+    }
+
+    // Fracción completada en [0, 1]
+    pub fn progress(&self) -> f32 {
+        self.progress.load(Ordering::Relaxed) as f32 / self.total_pixels.max(1) as f32
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+
+    // Espera al hilo y devuelve la imagen (None si el render falló)
+    pub fn finish(self) -> Option<Framebuffer> {
+        self.handle.join().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adaptive_config::{AdaptiveConfig, RenderResolution};
+    use crate::math::Vec3;
+
+    #[test]
+    fn finishes_with_full_progress() {
+        let config = AdaptiveConfig { resolution: RenderResolution::Fixed(40, 30), ..AdaptiveConfig::performance_mode() };
+        let raytracer = Raytracer::with_config(&config, 800, 600);
+        let camera = Camera::new(Vec3::new(0.0, 0.0, 3.0), Vec3::zero(), Vec3::up(), 60.0, 4.0 / 3.0);
+        let render = BackgroundRender::start(raytracer, Scene::new(), camera);
+        while !render.is_finished() {
+            thread::yield_now();
+        }
+        assert_eq!(render.progress(), 1.0);
+        let framebuffer = render.finish().unwrap();
+        assert_eq!((framebuffer.width, framebuffer.height), (40, 30));
+    }
+}
@@ -1,5 +1,9 @@
 // Módulo de raytracer
 
 pub mod raytracer;
+pub mod background;
+pub mod tiles;
 
-pub use raytracer::Raytracer;
+pub use raytracer::{Raytracer, RenderMode};
+pub use background::BackgroundRender;
+pub use tiles::{Tile, TileOrder, TileResult};
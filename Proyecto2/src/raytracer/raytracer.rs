@@ -1,39 +1,76 @@
 // Motor de raytracing optimizado con paralelización
 
-use crate::math::{Vec3, Ray};
+use crate::math::{Vec3, Ray, Rng};
 use crate::scene::Scene;
-use crate::camera::Camera;
+use crate::camera::{Camera, StereoRig};
 use crate::geometry::HitRecord;
 use crate::framebuffer::Framebuffer;
 use crate::adaptive_config::{RenderQuality, AdaptiveConfig};
+use crate::spectral::{self, SpectralAccumulator};
+use crate::volume::{Medium, VolumeMode};
+use super::tiles::{Tile, TileOrder, TileResult};
 use rayon::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Sender};
 
+// Las luces no están normalizadas por π (ver calculate_lighting); se escala la fase
+// para que un medio isotrópico responda con el mismo brillo que una superficie difusa
+const PHASE_SCALE: f32 = 4.0 * std::f32::consts::PI;
+
+// Modo de renderizado: RGB (por defecto) o espectral para dispersión
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RenderMode {
+    Rgb,
+    // Número de longitudes de onda muestreadas por pixel
+    Spectral { wavelengths: u32 },
+}
+
+#[derive(Clone)]
 pub struct Raytracer {
     pub width: u32,
     pub height: u32,
     pub max_depth: i32,
     pub quality: RenderQuality,
+    pub render_mode: RenderMode,
+    pub volume_mode: VolumeMode,
+    // Muestras por pixel (posición y tiempo con jitter; 1 = un rayo por el centro)
+    pub samples_per_pixel: u32,
+    // Renderizado estéreo de ambos ojos en la misma imagen (None = monoscópico)
+    pub stereo: Option<StereoRig>,
+    // Lado de los tiles en pixels y orden en que se renderizan
+    pub tile_size: u32,
+    pub tile_order: TileOrder,
 }
 
 impl Raytracer {
-    // Crea un raytracer con configuración específica
-    pub fn with_config(config: &AdaptiveConfig) -> Self {
-        let (width, height) = config.quality.dimensions();
+    // Crea un raytracer con configuración específica para una ventana de window_width x window_height
+    pub fn with_config(config: &AdaptiveConfig, window_width: u32, window_height: u32) -> Self {
+        let (width, height) = config.resolution.dimensions(window_width, window_height);
         Raytracer {
             width,
             height,
             max_depth: config.quality.max_depth(),
             quality: config.quality,
+            render_mode: RenderMode::Rgb,
+            volume_mode: config.quality.volume_mode(),
+            samples_per_pixel: 1,
+            stereo: None,
+            tile_size: 32,
+            tile_order: TileOrder::default(),
         }
     }
     
-    // Actualiza la calidad dinámicamente
+    // Actualiza la calidad dinámicamente (rebotes y volúmenes; la resolución va aparte)
     pub fn set_quality(&mut self, quality: RenderQuality) {
         self.quality = quality;
-        let (width, height) = quality.dimensions();
-        self.width = width;
-        self.height = height;
         self.max_depth = quality.max_depth();
+        self.volume_mode = quality.volume_mode();
+    }
+    
+    // Cambia el tamaño de la imagen renderizada
+    pub fn set_resolution(&mut self, width: u32, height: u32) {
+        self.width = width.max(1);
+        self.height = height.max(1);
     }
     
     // Método para renderizado directo a framebuffer (más eficiente)
@@ -41,39 +78,145 @@ impl Raytracer {
         println!("Renderizando {}x{} pixels directamente a framebuffer...", self.width, self.height);
         
         let total_pixels = (self.width * self.height) as usize;
-        let mut pixel_data: Vec<Vec3> = vec![Vec3::zero(); total_pixels];
-        
-        // Renderizado paralelo
-        pixel_data
-            .par_iter_mut()
-            .enumerate()
-            .for_each(|(idx, pixel_color)| {
-                let y = idx / (self.width as usize);
-                let x = idx % (self.width as usize);
-                
-                let u = x as f32 / (self.width - 1) as f32;
-                let v = (self.height - 1 - y as u32) as f32 / (self.height - 1) as f32;
-                
-                let ray = camera.get_ray(u, v);
-                *pixel_color = self.ray_color(&ray, scene, self.max_depth);
-                
-                if idx % 20000 == 0 {
-                    let progress = (idx as f32 / total_pixels as f32 * 100.0) as u32;
-                    print!("\r{}%", progress);
-                    use std::io::{self, Write};
-                    let _ = io::stdout().flush();
-                }
+        let mut last_percent = 0;
+        self.collect_tiles(scene, camera, framebuffer, |done| {
+            let percent = done * 100 / total_pixels.max(1);
+            if percent / 10 != last_percent / 10 {
+                print!("\r{}%", percent);
+                use std::io::{self, Write};
+                let _ = io::stdout().flush();
+            }
+            last_percent = percent;
+        });
+        
+        println!("\nRenderizado directo completo!");
+    }
+    
+    // Igual que render_to_framebuffer pero sin escribir en la consola, contando en
+    // `progress` los pixels terminados (para mostrar el avance desde otro hilo)
+    pub fn render_with_progress(&self, scene: &Scene, camera: &Camera, framebuffer: &mut Framebuffer, progress: &AtomicUsize) {
+        self.collect_tiles(scene, camera, framebuffer, |done| progress.store(done, Ordering::Relaxed));
+    }
+    
+    // Escribe cada tile al framebuffer en cuanto llega y avisa con los pixels terminados
+    fn collect_tiles(&self, scene: &Scene, camera: &Camera, framebuffer: &mut Framebuffer, mut on_progress: impl FnMut(usize)) {
+        let (sender, receiver) = mpsc::channel();
+        std::thread::scope(|scope| {
+            scope.spawn(move || self.render_tiles(scene, camera, sender));
+            
+            let mut done = 0;
+            for result in receiver {
+                result.write_to(framebuffer);
+                done += result.tile.pixel_count();
+                on_progress(done);
+            }
+        });
+    }
+    
+    // Tiles de la imagen completa en el orden configurado
+    pub fn tiles(&self) -> Vec<Tile> {
+        self.tile_order.tiles(self.width, self.height, self.tile_size)
+    }
+    
+    // Renderiza todos los tiles en paralelo y envía cada uno por `sender` al terminar.
+    // Los tiles se reparten en el orden configurado, así que llegan aproximadamente en ese orden
+    pub fn render_tiles(&self, scene: &Scene, camera: &Camera, sender: Sender<TileResult>) {
+        let camera = self.view_camera(camera);
+        self.tiles()
+            .into_iter()
+            .par_bridge()
+            .for_each_with(sender, |sender, tile| {
+                // Si el receptor ya no existe no hay a quién entregar el resto
+                let _ = sender.send(self.trace_tile(scene, &camera, tile));
             });
+    }
+    
+    // Renderiza un solo tile (útil para repartir o reanudar un render por partes)
+    pub fn render_tile(&self, scene: &Scene, camera: &Camera, tile: Tile) -> TileResult {
+        self.trace_tile(scene, &self.view_camera(camera), tile)
+    }
+    
+    // En estéreo cada ojo usa su propia proporción de aspecto
+    fn view_camera(&self, camera: &Camera) -> Camera {
+        match &self.stereo {
+            Some(rig) => rig.eye_camera(camera, self.width, self.height),
+            None => camera.clone(),
+        }
+    }
+    
+    fn trace_tile(&self, scene: &Scene, camera: &Camera, tile: Tile) -> TileResult {
+        let mut pixels = Vec::with_capacity(tile.pixel_count());
+        for y in tile.y..tile.y + tile.height {
+            for x in tile.x..tile.x + tile.width {
+                pixels.push(self.trace_pixel(scene, camera, x, y));
+            }
+        }
+        TileResult { tile, pixels }
+    }
+    
+    // Coordenadas de imagen (0-1, v hacia arriba) de un punto del pixel (x, y) desplazado
+    // `jitter` desde su centro
+    fn pixel_uv(&self, x: u32, y: u32, jitter_x: f32, jitter_y: f32) -> (f32, f32) {
+        let u = (x as f32 + 0.5 + jitter_x) / self.width as f32;
+        let v = ((self.height - y) as f32 - 0.5 - jitter_y) / self.height as f32;
+        (u, v)
+    }
+    
+    // Calcula el color de un pixel promediando sus muestras
+    fn trace_pixel(&self, scene: &Scene, camera: &Camera, x: u32, y: u32) -> Vec3 {
+        let samples = self.samples_per_pixel.max(1);
+        let mut rng = Rng::for_pixel(x, y, 0);
+        let mut color = Vec3::zero();
         
-        // Escribir al framebuffer
-        for (idx, &color) in pixel_data.iter().enumerate() {
-            let y = (idx / (self.width as usize)) as u32;
-            let x = (idx % (self.width as usize)) as u32;
-            framebuffer.set_pixel_from_vec3(x, y, color);
+        for sample in 0..samples {
+            // Con una sola muestra el rayo pasa por el centro del pixel
+            let (jitter_x, jitter_y) = if samples > 1 {
+                (rng.next_f32() - 0.5, rng.next_f32() - 0.5)
+            } else {
+                (0.0, 0.0)
+            };
+            let (u, v) = self.pixel_uv(x, y, jitter_x, jitter_y);
+            
+            // Instantes estratificados a lo largo del obturador
+            let time = camera.shutter_time((sample as f32 + rng.next_f32()) / samples as f32);
+            // Fuera de la imagen circular del ojo de pez el pixel queda negro
+            let ray = match &self.stereo {
+                Some(rig) => {
+                    let (eye, u, v) = rig.layout.split(u, v);
+                    rig.eye_ray(camera, eye, u, v, time)
+                }
+                None => camera.get_ray_at(u, v, time),
+            };
+            if let Some(ray) = ray {
+                color += self.trace_sample(&ray, scene, &mut rng);
+            }
         }
         
-        println!("\nRenderizado directo completo!");
+        color / samples as f32
     }
+    
+    // Color de un rayo de cámara según el modo de renderizado
+    fn trace_sample(&self, ray: &Ray, scene: &Scene, rng: &mut Rng) -> Vec3 {
+        match self.render_mode {
+            RenderMode::Rgb => self.ray_color(ray, scene, self.max_depth),
+            RenderMode::Spectral { wavelengths } => {
+                let wavelengths = wavelengths.max(1);
+                let mut accumulator = SpectralAccumulator::new();
+                
+                // Muestreo estratificado del rango visible
+                for i in 0..wavelengths {
+                    let jitter = (i as f32 + rng.next_f32()) / wavelengths as f32;
+                    let lambda = spectral::sample_wavelength(jitter);
+                    let spectral_ray = ray.clone().with_wavelength(lambda);
+                    let radiance = self.ray_color(&spectral_ray, scene, self.max_depth);
+                    accumulator.add_sample(lambda, radiance);
+                }
+                
+                accumulator.to_rgb()
+            }
+        }
+    }
+    
     fn ray_color(&self, ray: &Ray, scene: &Scene, depth: i32) -> Vec3 {
         // Si hemos alcanzado el límite de rebotes, no contribuye más luz
         if depth <= 0 {
@@ -81,12 +224,123 @@ impl Raytracer {
         }
         
         // Verifica si el rayo golpea algún objeto
-        if let Some(hit_record) = scene.hit(ray, 0.001, f32::INFINITY) {
-            self.calculate_lighting(&hit_record, ray, scene, depth)
+        let (mut color, t_hit) = if let Some(hit_record) = scene.hit(ray, 0.001, f32::INFINITY) {
+            (self.calculate_lighting(&hit_record, ray, scene, depth), hit_record.t)
         } else {
             // Si no golpea nada, devuelve el color de fondo
-            scene.get_background_color(ray)
+            (scene.get_background_color(ray), f32::INFINITY)
+        };
+        
+        // Niebla y luz dispersada por los medios que atraviesa el rayo
+        for medium in &scene.media {
+            color = self.apply_medium(medium, ray, t_hit, color, scene);
+        }
+        color
+    }
+    
+    // Iluminación directa de los emisores: muestreo por área con rayos de sombra
+    fn emitter_lighting(&self, hit: &HitRecord, incident_ray: &Ray, scene: &Scene, surface_color: Vec3) -> Vec3 {
+        let samples = scene.emitter_samples.max(1);
+        let mut rng = Rng::for_point(hit.point, incident_ray.direction.y.to_bits());
+        let view_dir = -incident_ray.direction;
+        let mut total = Vec3::zero();
+        
+        for _ in 0..samples {
+            let Some(sample) = scene.emitters.sample(&mut rng) else {
+                continue;
+            };
+            
+            let to_light = sample.point - hit.point;
+            let distance_sq = to_light.length_squared();
+            if distance_sq < 1e-8 {
+                continue;
+            }
+            let light_dir = to_light / distance_sq.sqrt();
+            
+            // Ambas superficies deben mirarse
+            let cos_surface = hit.normal.dot(&light_dir);
+            let cos_light = -sample.normal.dot(&light_dir);
+            if cos_surface <= 0.0 || cos_light <= 0.0 {
+                continue;
+            }
+            
+            if scene.is_in_shadow(hit.point + hit.normal * 0.001, sample.point, incident_ray.time) {
+                continue;
+            }
+            
+            // Término geométrico dividido por la pdf de área (distancia acotada para evitar picos)
+            let geometry = cos_light / (distance_sq.max(0.01) * sample.pdf);
+            let light_color = sample.emission * geometry;
+            total += surface_color * light_color * cos_surface;
+            
+            if hit.material.specular > 0.0 {
+                let reflect_dir = (-light_dir).reflect(&hit.normal);
+                let spec_strength = view_dir.dot(&reflect_dir).max(0.0)
+                    .powf((1.0 - hit.material.roughness) * 128.0);
+                total += light_color * hit.material.specular * spec_strength;
+            }
         }
+        
+        total / samples as f32
+    }
+    
+    // Atenúa el color por el medio y añade la dispersión simple de las luces
+    fn apply_medium(&self, medium: &Medium, ray: &Ray, t_hit: f32, color: Vec3, scene: &Scene) -> Vec3 {
+        let Some((t0, t1)) = medium.segment(ray, t_hit) else {
+            return color;
+        };
+        
+        let length = t1 - t0;
+        let transmittance = medium.transmittance(length);
+        let sigma_s = medium.scattering();
+        
+        // ∫ T(s) ds sobre el tramo (peso de la luz dispersada)
+        let path_weight = if medium.density > 0.0 {
+            (1.0 - transmittance) / medium.density
+        } else {
+            length
+        };
+        
+        // La luz ambiental también se dispersa (da el color de la niebla)
+        let mut in_scattered = sigma_s * scene.ambient_light * path_weight;
+        
+        match self.volume_mode {
+            VolumeMode::SingleScatterApprox => {
+                // Luces elegidas en el centro del tramo, con su peso de muestreo
+                for (light, weight) in scene.sample_lights(ray.at((t0 + t1) * 0.5)) {
+                    // Punto del tramo más cercano a la luz
+                    let t = (light.position - ray.origin).dot(&ray.direction).clamp(t0, t1);
+                    let point = ray.at(t);
+                    let cos_theta = ray.direction.dot(&light.get_direction_from(point));
+                    in_scattered += sigma_s * light.get_effective_color(point) * (medium.phase(cos_theta) * PHASE_SCALE * path_weight * weight);
+                }
+            }
+            VolumeMode::RayMarched { steps } => {
+                let steps = steps.max(1);
+                let dt = length / steps as f32;
+                let mut rng = Rng::for_point(ray.origin, ray.direction.x.to_bits());
+                let offset = rng.next_f32();
+                
+                for i in 0..steps {
+                    let t = t0 + (i as f32 + offset) * dt;
+                    let point = ray.at(t);
+                    let camera_transmittance = medium.transmittance(t - t0);
+                    
+                    // Mismo muestreo que las superficies: rayos de sombra acotados por paso
+                    for (light, weight) in scene.sample_lights(point) {
+                        // Rayo de sombra: los objetos cortan el haz de luz
+                        if scene.is_in_shadow(point, light.position, ray.time) {
+                            continue;
+                        }
+                        let cos_theta = ray.direction.dot(&light.get_direction_from(point));
+                        in_scattered += sigma_s * light.get_effective_color(point)
+                            * (medium.phase(cos_theta) * PHASE_SCALE * camera_transmittance * dt * weight);
+                    }
+                }
+            }
+        }
+        
+        color * transmittance + in_scattered
     }
     
         // Calcula el color que debe tener un rayo (optimizado)
@@ -101,7 +355,7 @@ impl Raytracer {
         color += scene.ambient_light * surface_color;
         
         // Contribución de todas las luces
-        for (light, shadow_factor) in scene.get_lights_affecting_point(hit.point) {
+        for (light, shadow_factor) in scene.get_lights_affecting_point(hit.point, incident_ray.time) {
             let light_dir = light.get_direction_from(hit.point);
             let light_color = light.get_effective_color(hit.point);
             
@@ -123,24 +377,38 @@ impl Raytracer {
             }
         }
         
+        // Luces de área (objetos emisivos)
+        if !scene.emitters.is_empty() {
+            color += self.emitter_lighting(hit, incident_ray, scene, surface_color);
+        }
+        
+        // Cáusticas del mapa de fotones (solo en la parte difusa de la superficie)
+        if let Some(photon_map) = &scene.photon_map {
+            let diffuse_weight = (1.0 - hit.material.transparency) * (1.0 - hit.material.reflectivity);
+            if diffuse_weight > 0.0 {
+                color += surface_color * photon_map.radiance_estimate(hit.point, hit.normal) * diffuse_weight;
+            }
+        }
+        
         // Reflexión
         if hit.material.reflectivity > 0.0 && depth > 1 {
             let reflected = incident_ray.direction.reflect(&hit.normal);
-            let reflection_ray = Ray::new(hit.point + hit.normal * 0.001, reflected);
+            let reflection_ray = incident_ray.spawn(hit.point + hit.normal * 0.001, reflected);
             let reflection_color = self.ray_color(&reflection_ray, scene, depth - 1);
             color += reflection_color * hit.material.reflectivity;
         }
         
         // Refracción
         if hit.material.transparency > 0.0 && depth > 1 {
+            let refractive_index = hit.material.refractive_index_at(incident_ray.wavelength);
             let refraction_ratio = if hit.front_face {
-                1.0 / hit.material.refractive_index
+                1.0 / refractive_index
             } else {
-                hit.material.refractive_index
+                refractive_index
             };
             
             if let Some(refracted) = incident_ray.direction.refract(&hit.normal, refraction_ratio) {
-                let refraction_ray = Ray::new(hit.point - hit.normal * 0.001, refracted);
+                let refraction_ray = incident_ray.spawn(hit.point - hit.normal * 0.001, refracted);
                 let refraction_color = self.ray_color(&refraction_ray, scene, depth - 1);
                 
                 // Mejor balance entre reflexión y refracción según el ángulo de Fresnel
@@ -162,3 +430,85 @@ impl Raytracer {
         color.clamp(0.0, 1.0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adaptive_config::RenderResolution;
+    use crate::geometry::Cube;
+    use crate::lighting::Light;
+    use crate::material::Material;
+
+    fn raytracer(resolution: RenderResolution) -> Raytracer {
+        let config = AdaptiveConfig { resolution, ..AdaptiveConfig::performance_mode() };
+        Raytracer::with_config(&config, 800, 600)
+    }
+
+    #[test]
+    fn size_comes_from_render_resolution() {
+        let scaled = raytracer(RenderResolution::Scale(50));
+        assert_eq!((scaled.width, scaled.height), (400, 300));
+        let fixed = raytracer(RenderResolution::Fixed(123, 45));
+        assert_eq!((fixed.width, fixed.height), (123, 45));
+    }
+
+    #[test]
+    fn pixel_centers_stay_inside_image() {
+        // Una imagen de un pixel apunta al centro en vez de dividir por cero
+        let single = raytracer(RenderResolution::Fixed(1, 1));
+        assert_eq!(single.pixel_uv(0, 0, 0.0, 0.0), (0.5, 0.5));
+
+        let image = raytracer(RenderResolution::Fixed(4, 2));
+        assert_eq!(image.pixel_uv(0, 0, 0.0, 0.0), (0.125, 0.75));
+        assert_eq!(image.pixel_uv(3, 1, 0.0, 0.0), (0.875, 0.25));
+        // El jitter máximo llega justo a los bordes de la imagen
+        assert_eq!(image.pixel_uv(0, 0, -0.5, -0.5), (0.0, 1.0));
+        assert_eq!(image.pixel_uv(3, 1, 0.5, 0.5), (1.0, 0.0));
+    }
+
+    // Niebla en una caja alrededor del origen, sin luz ambiental
+    fn fog_scene() -> (Scene, Medium) {
+        let mut scene = Scene::new();
+        scene.ambient_light = Vec3::zero();
+        let medium = Medium::new(0.5).with_region(Vec3::new(-1.0, -1.0, -1.0), Vec3::new(1.0, 1.0, 1.0));
+        (scene, medium)
+    }
+
+    fn through_fog() -> Ray {
+        Ray::new(Vec3::new(-5.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0))
+    }
+
+    #[test]
+    fn medium_attenuates_only_inside_region() {
+        let (scene, medium) = fog_scene();
+        let tracer = raytracer(RenderResolution::Fixed(4, 4));
+        let color = Vec3::new(1.0, 0.5, 0.25);
+
+        let fogged = tracer.apply_medium(&medium, &through_fog(), f32::INFINITY, color, &scene);
+        assert!((fogged - color * medium.transmittance(2.0)).length() < 1e-5);
+
+        // Si la superficie está antes de la niebla el color no cambia
+        let blocked = tracer.apply_medium(&medium, &through_fog(), 3.0, color, &scene);
+        assert!((blocked - color).length() < 1e-6);
+    }
+
+    #[test]
+    fn ray_marched_medium_respects_shadows() {
+        let (mut scene, medium) = fog_scene();
+        scene.add_light(Light::new(Vec3::new(0.0, 5.0, 0.0), Vec3::one(), 1.0));
+        let mut tracer = raytracer(RenderResolution::Fixed(4, 4));
+        tracer.volume_mode = VolumeMode::RayMarched { steps: 8 };
+        let lit = tracer.apply_medium(&medium, &through_fog(), f32::INFINITY, Vec3::zero(), &scene);
+        assert!(lit.x > 0.0);
+
+        // Un techo entre la luz y la niebla bloquea los haces
+        scene.add_object(Cube::new(Vec3::new(0.0, 3.0, 0.0), Vec3::new(10.0, 0.2, 10.0), Material::new()));
+        let shadowed = tracer.apply_medium(&medium, &through_fog(), f32::INFINITY, Vec3::zero(), &scene);
+        assert!(shadowed.length() < 1e-6);
+
+        // La aproximación barata no traza sombras
+        tracer.volume_mode = VolumeMode::SingleScatterApprox;
+        let approx = tracer.apply_medium(&medium, &through_fog(), f32::INFINITY, Vec3::zero(), &scene);
+        assert!(approx.x > 0.0);
+    }
+}
@@ -0,0 +1,173 @@
+// Renderizado por bloques (tiles): división de la imagen y orden de procesamiento
+
+use crate::framebuffer::Framebuffer;
+use crate::math::Vec3;
+use std::str::FromStr;
+
+// Rectángulo de pixels de la imagen
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tile {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Tile {
+    pub fn pixel_count(&self) -> usize {
+        (self.width * self.height) as usize
+    }
+}
+
+// Orden en que se reparten los tiles entre los hilos
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TileOrder {
+    // Filas de arriba abajo
+    Scanline,
+    // Desde el centro de la imagen hacia afuera (lo importante aparece primero)
+    #[default]
+    Spiral,
+    // Curva de Hilbert: tiles consecutivos siempre vecinos (mejor localidad de caché)
+    Hilbert,
+}
+
+impl TileOrder {
+    // Divide una imagen de width x height en tiles de tile_size (los del borde pueden ser
+    // menores) y los devuelve en este orden
+    pub fn tiles(&self, width: u32, height: u32, tile_size: u32) -> Vec<Tile> {
+        let tile_size = tile_size.max(1);
+        let columns = width.div_ceil(tile_size);
+        let rows = height.div_ceil(tile_size);
+
+        let mut cells: Vec<(u32, u32)> = (0..rows)
+            .flat_map(|row| (0..columns).map(move |column| (column, row)))
+            .collect();
+
+        match self {
+            TileOrder::Scanline => {}
+            TileOrder::Spiral => {
+                // Anillos cuadrados alrededor del centro, cada uno recorrido por ángulo
+                let center_x = (columns as f32 - 1.0) * 0.5;
+                let center_y = (rows as f32 - 1.0) * 0.5;
+                let key = |&(column, row): &(u32, u32)| {
+                    let dx = column as f32 - center_x;
+                    let dy = row as f32 - center_y;
+                    (dx.abs().max(dy.abs()), dy.atan2(dx))
+                };
+                cells.sort_by(|a, b| key(a).partial_cmp(&key(b)).unwrap_or(std::cmp::Ordering::Equal));
+            }
+            TileOrder::Hilbert => {
+                let side = columns.max(rows).next_power_of_two();
+                cells.sort_by_key(|&(column, row)| hilbert_index(side, column, row));
+            }
+        }
+
+        cells
+            .into_iter()
+            .map(|(column, row)| {
+                let x = column * tile_size;
+                let y = row * tile_size;
+                Tile {
+                    x,
+                    y,
+                    width: tile_size.min(width - x),
+                    height: tile_size.min(height - y),
+                }
+            })
+            .collect()
+    }
+}
+
+impl FromStr for TileOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "scanline" => Ok(TileOrder::Scanline),
+            "spiral" => Ok(TileOrder::Spiral),
+            "hilbert" => Ok(TileOrder::Hilbert),
+            other => Err(format!("orden de tiles desconocido '{}'", other)),
+        }
+    }
+}
+
+// Posición de la celda (x, y) a lo largo de la curva de Hilbert de una rejilla side x side
+// (side potencia de dos)
+fn hilbert_index(side: u32, mut x: u32, mut y: u32) -> u64 {
+    let mut index = 0u64;
+    let mut s = side / 2;
+    while s > 0 {
+        let rx = u32::from(x & s > 0);
+        let ry = u32::from(y & s > 0);
+        index += s as u64 * s as u64 * ((3 * rx) ^ ry) as u64;
+        // Rotar el cuadrante para que la curva siga siendo continua
+        if ry == 0 {
+            if rx == 1 {
+                x = side - 1 - x;
+                y = side - 1 - y;
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+        s /= 2;
+    }
+    index
+}
+
+// Colores de un tile terminado (por filas)
+#[derive(Debug, Clone)]
+pub struct TileResult {
+    pub tile: Tile,
+    pub pixels: Vec<Vec3>,
+}
+
+impl TileResult {
+    // Copia el tile a su lugar en el framebuffer
+    pub fn write_to(&self, framebuffer: &mut Framebuffer) {
+        for (idx, &color) in self.pixels.iter().enumerate() {
+            let x = self.tile.x + idx as u32 % self.tile.width;
+            let y = self.tile.y + idx as u32 / self.tile.width;
+            framebuffer.set_pixel_from_vec3(x, y, color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn covers_image_once(order: TileOrder, width: u32, height: u32, tile_size: u32) {
+        let mut hits = vec![0u32; (width * height) as usize];
+        for tile in order.tiles(width, height, tile_size) {
+            for y in tile.y..tile.y + tile.height {
+                for x in tile.x..tile.x + tile.width {
+                    hits[(y * width + x) as usize] += 1;
+                }
+            }
+        }
+        assert!(hits.iter().all(|&count| count == 1), "{:?}", order);
+    }
+
+    #[test]
+    fn every_order_covers_each_pixel_once() {
+        for order in [TileOrder::Scanline, TileOrder::Spiral, TileOrder::Hilbert] {
+            covers_image_once(order, 100, 70, 32);
+            covers_image_once(order, 64, 64, 16);
+        }
+    }
+
+    #[test]
+    fn spiral_starts_at_center() {
+        let first = TileOrder::Spiral.tiles(160, 160, 32)[0];
+        assert_eq!((first.x, first.y), (64, 64));
+    }
+
+    #[test]
+    fn hilbert_tiles_are_neighbours() {
+        let tiles = TileOrder::Hilbert.tiles(128, 128, 16);
+        for pair in tiles.windows(2) {
+            let dx = pair[0].x.abs_diff(pair[1].x);
+            let dy = pair[0].y.abs_diff(pair[1].y);
+            assert_eq!(dx + dy, 16);
+        }
+    }
+}
@@ -2,20 +2,50 @@
 
 use crate::math::{Vec3, Ray};
 use crate::geometry::{Cube, HittableList, HitRecord, Hittable};
-use crate::lighting::Light;
+use crate::lighting::{Light, Emitter, EmitterList, LightSampling, LightDistribution};
+use crate::lighting::light_sampler::{luminance, Reservoir};
+use crate::math::Rng;
+use crate::photon::{PhotonConfig, PhotonMap};
+use crate::volume::Medium;
+
+use crate::animation::Timeline;
+use crate::camera::Camera;
+
+use std::collections::HashMap;
+use std::sync::Arc;
 
 const EPSILON: f32 = 0.001;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Scene {
     // Lista de todos los objetos en la escena
     pub objects: HittableList,
-    // Lista de luces en la escena
-    pub lights: Vec<Light>,
+    // Lista de luces en la escena (privada: cada cambio recalcula `light_distribution`)
+    lights: Vec<Light>,
+    // Estrategia de selección de luces por punto sombreado
+    pub light_sampling: LightSampling,
+    // Distribución de potencia de `lights`
+    light_distribution: LightDistribution,
+    // Objetos emisivos que iluminan como luces de área
+    pub emitters: EmitterList,
+    // Muestras de luz de área por punto sombreado
+    pub emitter_samples: u32,
     // Color de fondo de la escena
     pub background_color: Vec3,
     // Luz ambiental global
     pub ambient_light: Vec3,
+    // Mapa de fotones para cáusticas (None = desactivado)
+    pub photon_map: Option<PhotonMap>,
+    // Configuración con la que se construyó el mapa (para reconstruirlo al animar)
+    photon_config: Option<PhotonConfig>,
+    // Medios participantes (niebla global o por región)
+    pub media: Vec<Medium>,
+    // Objetos con nombre (índice en `objects` y objeto original) para animarlos
+    named_objects: HashMap<String, (usize, Arc<dyn Hittable + Send + Sync>)>,
+    // Animación asociada a la escena (vacía si es estática)
+    pub timeline: Timeline,
+    // Cámara definida por la escena (None = la aplicación decide)
+    pub camera: Option<Camera>,
 }
 
 impl Scene {
@@ -24,8 +54,18 @@ impl Scene {
         Scene {
             objects: HittableList::new(),
             lights: Vec::new(),
+            light_sampling: LightSampling::PowerProportional { samples: 8 },
+            light_distribution: LightDistribution::default(),
+            emitters: EmitterList::new(),
+            emitter_samples: 4,
             background_color: Vec3::new(0.1, 0.1, 0.2), 
             ambient_light: Vec3::new(0.1, 0.1, 0.1),     
+            photon_map: None,
+            photon_config: None,
+            media: Vec::new(),
+            named_objects: HashMap::new(),
+            timeline: Timeline::new(),
+            camera: None,
         }
     }
     
@@ -36,12 +76,112 @@ impl Scene {
     
     // Añade un cubo a la escena
     pub fn add_cube(&mut self, cube: Cube) {
-        self.objects.add(cube);
+        self.add_object(cube);
+    }
+    
+    // Añade cualquier objeto; si su material emite luz se registra como luz de área
+    pub fn add_object<T: Hittable + 'static>(&mut self, object: T) {
+        self.add_shared_object(Arc::new(object));
+    }
+    
+    // Añade un objeto compartido (p. ej. el mismo objeto en otra escena)
+    pub fn add_shared_object(&mut self, object: Arc<dyn Hittable + Send + Sync>) {
+        if let Some(emitter) = Emitter::from_object(object.clone()) {
+            self.emitters.add(emitter);
+        }
+        self.objects.add_shared(object);
+    }
+    
+    // Añade un objeto que la línea de tiempo puede reemplazar por nombre
+    pub fn add_named_object(&mut self, name: &str, object: Arc<dyn Hittable + Send + Sync>) {
+        self.named_objects.insert(name.to_string(), (self.objects.len(), object.clone()));
+        self.add_shared_object(object);
+    }
+    
+    // Objeto original registrado con `add_named_object`
+    pub fn named_object(&self, name: &str) -> Option<Arc<dyn Hittable + Send + Sync>> {
+        self.named_objects.get(name).map(|(_, object)| object.clone())
+    }
+    
+    // Reemplaza el objeto con nombre en la escena (el original se conserva) y
+    // reconstruye las luces de área por si cambió un material emisivo
+    pub fn replace_named_object(&mut self, name: &str, object: Arc<dyn Hittable + Send + Sync>) {
+        let Some(&(index, _)) = self.named_objects.get(name) else {
+            return;
+        };
+        self.objects.set(index, object);
+        self.emitters = EmitterList::new();
+        for object in self.objects.iter() {
+            if let Some(emitter) = Emitter::from_object(object.clone()) {
+                self.emitters.add(emitter);
+            }
+        }
+    }
+    
+    // Aplica la animación de la escena en un instante (cámara, luces y objetos)
+    pub fn animate(&mut self, time: f32, camera: &mut Camera) {
+        let timeline = std::mem::take(&mut self.timeline);
+        timeline.apply(time, self, camera);
+        self.timeline = timeline;
     }
     
     // Añade una luz a la escena
     pub fn add_light(&mut self, light: Light) {
         self.lights.push(light);
+        self.update_light_distribution();
+    }
+    
+    // Añade una tira de `count` lámparas pequeñas entre dos puntos
+    pub fn add_light_string(&mut self, start: Vec3, end: Vec3, count: u32, color: Vec3, intensity: f32) {
+        for i in 0..count {
+            let t = if count > 1 { i as f32 / (count - 1) as f32 } else { 0.5 };
+            self.lights.push(Light::new(start.lerp(&end, t), color, intensity));
+        }
+        self.update_light_distribution();
+    }
+    
+    // Luces de la escena
+    pub fn lights(&self) -> &[Light] {
+        &self.lights
+    }
+    
+    // Modifica la luz `index` y recalcula la distribución de potencia.
+    // Devuelve false si la luz no existe
+    pub fn update_light(&mut self, index: usize, update: impl FnOnce(&mut Light)) -> bool {
+        let Some(light) = self.lights.get_mut(index) else {
+            return false;
+        };
+        update(light);
+        self.update_light_distribution();
+        true
+    }
+    
+    fn update_light_distribution(&mut self) {
+        self.light_distribution = LightDistribution::build(&self.lights);
+    }
+    
+    // Añade un medio participante a la escena
+    pub fn add_medium(&mut self, medium: Medium) {
+        self.media.push(medium);
+    }
+    
+    // Ejecuta el pre-paso de fotones; debe llamarse después de añadir objetos y luces
+    pub fn build_photon_map(&mut self, config: &PhotonConfig) {
+        self.photon_map = Some(PhotonMap::build(self, config));
+        self.photon_config = Some(config.clone());
+    }
+    
+    // Vuelve a emitir los fotones con la última configuración en el instante `time`
+    // (tras mover luces u objetos, o con geometría que cambia con el tiempo)
+    pub fn rebuild_photon_map(&mut self, time: f32) {
+        if let Some(config) = self.photon_config.take() {
+            self.build_photon_map(&config.with_time(time));
+        }
+    }
+    
+    // Si las cáusticas cambian de un instante a otro (línea de tiempo u objetos como el agua)
+    pub fn has_time_dependent_photons(&self) -> bool {
+        self.photon_map.is_some() && (self.timeline.animates_scene() || self.objects.is_time_dependent())
     }
     
     // Verifica si un rayo intersecta algún objeto de la escena
@@ -159,11 +299,12 @@ impl Scene {
         (n - n.floor()) * 2.0 - 1.0
     }
     
-    // Verifica si hay una línea de vista clara entre dos puntos (para sombras)
-    pub fn is_in_shadow(&self, from: Vec3, to: Vec3) -> bool {
+    // Verifica si hay una línea de vista clara entre dos puntos (para sombras).
+    // `time` es el instante del rayo que originó la consulta (objetos en movimiento)
+    pub fn is_in_shadow(&self, from: Vec3, to: Vec3, time: f32) -> bool {
         let direction = to - from;
         let distance = direction.length();
-        let ray = Ray::new(from, direction.normalize());
+        let ray = Ray::new(from, direction.normalize()).with_time(time);
         
         // Verifica si hay algún objeto entre los dos puntos
         if let Some(hit) = self.hit(&ray, EPSILON, distance - EPSILON) {
@@ -174,21 +315,181 @@ impl Scene {
         }
     }
     
-    // Obtiene todas las luces que afectan un punto (excluyendo las que están en sombra)
-    pub fn get_lights_affecting_point(&self, point: Vec3) -> Vec<(&Light, f32)> {
-        let mut affecting_lights = Vec::new();
-        
-        for light in &self.lights {
-            // Para luces puntuales, verifica si hay sombra
-            let shadow_factor = if self.is_in_shadow(point, light.position) {
-                0.3 // Sombra parcial
-            } else {
-                1.0 // Sin sombra
-            };
-            
-            affecting_lights.push((light, shadow_factor));
+    // Factor de sombra de una luz puntual
+    fn shadow_factor(&self, point: Vec3, light: &Light, time: f32) -> f32 {
+        if self.is_in_shadow(point, light.position, time) {
+            0.3 // Sombra parcial
+        } else {
+            1.0 // Sin sombra
         }
+    }
+    
+    // Obtiene las luces que afectan un punto junto con su factor (sombra × peso de muestreo).
+    // Según `light_sampling`, el número de rayos de sombra queda acotado aunque haya cientos de luces
+    pub fn get_lights_affecting_point(&self, point: Vec3, time: f32) -> Vec<(&Light, f32)> {
+        self.sample_lights(point)
+            .into_iter()
+            .map(|(light, weight)| (light, self.shadow_factor(point, light, time) * weight))
+            .collect()
+    }
+    
+    // Luces elegidas para un punto según `light_sampling` con su peso de Monte Carlo, sin
+    // rayos de sombra (para quien resuelve la visibilidad a su manera, como los medios)
+    pub fn sample_lights(&self, point: Vec3) -> Vec<(&Light, f32)> {
+        let mut rng = Rng::for_point(point, self.lights.len() as u32);
         
-        affecting_lights
+        match self.light_sampling {
+            LightSampling::PowerProportional { samples } if (samples as usize) < self.lights.len() => {
+                // Estimador de Monte Carlo: cada muestra pesa 1 / (N · pdf)
+                let samples = samples.max(1);
+                (0..samples)
+                    .filter_map(|_| self.light_distribution.sample(&mut rng))
+                    .map(|(index, pdf)| (&self.lights[index], 1.0 / (samples as f32 * pdf)))
+                    .collect()
+            }
+            LightSampling::Reservoir { candidates } if self.lights.len() > 1 => {
+                // RIS: candidatas por potencia, objetivo = contribución sin sombra en el punto
+                let mut reservoir = Reservoir::new();
+                for _ in 0..candidates.max(1) {
+                    if let Some((index, pdf)) = self.light_distribution.sample(&mut rng) {
+                        let target = luminance(self.lights[index].get_effective_color(point));
+                        reservoir.update(index, target / pdf, target, &mut rng);
+                    }
+                }
+                
+                // Una sola luz elegida (un solo rayo de sombra)
+                reservoir
+                    .selected
+                    .map(|index| (&self.lights[index], reservoir.contribution_weight()))
+                    .into_iter()
+                    .collect()
+            }
+            _ => self.lights.iter().map(|light| (light, 1.0)).collect(),
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::material::Material;
+
+    fn cube_at(x: f32, material: Material) -> Arc<dyn Hittable + Send + Sync> {
+        Arc::new(Cube::new(Vec3::new(x, 0.0, 0.0), Vec3::one(), material))
+    }
+
+    #[test]
+    fn replace_named_object_keeps_index_and_rebuilds_emitters() {
+        let mut scene = Scene::new();
+        scene.add_shared_object(cube_at(-3.0, Material::new()));
+        let original = cube_at(0.0, Material::new());
+        scene.add_named_object("caja", original.clone());
+        scene.add_shared_object(cube_at(3.0, Material::new()));
+        assert!(scene.emitters.is_empty());
+
+        let glowing = cube_at(0.0, Material::new().with_emission(Vec3::one()));
+        scene.replace_named_object("caja", glowing.clone());
+        assert_eq!(scene.objects.len(), 3);
+        assert!(Arc::ptr_eq(&scene.objects.get(1).unwrap(), &glowing));
+        assert_eq!(scene.emitters.len(), 1);
+
+        // El original se conserva para la siguiente evaluación de la línea de tiempo
+        assert!(Arc::ptr_eq(&scene.named_object("caja").unwrap(), &original));
+        scene.replace_named_object("caja", original);
+        assert!(scene.emitters.is_empty());
+
+        // Un nombre desconocido no modifica la escena
+        scene.replace_named_object("otro", glowing);
+        assert!(scene.emitters.is_empty());
+    }
+
+    // Promedio, sobre muchos puntos, de la suma de pesos y de la contribución estimada
+    fn average_estimates(scene: &Scene) -> (f32, f32, f32) {
+        let (mut weights, mut estimate, mut exact) = (0.0, 0.0, 0.0);
+        let points = 4000;
+        for i in 0..points {
+            let point = Vec3::new((i % 80) as f32 * 0.1 - 4.0, 0.0, (i / 80) as f32 * 0.1 - 2.5);
+            for (light, weight) in scene.get_lights_affecting_point(point, 0.0) {
+                weights += weight;
+                estimate += weight * luminance(light.get_effective_color(point));
+            }
+            exact += scene.lights().iter().map(|l| luminance(l.get_effective_color(point))).sum::<f32>();
+        }
+        let n = points as f32;
+        (weights / n, estimate / n, exact / n)
+    }
+
+    #[test]
+    fn light_sampling_weights_are_unbiased() {
+        let mut scene = Scene::new();
+        for i in 0..6 {
+            let position = Vec3::new(i as f32 - 2.5, 2.0 + (i % 2) as f32, (i % 3) as f32 - 1.0);
+            let color = Vec3::new(1.0, 0.3 + 0.1 * i as f32, 0.5);
+            scene.add_light(Light::new(position, color, 0.5 + i as f32));
+        }
+
+        for sampling in [
+            LightSampling::PowerProportional { samples: 2 },
+            LightSampling::Reservoir { candidates: 4 },
+        ] {
+            scene.light_sampling = sampling;
+            let (weights, estimate, exact) = average_estimates(&scene);
+            // E[Σ w] = número de luces y E[Σ w·f] = Σ f
+            assert!((weights - 6.0).abs() < 0.3, "{:?}: {}", sampling, weights);
+            assert!((estimate - exact).abs() < 0.05 * exact, "{:?}: {} vs {}", sampling, estimate, exact);
+        }
+
+        // Una luz apagada que se enciende debe entrar en la distribución de potencia
+        // (con la distribución vieja nunca se elegiría y la estimación quedaría corta)
+        scene.add_light(Light::new(Vec3::new(0.0, 2.0, 0.0), Vec3::one(), 0.0));
+        assert!(scene.update_light(6, |light| light.intensity = 30.0));
+        assert!(!scene.update_light(9, |light| light.intensity = 1.0));
+        scene.light_sampling = LightSampling::PowerProportional { samples: 2 };
+        let (_, estimate, exact) = average_estimates(&scene);
+        assert!((estimate - exact).abs() < 0.05 * exact, "{} vs {}", estimate, exact);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn waves_make_photons_time_dependent() {
+        use crate::geometry::{Moving, Motion, WaterSurface};
+        let config = PhotonConfig::new().with_photon_count(100);
+        let scene_with = |object: Arc<dyn Hittable + Send + Sync>| {
+            let mut scene = Scene::new();
+            scene.add_light(Light::new(Vec3::new(0.0, 3.0, 0.0), Vec3::one(), 1.0));
+            scene.add_shared_object(object);
+            scene
+        };
+        let water = |waves: u32| WaterSurface::new(Vec3::zero(), Vec3::one(), Material::new()).with_waves(waves, 0.01, 2.0, 0.2);
+
+        let mut calm = scene_with(Arc::new(water(0)));
+        assert!(!calm.has_time_dependent_photons());
+        calm.build_photon_map(&config);
+        assert!(!calm.has_time_dependent_photons());
+
+        let mut waving = scene_with(Arc::new(water(3)));
+        // Sin mapa de fotones no hay nada que reconstruir
+        assert!(!waving.has_time_dependent_photons());
+        waving.build_photon_map(&config);
+        assert!(waving.has_time_dependent_photons());
+
+        let mut moving = scene_with(Arc::new(Moving::from_object(water(0), Motion::linear(Vec3::one()))));
+        moving.build_photon_map(&config);
+        assert!(moving.has_time_dependent_photons());
+    }
+
+    #[test]
+    fn sample_lights_bounds_lights_per_point() {
+        let mut scene = Scene::new();
+        scene.add_light_string(Vec3::new(-5.0, 2.0, 0.0), Vec3::new(5.0, 2.0, 0.0), 100, Vec3::one(), 1.0);
+        let point = Vec3::new(0.5, 0.0, 0.0);
+
+        scene.light_sampling = LightSampling::PowerProportional { samples: 8 };
+        assert_eq!(scene.sample_lights(point).len(), 8);
+        scene.light_sampling = LightSampling::Reservoir { candidates: 16 };
+        assert_eq!(scene.sample_lights(point).len(), 1);
+
+        // Los mismos pesos que con sombras cuando nada bloquea las luces
+        let sampled: Vec<f32> = scene.sample_lights(point).iter().map(|(_, w)| *w).collect();
+        let shaded: Vec<f32> = scene.get_lights_affecting_point(point, 0.0).iter().map(|(_, w)| *w).collect();
+        assert_eq!(sampled, shaded);
+    }
+}
@@ -0,0 +1,31 @@
+// Funciones de igualación de color CIE 1931 y conversión a RGB
+
+use crate::math::Vec3;
+
+// Gaussiana asimétrica usada por el ajuste analítico
+fn lobe(x: f32, mu: f32, sigma_low: f32, sigma_high: f32) -> f32 {
+    let sigma = if x < mu { sigma_low } else { sigma_high };
+    let t = (x - mu) / sigma;
+    (-0.5 * t * t).exp()
+}
+
+// Observador estándar CIE 1931 (ajuste multi-lóbulo de Wyman, Sloan y Shirley)
+pub fn cie_xyz(lambda: f32) -> Vec3 {
+    let x = 1.056 * lobe(lambda, 599.8, 37.9, 31.0)
+        + 0.362 * lobe(lambda, 442.0, 16.0, 26.7)
+        - 0.065 * lobe(lambda, 501.1, 20.4, 26.2);
+    let y = 0.821 * lobe(lambda, 568.8, 46.9, 40.5)
+        + 0.286 * lobe(lambda, 530.9, 16.3, 31.1);
+    let z = 1.217 * lobe(lambda, 437.0, 11.8, 36.0)
+        + 0.681 * lobe(lambda, 459.0, 26.0, 13.8);
+    Vec3::new(x, y, z)
+}
+
+// CIE XYZ -> sRGB lineal (iluminante D65)
+pub fn xyz_to_linear_rgb(xyz: Vec3) -> Vec3 {
+    Vec3::new(
+        3.2406 * xyz.x - 1.5372 * xyz.y - 0.4986 * xyz.z,
+        -0.9689 * xyz.x + 1.8758 * xyz.y + 0.0415 * xyz.z,
+        0.0557 * xyz.x - 0.2040 * xyz.y + 1.0570 * xyz.z,
+    )
+}
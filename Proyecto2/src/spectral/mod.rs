@@ -0,0 +1,171 @@
+// Renderizado espectral: muestreo de longitudes de onda y conversión a RGB
+
+pub mod cie;
+
+pub use cie::{cie_xyz, xyz_to_linear_rgb};
+
+use crate::math::Vec3;
+use std::sync::OnceLock;
+
+// Rango visible que se muestrea (nanómetros)
+pub const LAMBDA_MIN: f32 = 380.0;
+pub const LAMBDA_MAX: f32 = 730.0;
+
+// Convierte una muestra uniforme en [0,1) a longitud de onda
+pub fn sample_wavelength(u: f32) -> f32 {
+    LAMBDA_MIN + (LAMBDA_MAX - LAMBDA_MIN) * u
+}
+
+// Funciones base suaves (rojo, verde, azul) que suman 1 en todo el rango,
+// de modo que un RGB blanco se convierte en un espectro constante
+fn basis(lambda: f32) -> Vec3 {
+    let sigmoid = |x: f32| 1.0 / (1.0 + (-x).exp());
+    let blue = 1.0 - sigmoid((lambda - 490.0) / 10.0);
+    let red = sigmoid((lambda - 590.0) / 10.0);
+    let green = 1.0 - blue - red;
+    Vec3::new(red, green, blue)
+}
+
+// Valor espectral de un color RGB en una longitud de onda
+pub fn rgb_to_spectral(rgb: Vec3, lambda: f32) -> f32 {
+    rgb.dot(&basis(lambda))
+}
+
+// Respuesta RGB de una muestra espectral unitaria en una longitud de onda
+fn rgb_response(lambda: f32) -> Vec3 {
+    xyz_to_linear_rgb(cie_xyz(lambda))
+}
+
+// Matriz de corrección: hace que un color RGB sin dispersión vuelva a sí mismo
+// tras pasar por espectro -> CIE XYZ -> RGB
+fn correction_matrix() -> &'static [[f32; 3]; 3] {
+    static MATRIX: OnceLock<[[f32; 3]; 3]> = OnceLock::new();
+    MATRIX.get_or_init(|| {
+        // m[i][j] = ∫ respuesta_i(λ) · base_j(λ) dλ
+        let mut m = [[0.0f32; 3]; 3];
+        let mut lambda = LAMBDA_MIN;
+        while lambda <= LAMBDA_MAX {
+            let response = rgb_response(lambda);
+            let b = basis(lambda);
+            let r = [response.x, response.y, response.z];
+            let bv = [b.x, b.y, b.z];
+            for i in 0..3 {
+                for j in 0..3 {
+                    m[i][j] += r[i] * bv[j];
+                }
+            }
+            lambda += 1.0;
+        }
+        invert_3x3(&m)
+    })
+}
+
+fn invert_3x3(m: &[[f32; 3]; 3]) -> [[f32; 3]; 3] {
+    let det = m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+    let inv_det = 1.0 / det;
+    [
+        [
+            (m[1][1] * m[2][2] - m[1][2] * m[2][1]) * inv_det,
+            (m[0][2] * m[2][1] - m[0][1] * m[2][2]) * inv_det,
+            (m[0][1] * m[1][2] - m[0][2] * m[1][1]) * inv_det,
+        ],
+        [
+            (m[1][2] * m[2][0] - m[1][0] * m[2][2]) * inv_det,
+            (m[0][0] * m[2][2] - m[0][2] * m[2][0]) * inv_det,
+            (m[0][2] * m[1][0] - m[0][0] * m[1][2]) * inv_det,
+        ],
+        [
+            (m[1][0] * m[2][1] - m[1][1] * m[2][0]) * inv_det,
+            (m[0][1] * m[2][0] - m[0][0] * m[2][1]) * inv_det,
+            (m[0][0] * m[1][1] - m[0][1] * m[1][0]) * inv_det,
+        ],
+    ]
+}
+
+// Acumula muestras espectrales de un pixel y las convierte a RGB
+#[derive(Debug, Clone, Copy)]
+pub struct SpectralAccumulator {
+    sum: Vec3,
+    count: u32,
+}
+
+impl SpectralAccumulator {
+    pub fn new() -> Self {
+        SpectralAccumulator {
+            sum: Vec3::zero(),
+            count: 0,
+        }
+    }
+
+    // Añade la radiancia RGB trazada con un rayo de longitud de onda `lambda`
+    pub fn add_sample(&mut self, lambda: f32, radiance: Vec3) {
+        self.sum += rgb_response(lambda) * rgb_to_spectral(radiance, lambda);
+        self.count += 1;
+    }
+
+    // Color RGB lineal final (estimador de Monte Carlo con pdf uniforme)
+    pub fn to_rgb(&self) -> Vec3 {
+        if self.count == 0 {
+            return Vec3::zero();
+        }
+        // El paso de integración de la matriz es 1nm, así que se escala por el ancho del rango
+        let rgb = self.sum * ((LAMBDA_MAX - LAMBDA_MIN) / self.count as f32);
+        let m = correction_matrix();
+        Vec3::new(
+            m[0][0] * rgb.x + m[0][1] * rgb.y + m[0][2] * rgb.z,
+            m[1][0] * rgb.x + m[1][1] * rgb.y + m[1][2] * rgb.z,
+            m[2][0] * rgb.x + m[2][1] * rgb.y + m[2][2] * rgb.z,
+        )
+        .clamp(0.0, 1.0)
+    }
+}
+
+impl Default for SpectralAccumulator {
+    fn default() -> Self {
+        SpectralAccumulator::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flat_spectrum_is_equal_energy_white() {
+        // Un espectro constante integra a X = Y = Z (iluminante E) con el ajuste CIE
+        let mut xyz = Vec3::zero();
+        let mut lambda = LAMBDA_MIN;
+        while lambda <= LAMBDA_MAX {
+            xyz += cie_xyz(lambda);
+            lambda += 1.0;
+        }
+        assert!((xyz.x / xyz.y - 1.0).abs() < 0.02, "{:?}", xyz);
+        assert!((xyz.z / xyz.y - 1.0).abs() < 0.02, "{:?}", xyz);
+    }
+
+    #[test]
+    fn white_becomes_flat_spectrum() {
+        for lambda in [400.0, 490.0, 555.0, 590.0, 700.0] {
+            assert!((rgb_to_spectral(Vec3::one(), lambda) - 1.0).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn accumulated_flat_spectrum_maps_to_neutral_rgb() {
+        for gray in [1.0, 0.5] {
+            let mut accumulator = SpectralAccumulator::default();
+            let samples = 350;
+            for i in 0..samples {
+                let lambda = sample_wavelength((i as f32 + 0.5) / samples as f32);
+                accumulator.add_sample(lambda, Vec3::new(gray, gray, gray));
+            }
+            let rgb = accumulator.to_rgb();
+            for channel in [rgb.x, rgb.y, rgb.z] {
+                assert!((channel - gray).abs() < 0.03 * gray, "{}: {:?}", gray, rgb);
+            }
+        }
+        assert_eq!(SpectralAccumulator::new().to_rgb().length(), 0.0);
+    }
+}
@@ -0,0 +1,161 @@
+// Medio participante homogéneo (niebla, vapor) con función de fase Henyey-Greenstein
+
+use crate::math::{Vec3, Ray};
+
+// Distancia máxima que recorre la niebla cuando el rayo no golpea nada
+const MAX_FOG_DISTANCE: f32 = 20.0;
+
+#[derive(Debug, Clone)]
+pub struct Medium {
+    // Coeficiente de extinción (densidad del medio)
+    pub density: f32,
+    // Albedo de dispersión (fracción de la extinción que se dispersa, por canal)
+    pub albedo: Vec3,
+    // Anisotropía de Henyey-Greenstein (-1 = hacia atrás, 0 = isotrópico, 1 = hacia adelante)
+    pub anisotropy: f32,
+    // Región (min, max) donde existe el medio; None = toda la escena
+    pub region: Option<(Vec3, Vec3)>,
+}
+
+impl Medium {
+    // Crea un medio global con la densidad dada
+    pub fn new(density: f32) -> Self {
+        Medium {
+            density: density.max(0.0),
+            albedo: Vec3::new(0.9, 0.9, 0.9),
+            anisotropy: 0.0,
+            region: None,
+        }
+    }
+
+    // Builder pattern para configurar el albedo
+    pub fn with_albedo(mut self, albedo: Vec3) -> Self {
+        self.albedo = albedo.clamp(0.0, 1.0);
+        self
+    }
+
+    // Builder pattern para configurar la anisotropía de la función de fase
+    pub fn with_anisotropy(mut self, anisotropy: f32) -> Self {
+        self.anisotropy = anisotropy.clamp(-0.99, 0.99);
+        self
+    }
+
+    // Builder pattern para limitar el medio a una caja
+    pub fn with_region(mut self, min: Vec3, max: Vec3) -> Self {
+        self.region = Some((min, max));
+        self
+    }
+
+    // Coeficiente de dispersión por canal
+    pub fn scattering(&self) -> Vec3 {
+        self.albedo * self.density
+    }
+
+    // Transmitancia de Beer-Lambert para una distancia
+    pub fn transmittance(&self, distance: f32) -> f32 {
+        (-self.density * distance).exp()
+    }
+
+    // Función de fase Henyey-Greenstein (cos_theta entre dirección del rayo y dirección hacia la luz)
+    pub fn phase(&self, cos_theta: f32) -> f32 {
+        let g = self.anisotropy;
+        let denom = (1.0 + g * g - 2.0 * g * cos_theta).max(1e-4);
+        (1.0 - g * g) / (4.0 * std::f32::consts::PI * denom * denom.sqrt())
+    }
+
+    // Tramo [t0, t1] del rayo que está dentro del medio antes de `t_max`
+    pub fn segment(&self, ray: &Ray, t_max: f32) -> Option<(f32, f32)> {
+        let t_max = t_max.min(MAX_FOG_DISTANCE);
+        let (mut t0, mut t1) = (0.0f32, t_max);
+
+        if let Some((min, max)) = self.region {
+            // Intersección rayo-caja por planos
+            let origin = [ray.origin.x, ray.origin.y, ray.origin.z];
+            let direction = [ray.direction.x, ray.direction.y, ray.direction.z];
+            let min = [min.x, min.y, min.z];
+            let max = [max.x, max.y, max.z];
+            for axis in 0..3 {
+                let inv_dir = 1.0 / direction[axis];
+                let mut near = (min[axis] - origin[axis]) * inv_dir;
+                let mut far = (max[axis] - origin[axis]) * inv_dir;
+                if inv_dir < 0.0 {
+                    std::mem::swap(&mut near, &mut far);
+                }
+                t0 = t0.max(near);
+                t1 = t1.min(far);
+            }
+        }
+
+        if t1 > t0 { Some((t0, t1)) } else { None }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    // ∫ phase dω sobre la esfera, integrando en cos_theta (regla del punto medio)
+    fn integrate_phase(medium: &Medium) -> f32 {
+        let steps = 20000;
+        let d_cos = 2.0 / steps as f32;
+        (0..steps)
+            .map(|i| {
+                let cos_theta = -1.0 + (i as f32 + 0.5) * d_cos;
+                2.0 * PI * medium.phase(cos_theta) * d_cos
+            })
+            .sum()
+    }
+
+    #[test]
+    fn phase_integrates_to_one() {
+        for g in [0.0, 0.3, 0.7, -0.5] {
+            let medium = Medium::new(1.0).with_anisotropy(g);
+            assert!((integrate_phase(&medium) - 1.0).abs() < 1e-3, "g = {}", g);
+        }
+        // Dispersión hacia adelante favorece cos_theta = 1
+        let forward = Medium::new(1.0).with_anisotropy(0.6);
+        assert!(forward.phase(1.0) > forward.phase(-1.0));
+        assert!((Medium::new(1.0).phase(0.3) - 1.0 / (4.0 * PI)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn transmittance_follows_beer_lambert() {
+        let medium = Medium::new(0.4);
+        assert_eq!(medium.transmittance(0.0), 1.0);
+        assert!((medium.transmittance(2.5) - (-1.0f32).exp()).abs() < 1e-6);
+        // Tramos consecutivos se multiplican
+        let split = medium.transmittance(1.0) * medium.transmittance(2.0);
+        assert!((medium.transmittance(3.0) - split).abs() < 1e-6);
+        // La densidad negativa se trata como vacío
+        assert_eq!(Medium::new(-1.0).transmittance(10.0), 1.0);
+    }
+
+    #[test]
+    fn segment_clips_to_fog_distance() {
+        let medium = Medium::new(0.1);
+        let ray = Ray::new(Vec3::zero(), Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(medium.segment(&ray, 5.0), Some((0.0, 5.0)));
+        assert_eq!(medium.segment(&ray, f32::INFINITY), Some((0.0, MAX_FOG_DISTANCE)));
+    }
+
+    #[test]
+    fn segment_clips_to_region() {
+        let medium = Medium::new(0.1).with_region(Vec3::new(-1.0, -1.0, -1.0), Vec3::new(1.0, 1.0, 1.0));
+        let ray = Ray::new(Vec3::new(-5.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(medium.segment(&ray, f32::INFINITY), Some((4.0, 6.0)));
+        // La superficie golpeada corta el tramo
+        assert_eq!(medium.segment(&ray, 5.0), Some((4.0, 5.0)));
+        assert_eq!(medium.segment(&ray, 3.0), None);
+
+        // Desde dentro de la región el tramo empieza en el origen
+        let inside = Ray::new(Vec3::zero(), Vec3::new(0.0, 0.0, -1.0));
+        assert_eq!(medium.segment(&inside, f32::INFINITY), Some((0.0, 1.0)));
+        let beside = Ray::new(Vec3::new(-5.0, 2.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(medium.segment(&beside, f32::INFINITY), None);
+
+        // Una región más allá de la distancia máxima no se ve
+        let far = Medium::new(0.1).with_region(Vec3::new(25.0, -1.0, -1.0), Vec3::new(30.0, 1.0, 1.0));
+        assert_eq!(far.segment(&Ray::new(Vec3::zero(), Vec3::new(1.0, 0.0, 0.0)), f32::INFINITY), None);
+    }
+}
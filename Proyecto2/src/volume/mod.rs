@@ -0,0 +1,14 @@
+// Medios participantes (niebla y haces de luz volumétricos)
+
+pub mod medium;
+
+pub use medium::Medium;
+
+// Forma de integrar la dispersión simple dentro del medio
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VolumeMode {
+    // Aproximación barata: una muestra por luz en el punto del rayo más cercano a ella, sin sombras
+    SingleScatterApprox,
+    // Ray marching con rayos de sombra por paso (haces de luz con sombras)
+    RayMarched { steps: u32 },
+}